@@ -0,0 +1,250 @@
+//! Assistant API
+//!
+//! Methods for AI assistant threads: the status shown while a reply is
+//! generated, suggested prompts and thread titles.
+//!
+//! Slack sends `assistant_thread_started` when a user opens a thread with
+//! the assistant and `assistant_thread_context_changed` when they switch
+//! channels with the assistant open; both carry an [`AssistantThread`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use slacko::{SlackClient, AuthConfig};
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = SlackClient::new(AuthConfig::bot("xoxb-token"))?;
+//! let (channel, thread_ts) = ("D123", "1700000000.000100");
+//! let chat = client.chat();
+//!
+//! // "is thinking..." stays visible until the stream is finished
+//! client
+//!     .assistant()
+//!     .with_status(channel, thread_ts, "is thinking...", async {
+//!         let stream = chat.start_stream(channel, Some(thread_ts)).await?;
+//!         chat.append_stream(channel, &stream.stream_id, "Hello").await?;
+//!         chat.stop_stream(channel, &stream.stream_id).await
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::SlackClient;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+/// How often [`AssistantApi::with_status`] re-sends the status
+///
+/// Slack clears a status after two minutes without a reply.
+pub const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(90);
+
+/// Assistant API client
+pub struct AssistantApi {
+    client: SlackClient,
+}
+
+impl AssistantApi {
+    pub(crate) fn new(client: SlackClient) -> Self {
+        Self { client }
+    }
+
+    /// Set the status shown in an assistant thread
+    ///
+    /// An empty status clears it. Posting a reply also clears it.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - Channel of the assistant thread
+    /// * `thread_ts` - Timestamp of the thread's parent message
+    /// * `status` - Status text (e.g. "is thinking...")
+    pub async fn set_status(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        status: &str,
+    ) -> Result<AssistantResponse> {
+        let params = SetStatusRequest {
+            channel_id: channel_id.to_string(),
+            thread_ts: thread_ts.to_string(),
+            status: status.to_string(),
+        };
+
+        self.client
+            .post("assistant.threads.setStatus", &params)
+            .await
+    }
+
+    /// Show suggested prompts in an assistant thread
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - Channel of the assistant thread
+    /// * `thread_ts` - Timestamp of the thread's parent message
+    /// * `prompts` - Prompts to suggest (up to four)
+    /// * `title` - Optional heading above the prompts
+    pub async fn set_suggested_prompts(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        prompts: Vec<SuggestedPrompt>,
+        title: Option<&str>,
+    ) -> Result<AssistantResponse> {
+        let params = SetSuggestedPromptsRequest {
+            channel_id: channel_id.to_string(),
+            thread_ts: thread_ts.to_string(),
+            prompts,
+            title: title.map(|s| s.to_string()),
+        };
+
+        self.client
+            .post("assistant.threads.setSuggestedPrompts", &params)
+            .await
+    }
+
+    /// Set the title of an assistant thread
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - Channel of the assistant thread
+    /// * `thread_ts` - Timestamp of the thread's parent message
+    /// * `title` - Title shown in the thread history
+    pub async fn set_title(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        title: &str,
+    ) -> Result<AssistantResponse> {
+        let params = SetTitleRequest {
+            channel_id: channel_id.to_string(),
+            thread_ts: thread_ts.to_string(),
+            title: title.to_string(),
+        };
+
+        self.client
+            .post("assistant.threads.setTitle", &params)
+            .await
+    }
+
+    /// Show a status while `work` runs, then clear it
+    ///
+    /// The status is refreshed every [`STATUS_REFRESH_INTERVAL`] so it stays
+    /// visible during long generations, and cleared whether `work` succeeds
+    /// or fails. Failing to clear is logged rather than returned.
+    pub async fn with_status<F, T>(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        status: &str,
+        work: F,
+    ) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        self.set_status(channel_id, thread_ts, status).await?;
+
+        let mut refresh = tokio::time::interval_at(
+            tokio::time::Instant::now() + STATUS_REFRESH_INTERVAL,
+            STATUS_REFRESH_INTERVAL,
+        );
+        tokio::pin!(work);
+        let result = loop {
+            tokio::select! {
+                result = &mut work => break result,
+                _ = refresh.tick() => {
+                    if let Err(e) = self.set_status(channel_id, thread_ts, status).await {
+                        warn!("Failed to refresh assistant status: {}", e);
+                    }
+                }
+            }
+        };
+
+        if let Err(e) = self.set_status(channel_id, thread_ts, "").await {
+            warn!("Failed to clear assistant status: {}", e);
+        }
+        result
+    }
+}
+
+// Event types
+
+/// `assistant_thread_started` event
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssistantThreadStartedEvent {
+    pub assistant_thread: AssistantThread,
+    pub event_ts: Option<String>,
+}
+
+/// `assistant_thread_context_changed` event
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssistantThreadContextChangedEvent {
+    pub assistant_thread: AssistantThread,
+    pub event_ts: Option<String>,
+}
+
+/// An assistant thread and what the user is looking at
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssistantThread {
+    pub user_id: String,
+    /// Channel of the thread (the assistant DM)
+    pub channel_id: String,
+    pub thread_ts: String,
+    #[serde(default)]
+    pub context: AssistantThreadContext,
+}
+
+/// Channel the user had open when the thread started or changed
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AssistantThreadContext {
+    pub channel_id: Option<String>,
+    pub team_id: Option<String>,
+    pub enterprise_id: Option<String>,
+}
+
+/// Prompt suggested to the user in an assistant thread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestedPrompt {
+    /// Label shown to the user
+    pub title: String,
+    /// Message sent when the prompt is clicked
+    pub message: String,
+}
+
+impl SuggestedPrompt {
+    pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+        }
+    }
+}
+
+// Request/Response types
+
+#[derive(Debug, Serialize)]
+pub struct SetStatusRequest {
+    pub channel_id: String,
+    pub thread_ts: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetSuggestedPromptsRequest {
+    pub channel_id: String,
+    pub thread_ts: String,
+    pub prompts: Vec<SuggestedPrompt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetTitleRequest {
+    pub channel_id: String,
+    pub thread_ts: String,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssistantResponse {}
@@ -2,9 +2,12 @@
 //!
 //! Methods for posting, updating, and deleting messages.
 
+use crate::blocks::MessageBuilder;
 use crate::client::SlackClient;
-use crate::error::Result;
-use crate::types::Message;
+use crate::error::{Result, SlackError};
+use crate::scheduled::ScheduledMessages;
+use crate::streaming::MessageStreamBuilder;
+use crate::types::{Message, MessageMetadata};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -50,6 +53,47 @@ impl ChatApi {
         self.client.post("chat.postMessage", &params).await
     }
 
+    /// Post a message with typed metadata
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel ID or name
+    /// * `text` - Message text
+    /// * `event_type` - Metadata event type (e.g. "task_created")
+    /// * `payload` - Metadata payload, serialized as the `event_payload`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use slacko::{SlackClient, AuthConfig};
+    /// # use serde::Serialize;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SlackClient::new(AuthConfig::bot("xoxb-token"))?;
+    /// #[derive(Serialize)]
+    /// struct TaskCreated {
+    ///     id: String,
+    /// }
+    ///
+    /// let task = TaskCreated { id: "TK-1".into() };
+    /// client.chat()
+    ///     .post_with_metadata("C12345", "Task created", "task_created", &task)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn post_with_metadata<T: Serialize>(
+        &self,
+        channel: &str,
+        text: &str,
+        event_type: &str,
+        payload: &T,
+    ) -> Result<PostMessageResponse> {
+        let params = PostMessageRequest::new(channel)
+            .text(text)
+            .message_metadata(MessageMetadata::new(event_type, payload)?);
+        self.client.post("chat.postMessage", &params).await
+    }
+
     /// Post a message with Block Kit blocks
     ///
     /// # Arguments
@@ -109,6 +153,14 @@ impl ChatApi {
         self.client.post("chat.update", &params).await
     }
 
+    /// Update a message with full options
+    pub async fn update_message_with_options(
+        &self,
+        params: UpdateMessageRequest,
+    ) -> Result<UpdateMessageResponse> {
+        self.client.post("chat.update", &params).await
+    }
+
     /// Delete a message
     ///
     /// # Arguments
@@ -182,13 +234,62 @@ impl ChatApi {
             channel: channel.to_string(),
             text: Some(text.to_string()),
             post_at,
-            blocks: None,
-            as_user: None,
+            ..Default::default()
         };
 
         self.client.post("chat.scheduleMessage", &params).await
     }
 
+    /// Schedule a Block Kit message to be sent later
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel ID
+    /// * `message` - Message payload (use MessageBuilder)
+    /// * `post_at` - Unix timestamp when to send
+    ///
+    /// Fails without calling Slack if `message` has fields
+    /// `chat.scheduleMessage` does not accept (such as `icon_emoji`) or
+    /// names a different channel. See [`scheduled`](Self::scheduled) for
+    /// scheduling at a `DateTime`.
+    pub async fn schedule_message_blocks(
+        &self,
+        channel: &str,
+        message: Value,
+        post_at: i64,
+    ) -> Result<ScheduleMessageResponse> {
+        let mut params =
+            serde_json::from_value::<ScheduleMessageRequest>(message).map_err(|e| {
+                SlackError::InvalidParameter(format!("invalid scheduled message: {}", e))
+            })?;
+        if !params.channel.is_empty() && params.channel != channel {
+            return Err(SlackError::InvalidParameter(format!(
+                "scheduled message names channel {} but is scheduled in {}",
+                params.channel, channel
+            )));
+        }
+        params.channel = channel.to_string();
+        params.post_at = post_at;
+
+        self.client.post("chat.scheduleMessage", &params).await
+    }
+
+    /// Schedule a message with full options
+    pub async fn schedule_message_with_options(
+        &self,
+        params: ScheduleMessageRequest,
+    ) -> Result<ScheduleMessageResponse> {
+        self.client.post("chat.scheduleMessage", &params).await
+    }
+
+    /// Manager for scheduled messages across channels
+    ///
+    /// Schedules at a `DateTime` within Slack's 120-day window, lists every
+    /// page, reschedules and cancels by filter.
+    pub fn scheduled(&self) -> ScheduledMessages {
+        ScheduledMessages::new(self.client.clone())
+    }
+
     /// Delete a scheduled message
     ///
     /// # Arguments
@@ -251,9 +352,7 @@ impl ChatApi {
             channel: channel.to_string(),
             ts: ts.to_string(),
             unfurls,
-            user_auth_message: None,
-            user_auth_required: None,
-            user_auth_url: None,
+            ..Default::default()
         };
 
         self.client.post("chat.unfurl", &params).await
@@ -364,6 +463,19 @@ impl ChatApi {
         self.client.post("chat.appendStream", &params).await
     }
 
+    /// Stream a message from text written over time
+    ///
+    /// Returns a builder for a [`MessageStream`](crate::streaming::MessageStream)
+    /// that batches writes into `append_stream` calls, retries rate limits
+    /// and always finalizes with `stop_stream`.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel ID
+    pub fn message_stream(&self, channel: &str) -> MessageStreamBuilder {
+        MessageStreamBuilder::new(self.client.clone(), channel)
+    }
+
     /// Stop/finalize a text stream
     ///
     /// Completes a streaming message and converts it to a regular message.
@@ -384,7 +496,7 @@ impl ChatApi {
 
 // Request/Response types
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PostMessageRequest {
     pub channel: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -501,6 +613,13 @@ impl PostMessageRequest {
         self.metadata = Some(metadata);
         self
     }
+
+    /// Attach event metadata to the message
+    pub fn message_metadata(mut self, metadata: MessageMetadata) -> Self {
+        self.metadata =
+            Some(serde_json::to_value(metadata).expect("MessageMetadata is always serializable"));
+        self
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -565,16 +684,54 @@ pub struct GetPermalinkResponse {
     pub permalink: String,
 }
 
-#[derive(Debug, Serialize)]
+/// Parameters for `chat.scheduleMessage`
+///
+/// Deserializing rejects fields the method does not accept, so message
+/// payloads never lose fields silently.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct ScheduleMessageRequest {
+    #[serde(default)]
     pub channel: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    #[serde(default)]
     pub post_at: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blocks: Option<Vec<Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unfurl_links: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unfurl_media: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_names: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub as_user: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_ts: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_broadcast: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mrkdwn: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+}
+
+impl From<MessageBuilder> for ScheduleMessageRequest {
+    /// A request with the message's content; set `channel` and `post_at`
+    fn from(message: MessageBuilder) -> Self {
+        Self {
+            text: message.text,
+            blocks: message.blocks,
+            thread_ts: message.thread_ts,
+            mrkdwn: message.mrkdwn,
+            ..Self::default()
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -595,22 +752,34 @@ pub struct DeleteScheduledMessageResponse {}
 
 /// Provide custom unfurl behavior for URLs in messages
 ///
+/// Identify the message with `channel` and `ts`, or with `unfurl_id` and
+/// `source` from a `link_shared` event (required for links in the message
+/// composer). Empty `channel` and `ts` are not sent.
+///
 /// # Arguments
 ///
 /// * `channel` - Channel ID
 /// * `ts` - Message timestamp
 /// * `unfurls` - Map of URLs to unfurl definitions
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 pub struct UnfurlRequest {
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub channel: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub ts: String,
     pub unfurls: serde_json::Value,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub unfurl_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub user_auth_message: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user_auth_required: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user_auth_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_auth_blocks: Option<Vec<Value>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -639,7 +808,7 @@ pub struct ScheduledMessagesListResponse {
     pub response_metadata: Option<ResponseMetadata>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ScheduledMessage {
     pub id: String,
     pub channel_id: String,
@@ -649,6 +818,13 @@ pub struct ScheduledMessage {
     pub text: Option<String>,
 }
 
+impl ScheduledMessage {
+    /// When the message will be posted
+    pub fn post_at_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp(self.post_at, 0)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ResponseMetadata {
     #[serde(default)]
@@ -4,6 +4,7 @@
 
 use crate::client::SlackClient;
 use crate::error::Result;
+use crate::thread::Thread;
 use crate::types::{Channel, Message, ResponseMetadata};
 use serde::{Deserialize, Serialize};
 
@@ -153,6 +154,7 @@ impl ConversationsApi {
             oldest: None,
             latest: None,
             inclusive: None,
+            include_all_metadata: None,
         };
 
         self.client.post("conversations.history", &params).await
@@ -183,6 +185,27 @@ impl ConversationsApi {
         self.client.post("conversations.replies", &params).await
     }
 
+    /// Get thread replies with custom parameters
+    pub async fn replies_with_options(
+        &self,
+        params: ConversationRepliesRequest,
+    ) -> Result<ConversationRepliesResponse> {
+        self.client.post("conversations.replies", &params).await
+    }
+
+    /// A handle on the thread rooted at `ts`
+    ///
+    /// See [`Thread`] for loading every page, replying and following new
+    /// replies.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel ID
+    /// * `ts` - Timestamp of the thread's root message
+    pub fn thread(&self, channel: &str, ts: &str) -> Thread {
+        Thread::new(self.client.clone(), channel, ts)
+    }
+
     /// Invite users to a conversation
     ///
     /// # Arguments
@@ -668,7 +691,7 @@ pub struct UnarchiveConversationRequest {
 #[derive(Debug, Deserialize)]
 pub struct UnarchiveConversationResponse {}
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 pub struct ConversationHistoryRequest {
     pub channel: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -681,6 +704,9 @@ pub struct ConversationHistoryRequest {
     pub latest: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inclusive: Option<bool>,
+    /// Return metadata posted by every app, not just the calling one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_all_metadata: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
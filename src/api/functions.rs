@@ -0,0 +1,134 @@
+//! Functions API
+//!
+//! Methods for custom workflow functions on Slack's automation platform.
+//! Slack sends a `function_executed` event when a workflow reaches one of
+//! the app's functions; the app reports the result with
+//! `functions.completeSuccess` or `functions.completeError`.
+
+use crate::client::SlackClient;
+use crate::error::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Functions API client
+pub struct FunctionsApi {
+    client: SlackClient,
+}
+
+impl FunctionsApi {
+    pub(crate) fn new(client: SlackClient) -> Self {
+        Self { client }
+    }
+
+    /// Complete a function execution with its output parameters
+    ///
+    /// # Arguments
+    ///
+    /// * `function_execution_id` - Execution ID from the `function_executed` event
+    /// * `outputs` - Output values keyed by output parameter name
+    pub async fn complete_success(
+        &self,
+        function_execution_id: &str,
+        outputs: &impl Serialize,
+    ) -> Result<FunctionsCompleteResponse> {
+        let params = FunctionsCompleteSuccessRequest {
+            function_execution_id: function_execution_id.to_string(),
+            outputs: serde_json::to_value(outputs)?,
+        };
+
+        self.client.post("functions.completeSuccess", &params).await
+    }
+
+    /// Mark a function execution as failed
+    ///
+    /// # Arguments
+    ///
+    /// * `function_execution_id` - Execution ID from the `function_executed` event
+    /// * `error` - Message shown to the workflow's user
+    pub async fn complete_error(
+        &self,
+        function_execution_id: &str,
+        error: &str,
+    ) -> Result<FunctionsCompleteResponse> {
+        let params = FunctionsCompleteErrorRequest {
+            function_execution_id: function_execution_id.to_string(),
+            error: error.to_string(),
+        };
+
+        self.client.post("functions.completeError", &params).await
+    }
+}
+
+// Event types
+
+/// `function_executed` event
+#[derive(Debug, Clone, Deserialize)]
+pub struct FunctionExecutedEvent {
+    pub function: FunctionDefinition,
+    /// Input values keyed by input parameter name
+    #[serde(default)]
+    pub inputs: serde_json::Map<String, Value>,
+    pub function_execution_id: String,
+    pub workflow_execution_id: Option<String>,
+    pub event_ts: Option<String>,
+    /// Short-lived token for API calls made on behalf of this execution
+    pub bot_access_token: Option<String>,
+}
+
+impl FunctionExecutedEvent {
+    /// A single input value
+    pub fn input(&self, name: &str) -> Option<&Value> {
+        self.inputs.get(name)
+    }
+
+    /// Deserialize the inputs into a typed struct
+    pub fn inputs_as<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_value(Value::Object(self.inputs.clone()))?)
+    }
+}
+
+/// Definition of the executed function
+#[derive(Debug, Clone, Deserialize)]
+pub struct FunctionDefinition {
+    pub id: String,
+    pub callback_id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub function_type: Option<String>,
+    pub app_id: Option<String>,
+    #[serde(default)]
+    pub input_parameters: Vec<FunctionParameter>,
+    #[serde(default)]
+    pub output_parameters: Vec<FunctionParameter>,
+}
+
+/// Input or output parameter of a function
+#[derive(Debug, Clone, Deserialize)]
+pub struct FunctionParameter {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub parameter_type: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_required: bool,
+}
+
+// Request/Response types
+
+#[derive(Debug, Serialize)]
+pub struct FunctionsCompleteSuccessRequest {
+    pub function_execution_id: String,
+    pub outputs: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionsCompleteErrorRequest {
+    pub function_execution_id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FunctionsCompleteResponse {}
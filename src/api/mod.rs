@@ -3,6 +3,7 @@
 pub mod admin;
 pub mod api_test;
 pub mod apps;
+pub mod assistant;
 pub mod auth;
 pub mod bookmarks;
 pub mod bots;
@@ -13,6 +14,7 @@ pub mod dialog;
 pub mod dnd;
 pub mod emoji;
 pub mod files;
+pub mod functions;
 pub mod lists;
 pub mod oauth;
 pub mod openid;
@@ -1,34 +1,214 @@
 //! RTM (Real-Time Messaging) API
 //!
 //! Methods for real-time messaging via WebSocket.
+//!
+//! [`RtmApi::session`] opens a long-lived session that yields typed
+//! [`RtmEvent`]s, reconnects when Slack says `goodbye` or the socket fails,
+//! pings the server, and sends `message`, `typing` and `presence_sub`
+//! frames through an [`RtmSender`].
+//!
+//! With stealth credentials (xoxc token and xoxd cookie) sessions connect to
+//! the gateway the Slack web client uses, sending the `d` cookie and the web
+//! client's `Origin` in the WebSocket handshake. Its frames arrive as the
+//! same [`RtmEvent`]s.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use slacko::api::rtm::RtmEvent;
+//! # use slacko::{SlackClient, AuthConfig};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = SlackClient::new(AuthConfig::oauth("token"))?;
+//! let mut session = client.rtm().session().await?;
+//! let sender = session.sender();
+//!
+//! while let Some(event) = session.next().await {
+//!     match event {
+//!         RtmEvent::Message(message) if message.text.as_deref() == Some("ping") => {
+//!             let channel = message.channel.unwrap_or_default();
+//!             sender.typing(&channel).await?;
+//!             let reply = sender.send_message(&channel, "pong").await?;
+//!             println!("Sent at {:?}", reply.ts);
+//!         }
+//!         RtmEvent::PresenceChange(change) => println!("{:?} is {}", change.user, change.presence),
+//!         _ => {}
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
 
+use crate::auth::AuthType;
 use crate::client::SlackClient;
 use crate::error::{Result, SlackError};
-use crate::types::RtmConnectResponse;
-use futures_util::{SinkExt, StreamExt};
+use crate::lifecycle::{ConnectionState, ConnectionStatus, ShutdownHandle, StatusReporter};
+use crate::types::{RtmConnectResponse, SelfInfo, Team};
+use futures_util::{SinkExt, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::header::{HeaderValue, COOKIE, ORIGIN};
+use tokio_tungstenite::{
+    connect_async, tungstenite::Message as WsMessage, MaybeTlsStream, WebSocketStream,
+};
 use tracing::{debug, error, info, warn};
+use url::Url;
+
+/// Default interval between client pings
+pub const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Default time without any frame before a connection is considered dead
+pub const DEFAULT_PING_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time to wait for Slack to reply to an outbound frame
+pub const DEFAULT_REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// WebSocket gateway used by the Slack web client
+pub const STEALTH_GATEWAY_URL: &str = "wss://wss-primary.slack.com/";
+
+/// `Origin` sent by the Slack web client
+const STEALTH_ORIGIN: &str = "https://app.slack.com";
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
 
 /// RTM API client
+#[derive(Clone)]
 pub struct RtmApi {
     client: SlackClient,
+    shutdown: ShutdownHandle,
+    status: StatusReporter,
+    ping_interval: Duration,
+    ping_timeout: Duration,
+    reply_timeout: Duration,
+    gateway_url: String,
 }
 
-/// RTM event types
-#[derive(Debug, Deserialize, Serialize)]
-pub struct RtmEvent {
-    #[serde(rename = "type")]
-    pub event_type: String,
-    #[serde(flatten)]
-    pub data: serde_json::Value,
+// ============================================
+// Events
+// ============================================
+
+/// Typed RTM event
+///
+/// Events without a dedicated variant, or that fail to parse into one,
+/// are kept as [`RtmEvent::Other`].
+#[derive(Debug, Clone)]
+pub enum RtmEvent {
+    /// The connection is ready
+    Hello,
+    /// The server is about to close the connection; the session reconnects
+    Goodbye,
+    /// URL for resuming this session
+    ReconnectUrl { url: String },
+    /// A message was posted, edited or deleted
+    Message(RtmMessageEvent),
+    /// One or more users changed presence
+    PresenceChange(PresenceChangeEvent),
+    /// A user is typing
+    UserTyping(UserTypingEvent),
+    /// A reaction was added
+    ReactionAdded(ReactionEvent),
+    /// A reaction was removed
+    ReactionRemoved(ReactionEvent),
+    /// A channel's read cursor moved
+    ChannelMarked(MarkedEvent),
+    /// A direct message's read cursor moved
+    ImMarked(MarkedEvent),
+    /// A private channel's read cursor moved
+    GroupMarked(MarkedEvent),
+    /// The server reported an error
+    Error(RtmError),
+    /// Any other event
+    Other { event_type: String, data: Value },
+}
+
+impl RtmEvent {
+    /// Parse an event frame
+    pub fn from_value(value: Value) -> Self {
+        let event_type = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let typed = match event_type.as_str() {
+            "hello" => Ok(Self::Hello),
+            "goodbye" => Ok(Self::Goodbye),
+            "reconnect_url" => match value.get("url").and_then(|u| u.as_str()) {
+                Some(url) => Ok(Self::ReconnectUrl {
+                    url: url.to_string(),
+                }),
+                None => Err(serde::de::Error::missing_field("url")),
+            },
+            "message" => serde_json::from_value(value.clone()).map(Self::Message),
+            "presence_change" => serde_json::from_value(value.clone()).map(Self::PresenceChange),
+            "user_typing" => serde_json::from_value(value.clone()).map(Self::UserTyping),
+            "reaction_added" => serde_json::from_value(value.clone()).map(Self::ReactionAdded),
+            "reaction_removed" => serde_json::from_value(value.clone()).map(Self::ReactionRemoved),
+            "channel_marked" => serde_json::from_value(value.clone()).map(Self::ChannelMarked),
+            "im_marked" => serde_json::from_value(value.clone()).map(Self::ImMarked),
+            "group_marked" => serde_json::from_value(value.clone()).map(Self::GroupMarked),
+            "error" => serde_json::from_value(value.get("error").cloned().unwrap_or_default())
+                .map(Self::Error),
+            _ => {
+                return Self::Other {
+                    event_type,
+                    data: value,
+                }
+            }
+        };
+
+        typed.unwrap_or_else(|e: serde_json::Error| {
+            debug!("Keeping {} event untyped: {}", event_type, e);
+            Self::Other {
+                event_type,
+                data: value,
+            }
+        })
+    }
+
+    /// The event's `type`
+    pub fn event_type(&self) -> &str {
+        match self {
+            Self::Hello => "hello",
+            Self::Goodbye => "goodbye",
+            Self::ReconnectUrl { .. } => "reconnect_url",
+            Self::Message(_) => "message",
+            Self::PresenceChange(_) => "presence_change",
+            Self::UserTyping(_) => "user_typing",
+            Self::ReactionAdded(_) => "reaction_added",
+            Self::ReactionRemoved(_) => "reaction_removed",
+            Self::ChannelMarked(_) => "channel_marked",
+            Self::ImMarked(_) => "im_marked",
+            Self::GroupMarked(_) => "group_marked",
+            Self::Error(_) => "error",
+            Self::Other { event_type, .. } => event_type,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RtmEvent {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(Self::from_value)
+    }
 }
 
 /// RTM message event
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct RtmMessageEvent {
     #[serde(rename = "type")]
     pub event_type: String,
+    pub subtype: Option<String>,
     pub channel: Option<String>,
     pub user: Option<String>,
     pub text: Option<String>,
@@ -37,12 +217,130 @@ pub struct RtmMessageEvent {
     pub bot_id: Option<String>,
 }
 
+/// `presence_change` event
+#[derive(Debug, Clone, Deserialize)]
+pub struct PresenceChangeEvent {
+    /// Set when a single user changed presence
+    pub user: Option<String>,
+    /// Set when several users changed presence (batched after `presence_sub`)
+    pub users: Option<Vec<String>>,
+    /// `active` or `away`
+    pub presence: String,
+}
+
+/// `user_typing` event
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserTypingEvent {
+    pub channel: String,
+    pub user: String,
+    pub thread_ts: Option<String>,
+}
+
+/// `reaction_added` / `reaction_removed` event
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReactionEvent {
+    pub user: String,
+    pub reaction: String,
+    pub item_user: Option<String>,
+    /// The reacted item (`{"type": "message", "channel", "ts"}` for messages)
+    pub item: Value,
+    pub event_ts: Option<String>,
+}
+
+/// `channel_marked` / `im_marked` / `group_marked` event
+#[derive(Debug, Clone, Deserialize)]
+pub struct MarkedEvent {
+    pub channel: String,
+    pub ts: String,
+    pub unread_count_display: Option<u32>,
+    pub mention_count_display: Option<u32>,
+}
+
+/// Error reported by the RTM server
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RtmError {
+    pub code: i64,
+    pub msg: String,
+}
+
+/// Slack's reply to an outbound frame, matched by `reply_to`
+#[derive(Debug, Clone, Deserialize)]
+pub struct RtmReply {
+    pub reply_to: u64,
+    pub ok: Option<bool>,
+    pub ts: Option<String>,
+    pub text: Option<String>,
+    pub error: Option<RtmError>,
+}
+
 /// Callback type for RTM message handlers
 pub type MessageHandler = Box<dyn Fn(RtmMessageEvent) + Send + Sync>;
 
+// ============================================
+// RTM API
+// ============================================
+
 impl RtmApi {
     pub(crate) fn new(client: SlackClient) -> Self {
-        Self { client }
+        Self {
+            client,
+            shutdown: ShutdownHandle::new(),
+            status: StatusReporter::new(),
+            ping_interval: DEFAULT_PING_INTERVAL,
+            ping_timeout: DEFAULT_PING_TIMEOUT,
+            reply_timeout: DEFAULT_REPLY_TIMEOUT,
+            gateway_url: STEALTH_GATEWAY_URL.to_string(),
+        }
+    }
+
+    /// Use an existing shutdown handle
+    pub fn with_shutdown(mut self, shutdown: ShutdownHandle) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Handle for gracefully stopping `start()` and sessions
+    ///
+    /// On shutdown the WebSocket is closed cleanly and `start()` returns `Ok(())`.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Watch channel reporting connection state for health checks
+    pub fn status(&self) -> watch::Receiver<ConnectionStatus> {
+        self.status.subscribe()
+    }
+
+    /// Set how often the session pings the server
+    ///
+    /// Defaults to [`DEFAULT_PING_INTERVAL`].
+    pub fn ping_interval(mut self, interval: Duration) -> Self {
+        self.ping_interval = interval;
+        self
+    }
+
+    /// Set how long a connection may go without receiving any frame
+    ///
+    /// Silent connections are replaced. Defaults to [`DEFAULT_PING_TIMEOUT`].
+    pub fn ping_timeout(mut self, timeout: Duration) -> Self {
+        self.ping_timeout = timeout;
+        self
+    }
+
+    /// Set how long [`RtmSender`] waits for replies
+    ///
+    /// Defaults to [`DEFAULT_REPLY_TIMEOUT`].
+    pub fn reply_timeout(mut self, timeout: Duration) -> Self {
+        self.reply_timeout = timeout;
+        self
+    }
+
+    /// Set the web client gateway used with stealth credentials
+    ///
+    /// Defaults to [`STEALTH_GATEWAY_URL`].
+    pub fn gateway_url(mut self, url: impl Into<String>) -> Self {
+        self.gateway_url = url.into();
+        self
     }
 
     /// Connect to the RTM API
@@ -54,8 +352,46 @@ impl RtmApi {
         self.client.get("rtm.connect", &params).await
     }
 
+    /// Open a session that yields every RTM event
+    ///
+    /// The first connection is made before this returns, so bad credentials
+    /// fail here. Afterwards the session reconnects on `goodbye`, closed
+    /// sockets and missed pings until shutdown is requested through
+    /// [`shutdown_handle`](Self::shutdown_handle) or an unrecoverable API
+    /// error occurs (see [`RtmSession::join`]).
+    pub async fn session(&self) -> Result<RtmSession> {
+        self.status.set_state(ConnectionState::Connecting);
+        let (info, ws) = match self.open().await {
+            Ok(opened) => opened,
+            Err(e) => {
+                self.status.set_state(ConnectionState::Closed);
+                return Err(e);
+            }
+        };
+
+        let (events_tx, events_rx) = mpsc::channel(256);
+        let (commands_tx, commands_rx) = mpsc::channel(64);
+        let api = self.clone();
+        let task = tokio::spawn(async move { api.run_session(ws, events_tx, commands_rx).await });
+
+        Ok(RtmSession {
+            self_info: info.self_info,
+            team: info.team,
+            events: events_rx,
+            sender: RtmSender {
+                tx: commands_tx,
+                reply_timeout: self.reply_timeout,
+            },
+            task: Some(task),
+        })
+    }
+
     /// Start an RTM connection and listen for events
     ///
+    /// Calls `on_message` for messages not posted by the connected user or,
+    /// for bot tokens, by the bot itself, reconnecting as needed until
+    /// shutdown.
+    ///
     /// # Arguments
     ///
     /// * `on_message` - Callback function for message events
@@ -76,64 +412,24 @@ impl RtmApi {
     where
         F: Fn(RtmMessageEvent) + Send + Sync + 'static,
     {
-        let rtm_info = self.connect().await?;
-        let ws_url = &rtm_info.url;
-        let bot_id = rtm_info.self_info.id.clone();
-
-        info!("Connecting to RTM WebSocket: {}", ws_url);
-
-        let (ws_stream, _) = connect_async(ws_url)
-            .await
-            .map_err(|e| SlackError::websocket_error(format!("Failed to connect: {}", e)))?;
-
-        info!("RTM WebSocket connected");
-
-        let (mut write, mut read) = ws_stream.split();
+        let own_bot_id = self.own_bot_id().await?;
+        let mut session = self.session().await?;
+        let self_id = session.self_info().id.clone();
 
-        // Message processing loop
-        while let Some(msg) = read.next().await {
-            match msg {
-                Ok(WsMessage::Text(text)) => {
-                    debug!("RTM received: {}", text);
-
-                    if let Ok(event) = serde_json::from_str::<RtmMessageEvent>(&text) {
-                        // Only process message events
-                        if event.event_type == "message" {
-                            // Skip messages from ourselves
-                            if event.bot_id.as_ref() == Some(&bot_id) {
-                                continue;
-                            }
-
-                            // Call the message handler
-                            on_message(event);
-                        }
-                    }
-                }
-                Ok(WsMessage::Close(_)) => {
-                    warn!("RTM WebSocket closed");
-                    break;
-                }
-                Ok(WsMessage::Ping(data)) => {
-                    debug!("RTM received ping");
-                    if let Err(e) = write.send(WsMessage::Pong(data)).await {
-                        error!("Failed to send pong: {}", e);
-                    }
-                }
-                Ok(_) => {
-                    debug!("RTM received other message type");
-                }
-                Err(e) => {
-                    error!("RTM WebSocket error: {}", e);
-                    return Err(SlackError::websocket_error(format!(
-                        "WebSocket error: {}",
-                        e
-                    )));
+        while let Some(event) = session.next().await {
+            if let RtmEvent::Message(message) = event {
+                // Skip messages from ourselves, including `bot_message` posts
+                // which carry only the bot ID
+                if message.user.as_deref() == Some(self_id.as_str())
+                    || (own_bot_id.is_some() && message.bot_id == own_bot_id)
+                {
+                    continue;
                 }
+                on_message(message);
             }
         }
 
-        warn!("RTM connection closed");
-        Ok(())
+        session.join().await
     }
 
     /// Start RTM and filter messages by channel
@@ -155,4 +451,463 @@ impl RtmApi {
         })
         .await
     }
+
+    /// Bot ID behind a bot token, from `auth.test`
+    async fn own_bot_id(&self) -> Result<Option<String>> {
+        match self.client.auth.auth_type {
+            AuthType::Bot { .. } => Ok(self.client.auth().test().await?.bot_id),
+            _ => Ok(None),
+        }
+    }
+
+    /// Fetch a WebSocket URL and connect to it
+    async fn open(&self) -> Result<(RtmConnectResponse, WsStream)> {
+        let info = match self.client.auth.stealth_session() {
+            Some((token, _)) => self.stealth_connect(token).await?,
+            None => self.connect().await?,
+        };
+
+        info!("Connecting to RTM WebSocket");
+        let mut request =
+            info.url.as_str().into_client_request().map_err(|e| {
+                SlackError::websocket_error(format!("Invalid WebSocket URL: {}", e))
+            })?;
+        if let Some((_, cookie)) = self.client.auth.stealth_session() {
+            let headers = request.headers_mut();
+            headers.insert(COOKIE, header_value(&format!("d={}", cookie))?);
+            headers.insert(ORIGIN, HeaderValue::from_static(STEALTH_ORIGIN));
+        }
+
+        let (ws, _) = connect_async(request)
+            .await
+            .map_err(|e| SlackError::websocket_error(format!("Failed to connect: {}", e)))?;
+        info!("RTM WebSocket connected");
+        Ok((info, ws))
+    }
+
+    /// Build the web client gateway URL for a stealth session
+    ///
+    /// `rtm.connect` is not available to browser sessions, so the user and
+    /// team come from `auth.test`.
+    async fn stealth_connect(&self, token: &str) -> Result<RtmConnectResponse> {
+        let identity = self.client.auth().test().await?;
+
+        let mut url = Url::parse(&self.gateway_url)
+            .map_err(|e| SlackError::config_error(format!("Invalid gateway URL: {}", e)))?;
+        url.query_pairs_mut()
+            .append_pair("token", token)
+            .append_pair("gateway_server", &format!("{}-1", identity.team_id))
+            .append_pair("slack_client", "desktop")
+            .append_pair("batch_presence_aware", "1");
+
+        Ok(RtmConnectResponse {
+            url: url.into(),
+            self_info: SelfInfo {
+                id: identity.user_id,
+                name: identity.user,
+            },
+            team: Team {
+                id: identity.team_id,
+                name: identity.team,
+                domain: None,
+                email_domain: None,
+                icon: None,
+            },
+        })
+    }
+
+    /// Keep the session connected until shutdown or an unrecoverable error
+    async fn run_session(
+        &self,
+        first: WsStream,
+        events: mpsc::Sender<RtmEvent>,
+        mut commands: mpsc::Receiver<Outbound>,
+    ) -> Result<()> {
+        let mut ws = Some(first);
+        let mut next_id = 1;
+        let mut backoff = Duration::from_secs(1);
+        let max_backoff = Duration::from_secs(60);
+        let mut attempt = 0;
+
+        let result = loop {
+            if self.shutdown.is_shutdown() {
+                break Ok(());
+            }
+
+            let stream = match ws.take() {
+                Some(stream) => stream,
+                None => match self.open().await {
+                    Ok((_, stream)) => stream,
+                    Err(e) if is_fatal(&e) => {
+                        error!("RTM cannot reconnect: {}", e);
+                        break Err(e);
+                    }
+                    Err(e) => {
+                        warn!("RTM reconnect failed: {}", e);
+                        attempt += 1;
+                        self.status
+                            .set_state(ConnectionState::Reconnecting { attempt });
+                        self.sleep_unless_shutdown(backoff).await;
+                        backoff = (backoff * 2).min(max_backoff);
+                        continue;
+                    }
+                },
+            };
+
+            self.status.connection_opened();
+            let end = self
+                .run_connection(stream, &events, &mut commands, &mut next_id)
+                .await;
+            self.status.connection_closed();
+
+            match end {
+                ConnectionEnd::Shutdown | ConnectionEnd::Abandoned => break Ok(()),
+                ConnectionEnd::Goodbye => {
+                    info!("RTM server said goodbye, reconnecting");
+                    backoff = Duration::from_secs(1);
+                }
+                ConnectionEnd::Closed => {
+                    info!("RTM connection closed, reconnecting");
+                    backoff = Duration::from_secs(1);
+                }
+                ConnectionEnd::Failed(e) => {
+                    warn!("RTM connection error: {}, reconnecting...", e);
+                    self.sleep_unless_shutdown(backoff).await;
+                    backoff = (backoff * 2).min(max_backoff);
+                }
+            }
+            attempt += 1;
+            self.status
+                .set_state(ConnectionState::Reconnecting { attempt });
+        };
+
+        self.status.set_state(ConnectionState::Closed);
+        result
+    }
+
+    /// Read events and write outbound frames on one connection
+    async fn run_connection(
+        &self,
+        ws: WsStream,
+        events: &mpsc::Sender<RtmEvent>,
+        commands: &mut mpsc::Receiver<Outbound>,
+        next_id: &mut u64,
+    ) -> ConnectionEnd {
+        let (mut write, mut read) = ws.split();
+        let mut pending: HashMap<u64, oneshot::Sender<RtmReply>> = HashMap::new();
+        let mut ping = tokio::time::interval_at(
+            tokio::time::Instant::now() + self.ping_interval,
+            self.ping_interval,
+        );
+        let mut last_seen = Instant::now();
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown.wait() => {
+                    info!("RTM shutting down");
+                    self.status.set_state(ConnectionState::ShuttingDown);
+                    if let Err(e) = write.send(WsMessage::Close(None)).await {
+                        debug!("Failed to send close frame: {}", e);
+                    }
+                    return ConnectionEnd::Shutdown;
+                }
+                command = commands.recv() => {
+                    let Some(Outbound { mut frame, reply }) = command else {
+                        return ConnectionEnd::Abandoned;
+                    };
+                    let id = *next_id;
+                    *next_id += 1;
+                    frame["id"] = json!(id);
+                    if let Some(reply) = reply {
+                        pending.insert(id, reply);
+                    }
+                    debug!("RTM sending: {}", frame);
+                    if let Err(e) = write.send(WsMessage::Text(frame.to_string())).await {
+                        return ConnectionEnd::Failed(SlackError::websocket_error(format!(
+                            "Failed to send frame: {}",
+                            e
+                        )));
+                    }
+                }
+                _ = ping.tick() => {
+                    if last_seen.elapsed() > self.ping_timeout {
+                        return ConnectionEnd::Failed(SlackError::websocket_error(format!(
+                            "No frames received for {:?}, connection presumed dead",
+                            last_seen.elapsed()
+                        )));
+                    }
+                    let frame = json!({ "id": *next_id, "type": "ping" });
+                    *next_id += 1;
+                    if let Err(e) = write.send(WsMessage::Text(frame.to_string())).await {
+                        return ConnectionEnd::Failed(SlackError::websocket_error(format!(
+                            "Failed to send ping: {}",
+                            e
+                        )));
+                    }
+                }
+                msg = read.next() => {
+                    last_seen = Instant::now();
+                    match msg {
+                        Some(Ok(WsMessage::Text(text))) => {
+                            debug!("RTM received: {}", text);
+                            self.status.event_received();
+                            let Ok(value) = serde_json::from_str::<Value>(&text) else {
+                                warn!("Failed to parse RTM frame");
+                                continue;
+                            };
+
+                            // Replies to our frames (and pongs) carry reply_to
+                            let reply_type = value.get("type").and_then(|t| t.as_str());
+                            if value.get("reply_to").is_some()
+                                && matches!(reply_type, None | Some("pong"))
+                            {
+                                match serde_json::from_value::<RtmReply>(value) {
+                                    Ok(reply) => {
+                                        if let Some(tx) = pending.remove(&reply.reply_to) {
+                                            let _ = tx.send(reply);
+                                        }
+                                    }
+                                    Err(e) => warn!("Failed to parse RTM reply: {}", e),
+                                }
+                                continue;
+                            }
+
+                            let event = RtmEvent::from_value(value);
+                            let goodbye = matches!(event, RtmEvent::Goodbye);
+                            if events.send(event).await.is_err() {
+                                return ConnectionEnd::Abandoned;
+                            }
+                            if goodbye {
+                                let _ = write.send(WsMessage::Close(None)).await;
+                                return ConnectionEnd::Goodbye;
+                            }
+                        }
+                        Some(Ok(WsMessage::Close(frame))) => {
+                            warn!("RTM WebSocket closed: {:?}", frame);
+                            return ConnectionEnd::Closed;
+                        }
+                        Some(Ok(WsMessage::Ping(data))) => {
+                            debug!("RTM received ping");
+                            if let Err(e) = write.send(WsMessage::Pong(data)).await {
+                                error!("Failed to send pong: {}", e);
+                            }
+                        }
+                        Some(Ok(_)) => {
+                            debug!("RTM received other message type");
+                        }
+                        Some(Err(e)) => {
+                            error!("RTM WebSocket error: {}", e);
+                            return ConnectionEnd::Failed(SlackError::websocket_error(format!(
+                                "WebSocket error: {}",
+                                e
+                            )));
+                        }
+                        None => return ConnectionEnd::Closed,
+                    }
+                }
+            }
+        }
+    }
+
+    async fn sleep_unless_shutdown(&self, duration: Duration) {
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => {}
+            _ = self.shutdown.wait() => {}
+        }
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(value)
+        .map_err(|_| SlackError::config_error("Stealth cookie is not a valid header value"))
+}
+
+/// Errors that reconnecting cannot fix
+fn is_fatal(error: &SlackError) -> bool {
+    matches!(
+        error,
+        SlackError::ApiError { .. } | SlackError::AuthError(_) | SlackError::ConfigError(_)
+    )
+}
+
+/// Why a connection's loop returned
+enum ConnectionEnd {
+    Shutdown,
+    /// The session was dropped
+    Abandoned,
+    Goodbye,
+    Closed,
+    Failed(SlackError),
+}
+
+/// Frame queued by an [`RtmSender`]
+struct Outbound {
+    frame: Value,
+    reply: Option<oneshot::Sender<RtmReply>>,
+}
+
+// ============================================
+// Session
+// ============================================
+
+/// A live RTM session
+///
+/// Yields events as a [`Stream`]; the stream ends after shutdown or an
+/// unrecoverable error. Dropping the session closes the connection.
+pub struct RtmSession {
+    self_info: SelfInfo,
+    team: Team,
+    events: mpsc::Receiver<RtmEvent>,
+    sender: RtmSender,
+    task: Option<JoinHandle<Result<()>>>,
+}
+
+impl RtmSession {
+    /// The connected user
+    pub fn self_info(&self) -> &SelfInfo {
+        &self.self_info
+    }
+
+    /// The connected workspace
+    pub fn team(&self) -> &Team {
+        &self.team
+    }
+
+    /// Handle for sending frames on this session
+    pub fn sender(&self) -> RtmSender {
+        self.sender.clone()
+    }
+
+    /// Wait for the session to end, discarding remaining events
+    ///
+    /// Returns the error that stopped reconnection, if any.
+    pub async fn join(mut self) -> Result<()> {
+        while self.events.recv().await.is_some() {}
+        match self.task.take() {
+            Some(task) => task
+                .await
+                .map_err(|e| SlackError::Other(format!("RTM session task failed: {}", e)))?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Stream for RtmSession {
+    type Item = RtmEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_recv(cx)
+    }
+}
+
+impl Drop for RtmSession {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
+
+impl std::fmt::Debug for RtmSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RtmSession")
+            .field("self_info", &self.self_info)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sends frames on an [`RtmSession`]
+///
+/// Frames sent while the session is reconnecting are delivered on the new
+/// connection. Clones share the session.
+#[derive(Clone, Debug)]
+pub struct RtmSender {
+    tx: mpsc::Sender<Outbound>,
+    reply_timeout: Duration,
+}
+
+impl RtmSender {
+    /// Post a plain-text message and wait for Slack's reply
+    pub async fn send_message(&self, channel: &str, text: &str) -> Result<RtmReply> {
+        self.request(json!({ "type": "message", "channel": channel, "text": text }))
+            .await
+    }
+
+    /// Post a plain-text reply in a thread and wait for Slack's reply
+    pub async fn send_thread_reply(
+        &self,
+        channel: &str,
+        thread_ts: &str,
+        text: &str,
+    ) -> Result<RtmReply> {
+        self.request(json!({
+            "type": "message",
+            "channel": channel,
+            "thread_ts": thread_ts,
+            "text": text
+        }))
+        .await
+    }
+
+    /// Show the connected user as typing in a channel
+    pub async fn typing(&self, channel: &str) -> Result<()> {
+        self.send(json!({ "type": "typing", "channel": channel }))
+            .await
+    }
+
+    /// Subscribe to `presence_change` events for these users
+    ///
+    /// Replaces the previous subscription.
+    pub async fn presence_sub(&self, user_ids: &[&str]) -> Result<()> {
+        self.send(json!({ "type": "presence_sub", "ids": user_ids }))
+            .await
+    }
+
+    /// Ping the server and return the round-trip time
+    pub async fn ping(&self) -> Result<Duration> {
+        let started = Instant::now();
+        self.request(json!({ "type": "ping" })).await?;
+        Ok(started.elapsed())
+    }
+
+    /// Send a frame without waiting for a reply
+    ///
+    /// The `id` field is assigned by the session.
+    pub async fn send(&self, frame: Value) -> Result<()> {
+        self.tx
+            .send(Outbound { frame, reply: None })
+            .await
+            .map_err(|_| SlackError::websocket_error("RTM session closed"))
+    }
+
+    /// Send a frame and wait for the reply carrying its `id`
+    ///
+    /// Fails with an API error when Slack replies with `ok: false`.
+    pub async fn request(&self, frame: Value) -> Result<RtmReply> {
+        let (tx, rx) = oneshot::channel();
+        self.tx
+            .send(Outbound {
+                frame,
+                reply: Some(tx),
+            })
+            .await
+            .map_err(|_| SlackError::websocket_error("RTM session closed"))?;
+
+        let reply = tokio::time::timeout(self.reply_timeout, rx)
+            .await
+            .map_err(|_| {
+                SlackError::websocket_error(format!("No RTM reply within {:?}", self.reply_timeout))
+            })?
+            .map_err(|_| SlackError::websocket_error("RTM connection closed before reply"))?;
+
+        if reply.ok == Some(false) {
+            let message = reply
+                .error
+                .as_ref()
+                .map(|e| e.msg.clone())
+                .unwrap_or_else(|| "Unknown error".to_string());
+            return Err(SlackError::api_error("rtm", message));
+        }
+        Ok(reply)
+    }
 }
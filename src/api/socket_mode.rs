@@ -3,20 +3,77 @@
 //! Socket Mode allows your app to receive events via WebSocket instead of HTTP.
 //! This is useful for apps that can't expose a public HTTP endpoint.
 
+use crate::auth::{AuthConfig, AuthType};
 use crate::client::SlackClient;
 use crate::error::{Result, SlackError};
-use futures_util::{SinkExt, StreamExt};
+use crate::lifecycle::{
+    ConnectionState, ConnectionStatus, DispatchMetrics, HandlerOutcome, HandlerTiming,
+    MetricsReporter, OverflowPolicy, ShutdownHandle, StatusReporter, TaskTracker,
+};
+use crate::replay::{Recorder, Replay, ReplayReport, ReplayedAck};
+use futures_util::future::BoxFuture;
+use futures_util::stream::BoxStream;
+use futures_util::{FutureExt, SinkExt, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, oneshot, watch, OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinHandle;
 use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
 use tracing::{debug, error, info, warn};
 
+/// Slack redelivers an envelope that is not acknowledged within 3 seconds
+pub const DEFAULT_ACK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Slack allows at most 10 concurrent Socket Mode connections per app
+pub const MAX_CONNECTIONS: usize = 10;
+
+/// Default interval between client-side pings
+pub const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Default time without any inbound frame before a connection is considered dead
+pub const DEFAULT_PING_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Number of recent envelope IDs remembered for deduplication
+const DEDUP_CAPACITY: usize = 1024;
+
 /// Socket Mode API client
+#[derive(Clone)]
 pub struct SocketModeApi {
     client: SlackClient,
+    api_client: Option<SlackClient>,
+    ack_timeout: Duration,
+    connections: usize,
+    ping_interval: Duration,
+    ping_timeout: Duration,
+    shutdown: ShutdownHandle,
+    status: StatusReporter,
+    recorder: Option<Recorder>,
+    max_in_flight: Option<usize>,
+    overflow: OverflowPolicy,
+    ordered_by_channel: bool,
+    metrics: MetricsReporter,
+    timing_observer: Option<TimingObserver>,
+}
+
+/// Callback receiving the timing of every handler run
+type TimingObserver = Arc<dyn Fn(&HandlerTiming) + Send + Sync>;
+
+/// Type-erased async event handler used by the connection loop
+pub(crate) type EventHandler = Arc<dyn Fn(SocketModeEvent) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Where the connection loop delivers events
+enum EventSink {
+    /// Run the handler for each event in its own task
+    Handler(EventHandler),
+    /// Forward events to a [`SocketModeStream`] in arrival order
+    Stream(mpsc::Sender<SocketModeEvent>),
 }
 
 // ============================================
@@ -29,10 +86,16 @@ struct ConnectionOpenRequest {}
 /// Response from apps.connections.open
 #[derive(Debug, Deserialize)]
 pub struct ConnectionOpenResponse {
+    /// `ok` is read by the response envelope; only successful responses get here
+    #[serde(default = "responded_ok")]
     pub ok: bool,
     pub url: String,
 }
 
+fn responded_ok() -> bool {
+    true
+}
+
 // ============================================
 // Socket Mode Event Types
 // ============================================
@@ -41,6 +104,9 @@ pub struct ConnectionOpenResponse {
 #[derive(Debug, Clone, Deserialize)]
 pub struct SocketModeEnvelope {
     /// Unique ID for this envelope - must be acknowledged
+    ///
+    /// Empty for `hello` and `disconnect` messages, which are not acknowledged.
+    #[serde(default)]
     pub envelope_id: String,
 
     /// Type of payload: "events_api", "interactive", "slash_commands", "hello", "disconnect"
@@ -62,6 +128,10 @@ pub struct SocketModeEnvelope {
     /// Reason for retry
     #[serde(default)]
     pub retry_reason: Option<String>,
+
+    /// Disconnect reason ("warning", "refresh_requested", "link_disabled")
+    #[serde(default)]
+    pub reason: Option<String>,
 }
 
 /// Parsed Socket Mode event with typed payload
@@ -78,6 +148,43 @@ pub struct SocketModeEvent {
 
     /// The parsed payload
     pub payload: SocketModePayload,
+
+    /// Retry attempt number (if this is a redelivery)
+    pub retry_attempt: Option<u32>,
+
+    /// Reason for the redelivery (e.g. "timeout")
+    pub retry_reason: Option<String>,
+
+    /// Handle used to acknowledge this envelope
+    pub ack: Ack,
+}
+
+impl SocketModeEvent {
+    /// Channel the event happened in
+    pub fn channel_id(&self) -> Option<&str> {
+        match &self.payload {
+            SocketModePayload::EventsApi(p) => {
+                let event = p.event.as_ref()?;
+                event
+                    .get("channel")
+                    .and_then(|c| c.as_str().or_else(|| c.get("id")?.as_str()))
+                    .or_else(|| event.get("item")?.get("channel")?.as_str())
+            }
+            SocketModePayload::Interactive(p) => p.channel.as_ref().map(|c| c.id.as_str()),
+            SocketModePayload::SlashCommand(p) => Some(&p.channel_id),
+            _ => None,
+        }
+    }
+
+    /// What the envelope carries: the event type, interaction type or command
+    pub fn kind(&self) -> Option<&str> {
+        match &self.payload {
+            SocketModePayload::EventsApi(p) => p.event.as_ref()?.get("type")?.as_str(),
+            SocketModePayload::Interactive(p) => Some(&p.interaction_type),
+            SocketModePayload::SlashCommand(p) => Some(&p.command),
+            _ => None,
+        }
+    }
 }
 
 /// Types of Socket Mode events
@@ -159,6 +266,8 @@ pub struct EventsApiPayload {
 }
 
 /// Interactive component payload
+///
+/// Use [`interaction`](Self::interaction) for a typed view of the payload.
 #[derive(Debug, Clone, Deserialize)]
 pub struct InteractivePayload {
     /// Type of interaction
@@ -199,7 +308,7 @@ pub struct InteractivePayload {
 }
 
 /// User in interactive payload
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractiveUser {
     pub id: String,
     #[serde(default)]
@@ -211,7 +320,7 @@ pub struct InteractiveUser {
 }
 
 /// Channel in interactive payload
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractiveChannel {
     pub id: String,
     #[serde(default)]
@@ -270,13 +379,444 @@ struct SocketModeAck {
     payload: Option<Value>,
 }
 
+/// Acknowledgement handle for a Socket Mode envelope
+///
+/// Every envelope (except `hello`) must be acknowledged within
+/// [`DEFAULT_ACK_TIMEOUT`]. Acknowledge as soon as possible, optionally with
+/// a response payload, and continue the slow work afterwards.
+///
+/// The handle is cheap to clone; only the first acknowledgement is sent.
+/// If every clone is dropped without acknowledging, a warning is logged and
+/// the envelope is acknowledged with an empty payload.
+#[derive(Clone)]
+pub struct Ack {
+    inner: Arc<AckInner>,
+}
+
+struct AckInner {
+    envelope_id: String,
+    accepts_response_payload: bool,
+    tx: Mutex<Option<oneshot::Sender<Option<Value>>>>,
+}
+
+impl Ack {
+    /// Create a handle along with the receiver that resolves when it is acknowledged
+    ///
+    /// The connection loop creates these for every envelope; this is exposed
+    /// for custom transports and for testing handlers.
+    pub fn new(
+        envelope_id: impl Into<String>,
+        accepts_response_payload: bool,
+    ) -> (Self, oneshot::Receiver<Option<Value>>) {
+        let (tx, rx) = oneshot::channel();
+        let ack = Self {
+            inner: Arc::new(AckInner {
+                envelope_id: envelope_id.into(),
+                accepts_response_payload,
+                tx: Mutex::new(Some(tx)),
+            }),
+        };
+        (ack, rx)
+    }
+
+    /// Create a handle for envelopes that do not need acknowledging (e.g. hello)
+    pub(crate) fn noop(envelope_id: impl Into<String>) -> Self {
+        Self {
+            inner: Arc::new(AckInner {
+                envelope_id: envelope_id.into(),
+                accepts_response_payload: false,
+                tx: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// The envelope ID this handle acknowledges
+    pub fn envelope_id(&self) -> &str {
+        &self.inner.envelope_id
+    }
+
+    /// Whether Slack accepts a response payload for this envelope
+    pub fn accepts_response_payload(&self) -> bool {
+        self.inner.accepts_response_payload
+    }
+
+    /// Whether the envelope has already been acknowledged
+    pub fn is_acked(&self) -> bool {
+        self.inner.tx.lock().map(|tx| tx.is_none()).unwrap_or(true)
+    }
+
+    /// Acknowledge the envelope without a response payload
+    ///
+    /// Returns `true` if this call delivered the acknowledgement.
+    pub fn ack(&self) -> bool {
+        self.inner.send(None)
+    }
+
+    /// Acknowledge the envelope with a response payload
+    ///
+    /// The payload is dropped (with a warning) if the envelope does not
+    /// accept response payloads. Returns `true` if this call delivered the
+    /// acknowledgement.
+    pub fn ack_with(&self, payload: Value) -> bool {
+        if !self.inner.accepts_response_payload {
+            warn!(
+                "Envelope {} does not accept a response payload; acknowledging without it",
+                self.inner.envelope_id
+            );
+            return self.inner.send(None);
+        }
+        self.inner.send(Some(payload))
+    }
+
+    /// Acknowledge the envelope with a typed response payload
+    ///
+    /// Use with [`ViewSubmissionResponse`](crate::responder::ViewSubmissionResponse)
+    /// and [`CommandResponse`](crate::responder::CommandResponse). Unlike
+    /// [`ack_with`](Self::ack_with), fails with
+    /// [`SlackError::InvalidParameter`] and leaves the envelope
+    /// unacknowledged if it does not accept a response payload.
+    pub fn ack_response(&self, response: &impl Serialize) -> Result<bool> {
+        if !self.inner.accepts_response_payload {
+            return Err(SlackError::InvalidParameter(format!(
+                "envelope {} does not accept a response payload",
+                self.inner.envelope_id
+            )));
+        }
+        let payload = serde_json::to_value(response)?;
+        Ok(self.inner.send(Some(payload)))
+    }
+}
+
+impl AckInner {
+    fn send(&self, payload: Option<Value>) -> bool {
+        let tx = match self.tx.lock() {
+            Ok(mut tx) => tx.take(),
+            Err(_) => None,
+        };
+
+        match tx {
+            Some(tx) => {
+                if tx.send(payload).is_err() {
+                    warn!(
+                        "Envelope {} acknowledged after the ack deadline",
+                        self.envelope_id
+                    );
+                    return false;
+                }
+                true
+            }
+            None => {
+                debug!("Envelope {} already acknowledged", self.envelope_id);
+                false
+            }
+        }
+    }
+}
+
+impl Drop for AckInner {
+    fn drop(&mut self) {
+        if let Some(tx) = self.tx.get_mut().ok().and_then(|tx| tx.take()) {
+            warn!(
+                "Envelope {} dropped without acknowledgement; acknowledging with empty payload",
+                self.envelope_id
+            );
+            let _ = tx.send(None);
+        }
+    }
+}
+
+impl std::fmt::Debug for Ack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ack")
+            .field("envelope_id", &self.inner.envelope_id)
+            .field(
+                "accepts_response_payload",
+                &self.inner.accepts_response_payload,
+            )
+            .field("acked", &self.is_acked())
+            .finish()
+    }
+}
+
+/// Stream of Socket Mode events returned by [`SocketModeApi::connect`]
+///
+/// The connection runs in a background task that is stopped when the
+/// stream is dropped. Use [`into_broadcast`](Self::into_broadcast) to fan
+/// events out to several consumers.
+pub struct SocketModeStream {
+    rx: mpsc::Receiver<SocketModeEvent>,
+    task: JoinHandle<()>,
+}
+
+impl SocketModeStream {
+    /// Fan the events out to multiple subscribers
+    ///
+    /// Every subscriber receives a clone of each event. Clones share the
+    /// same [`Ack`], so the first subscriber to acknowledge wins. Subscribers
+    /// that fall more than `capacity` events behind skip the missed events.
+    pub fn into_broadcast(self, capacity: usize) -> SocketModeBroadcast {
+        let (tx, _) = broadcast::channel(capacity);
+        let forward_tx = tx.clone();
+        let mut stream = self;
+
+        let task = tokio::spawn(async move {
+            while let Some(event) = stream.next().await {
+                if forward_tx.send(event).is_err() {
+                    debug!("No Socket Mode subscribers, discarding event");
+                }
+            }
+        });
+
+        SocketModeBroadcast { tx, task }
+    }
+}
+
+impl Stream for SocketModeStream {
+    type Item = SocketModeEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for SocketModeStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Broadcast fan-out of a Socket Mode connection
+///
+/// Created with [`SocketModeStream::into_broadcast`]. Dropping it closes the
+/// connection and ends all subscriber streams.
+pub struct SocketModeBroadcast {
+    tx: broadcast::Sender<SocketModeEvent>,
+    task: JoinHandle<()>,
+}
+
+impl SocketModeBroadcast {
+    /// Subscribe to events received after this call
+    pub fn subscribe(&self) -> BoxStream<'static, SocketModeEvent> {
+        futures_util::stream::unfold(self.tx.subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => return Some((event, rx)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Socket Mode subscriber lagged, skipped {} events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// Number of active subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.tx.receiver_count()
+    }
+}
+
+impl Drop for SocketModeBroadcast {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
 // ============================================
 // Socket Mode Client Implementation
 // ============================================
 
 impl SocketModeApi {
     pub(crate) fn new(client: SlackClient) -> Self {
-        Self { client }
+        Self {
+            client,
+            api_client: None,
+            ack_timeout: DEFAULT_ACK_TIMEOUT,
+            connections: 1,
+            ping_interval: DEFAULT_PING_INTERVAL,
+            ping_timeout: DEFAULT_PING_TIMEOUT,
+            shutdown: ShutdownHandle::new(),
+            status: StatusReporter::new(),
+            recorder: None,
+            max_in_flight: None,
+            overflow: OverflowPolicy::default(),
+            ordered_by_channel: false,
+            metrics: MetricsReporter::default(),
+            timing_observer: None,
+        }
+    }
+
+    /// Create a Socket Mode runner from an app-level token
+    ///
+    /// The app-level token (xapp-...) is only used to open connections;
+    /// handlers started with [`start_with_client`](Self::start_with_client)
+    /// receive `api_client` for Web API calls.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use slacko::{SlackClient, AuthConfig};
+    /// # use slacko::api::socket_mode::SocketModeApi;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bot = SlackClient::new(AuthConfig::bot("xoxb-token"))?;
+    /// let socket_mode = SocketModeApi::from_app_token("xapp-token", bot)?;
+    ///
+    /// socket_mode.start_with_client(|event, client| async move {
+    ///     event.ack.ack();
+    ///     let _ = client.chat().post_message("C12345", "Hello!").await;
+    /// }).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_app_token(app_token: impl Into<String>, api_client: SlackClient) -> Result<Self> {
+        let app_client = SlackClient::new(AuthConfig::app(app_token))?;
+        Ok(Self::new(app_client).with_api_client(api_client))
+    }
+
+    /// Create a Socket Mode runner from environment variables
+    ///
+    /// Reads the app-level token from `SLACK_APP_TOKEN` and the Web API
+    /// credentials handed to handlers via [`AuthConfig::from_env`]. Fails if
+    /// those resolve to the app-level token, which cannot call the Web API.
+    pub fn from_env() -> Result<Self> {
+        let app_client = SlackClient::new(AuthConfig::app_from_env()?)?;
+        let auth = AuthConfig::from_env()?;
+        if matches!(auth.auth_type, AuthType::App { .. }) {
+            return Err(SlackError::config_error(
+                "Socket Mode handlers need a bot or user token. Set SLACK_BOT_TOKEN, SLACK_XOXP_TOKEN, or SLACK_XOXC_TOKEN + SLACK_XOXD_COOKIE alongside SLACK_APP_TOKEN",
+            ));
+        }
+        let api_client = SlackClient::new(auth)?;
+        Ok(Self::new(app_client).with_api_client(api_client))
+    }
+
+    /// Set the client handed to handlers for Web API calls
+    pub fn with_api_client(mut self, client: SlackClient) -> Self {
+        self.api_client = Some(client);
+        self
+    }
+
+    /// Client handed to handlers for Web API calls
+    ///
+    /// Falls back to the connection client when no separate API client was set.
+    pub fn api_client(&self) -> &SlackClient {
+        self.api_client.as_ref().unwrap_or(&self.client)
+    }
+
+    /// Use an existing shutdown handle
+    ///
+    /// Useful for stopping several runners with one signal.
+    pub fn with_shutdown(mut self, shutdown: ShutdownHandle) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Handle for gracefully stopping the runners started from this client
+    ///
+    /// On shutdown, new envelopes are no longer accepted (Slack redelivers
+    /// them), in-flight handlers are awaited until they finish and their
+    /// acks are sent, and the socket is closed cleanly. The runner then
+    /// returns `Ok(())`.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Watch channel reporting connection state for health checks
+    pub fn status(&self) -> watch::Receiver<ConnectionStatus> {
+        self.status.subscribe()
+    }
+
+    /// Set the deadline for acknowledging envelopes
+    ///
+    /// Envelopes not acknowledged within this duration are acknowledged
+    /// automatically with an empty payload and a warning is logged.
+    /// Defaults to [`DEFAULT_ACK_TIMEOUT`].
+    pub fn ack_timeout(mut self, timeout: Duration) -> Self {
+        self.ack_timeout = timeout;
+        self
+    }
+
+    /// Set the number of parallel connections kept open by the reconnecting runners
+    ///
+    /// Slack delivers each envelope to one of the app's open connections, so
+    /// several connections provide redundancy during reconnects. Envelopes
+    /// delivered more than once are deduplicated by envelope ID. Clamped to
+    /// `1..=`[`MAX_CONNECTIONS`].
+    pub fn connections(mut self, count: usize) -> Self {
+        if !(1..=MAX_CONNECTIONS).contains(&count) {
+            warn!(
+                "Socket Mode supports 1 to {} connections, got {}",
+                MAX_CONNECTIONS, count
+            );
+        }
+        self.connections = count.clamp(1, MAX_CONNECTIONS);
+        self
+    }
+
+    /// Set how often the client pings the server
+    ///
+    /// Defaults to [`DEFAULT_PING_INTERVAL`].
+    pub fn ping_interval(mut self, interval: Duration) -> Self {
+        self.ping_interval = interval;
+        self
+    }
+
+    /// Set how long a connection may go without receiving any frame
+    ///
+    /// Connections that stay silent longer than this are considered dead and
+    /// are replaced. Defaults to [`DEFAULT_PING_TIMEOUT`].
+    pub fn ping_timeout(mut self, timeout: Duration) -> Self {
+        self.ping_timeout = timeout;
+        self
+    }
+
+    /// Limit how many handlers run at once
+    ///
+    /// Envelopes arriving while `max` handlers are queued or running are
+    /// handled according to the [`overflow`](Self::overflow) policy.
+    /// Unlimited by default.
+    pub fn max_in_flight(mut self, max: usize) -> Self {
+        self.max_in_flight = Some(max.max(1));
+        self
+    }
+
+    /// Set what happens to envelopes beyond [`max_in_flight`](Self::max_in_flight)
+    ///
+    /// Defaults to [`OverflowPolicy::Wait`].
+    pub fn overflow(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    /// Run handlers for events in the same channel one at a time, in arrival order
+    ///
+    /// Events without a channel are not ordered. Off by default.
+    pub fn ordered_by_channel(mut self, ordered: bool) -> Self {
+        self.ordered_by_channel = ordered;
+        self
+    }
+
+    /// Call `observer` with the timing of every handler run
+    pub fn on_handler_timing<F>(mut self, observer: F) -> Self
+    where
+        F: Fn(&HandlerTiming) + Send + Sync + 'static,
+    {
+        self.timing_observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Handler counters and timings for the runners started from this client
+    pub fn metrics(&self) -> DispatchMetrics {
+        self.metrics.snapshot()
+    }
+
+    /// Record every frame read from Slack
+    ///
+    /// See [`replay`](crate::replay) for replaying recordings offline.
+    pub fn record(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
     }
 
     /// Open a Socket Mode connection
@@ -303,7 +843,7 @@ impl SocketModeApi {
     /// ```no_run
     /// # use slacko::{SlackClient, AuthConfig};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = SlackClient::new(AuthConfig::bot("xapp-token"))?;
+    /// # let client = SlackClient::new(AuthConfig::app("xapp-token"))?;
     /// client.socket_mode().start(|event| {
     ///     println!("Received: {:?}", event.envelope_type);
     ///     None // No response payload
@@ -315,8 +855,8 @@ impl SocketModeApi {
     where
         F: Fn(SocketModeEvent) -> Option<Value> + Send + Sync + 'static,
     {
-        let conn = self.open_connection().await?;
-        self.run_connection(&conn.url, Arc::new(handler)).await
+        self.run_once(EventSink::Handler(Self::sync_handler(handler)))
+            .await
     }
 
     /// Start Socket Mode with automatic reconnection
@@ -327,129 +867,444 @@ impl SocketModeApi {
     where
         F: Fn(SocketModeEvent) -> Option<Value> + Send + Sync + 'static,
     {
-        let handler = Arc::new(handler);
+        self.reconnect_loop(EventSink::Handler(Self::sync_handler(handler)), None)
+            .await
+    }
+
+    /// Start Socket Mode with an async handler
+    ///
+    /// Each event is dispatched to its own task, so slow handlers never block
+    /// the WebSocket. Handlers should acknowledge through [`SocketModeEvent::ack`]
+    /// as early as possible and continue the heavy work afterwards; envelopes
+    /// that are not acknowledged within the ack timeout are acknowledged
+    /// automatically.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use slacko::{SlackClient, AuthConfig};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SlackClient::new(AuthConfig::app("xapp-token"))?;
+    /// let api_client = client.clone();
+    /// client.socket_mode().start_async(move |event| {
+    ///     let client = api_client.clone();
+    ///     async move {
+    ///         // Acknowledge first, then do the slow work
+    ///         event.ack.ack();
+    ///         let _ = client.chat().post_message("C12345", "Got it!").await;
+    ///     }
+    /// }).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn start_async<F, Fut>(&self, handler: F) -> Result<()>
+    where
+        F: Fn(SocketModeEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.run_once(EventSink::Handler(Self::async_handler(handler)))
+            .await
+    }
+
+    /// Start Socket Mode with an async handler and automatic reconnection
+    ///
+    /// Like `start_async()`, but automatically reconnects if the connection is lost.
+    pub async fn start_async_with_reconnect<F, Fut>(&self, handler: F) -> Result<()>
+    where
+        F: Fn(SocketModeEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.reconnect_loop(EventSink::Handler(Self::async_handler(handler)), None)
+            .await
+    }
+
+    /// Start Socket Mode with an async handler that receives the API client
+    ///
+    /// Like `start_async_with_reconnect()`, but each handler invocation also
+    /// receives [`api_client`](Self::api_client), typically a bot client
+    /// paired with the app-level token used for the connection.
+    pub async fn start_with_client<F, Fut>(&self, handler: F) -> Result<()>
+    where
+        F: Fn(SocketModeEvent, SlackClient) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let client = self.api_client().clone();
+        self.start_async_with_reconnect(move |event| handler(event, client.clone()))
+            .await
+    }
+
+    /// Wrap a synchronous handler whose return value is the ack payload
+    fn sync_handler<F>(handler: F) -> EventHandler
+    where
+        F: Fn(SocketModeEvent) -> Option<Value> + Send + Sync + 'static,
+    {
+        Arc::new(move |event: SocketModeEvent| {
+            let ack = event.ack.clone();
+            match handler(event) {
+                Some(payload) => ack.ack_with(payload),
+                None => ack.ack(),
+            };
+            futures_util::future::ready(()).boxed()
+        })
+    }
+
+    /// Wrap an async handler that acknowledges through the event's [`Ack`]
+    pub(crate) fn async_handler<F, Fut>(handler: F) -> EventHandler
+    where
+        F: Fn(SocketModeEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Arc::new(move |event: SocketModeEvent| handler(event).boxed())
+    }
+
+    /// Connect to Socket Mode and receive events as a stream
+    ///
+    /// Opens the first connection eagerly (so authentication errors surface
+    /// here) and then keeps the connection alive in a background task,
+    /// reconnecting as needed. Each event carries its [`Ack`] handle; events
+    /// dropped without acknowledging are logged and acknowledged with an
+    /// empty payload. Dropping the stream closes the connection.
+    ///
+    /// Events are yielded in the order they arrive on each connection. A
+    /// consumer that falls behind pauses reading from the socket; ack
+    /// deadlines keep running meanwhile. [`max_in_flight`](Self::max_in_flight),
+    /// [`overflow`](Self::overflow) and [`ordered_by_channel`](Self::ordered_by_channel)
+    /// only apply to handlers.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use slacko::{SlackClient, AuthConfig};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SlackClient::new(AuthConfig::app("xapp-token"))?;
+    /// use futures_util::StreamExt;
+    ///
+    /// let mut events = client.socket_mode().connect().await?;
+    /// while let Some(event) = events.next().await {
+    ///     event.ack.ack();
+    ///     println!("Received: {:?}", event.envelope_type);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(&self) -> Result<SocketModeStream> {
+        let conn = self.open_connection().await?;
+        let (tx, rx) = mpsc::channel::<SocketModeEvent>(100);
+
+        let api = self.clone();
+        let task = tokio::spawn(async move {
+            if let Err(e) = api
+                .reconnect_loop(EventSink::Stream(tx), Some(conn.url))
+                .await
+            {
+                error!("Socket Mode stream stopped: {}", e);
+            }
+        });
+
+        Ok(SocketModeStream { rx, task })
+    }
+
+    /// Keep the configured number of connections alive
+    ///
+    /// `url` is an already-opened connection URL to use for the first attempt.
+    async fn reconnect_loop(&self, sink: EventSink, mut url: Option<String>) -> Result<()> {
+        let dispatcher = Arc::new(self.dispatcher(sink));
+        self.status.set_state(ConnectionState::Connecting);
+
+        let slots = (0..self.connections).map(|slot| {
+            let url = if slot == 0 { url.take() } else { None };
+            self.run_slot(slot, dispatcher.clone(), url)
+        });
+
+        let result = futures_util::future::try_join_all(slots).await.map(|_| ());
+        self.status.set_state(ConnectionState::Closed);
+        result
+    }
+
+    /// Keep one connection slot alive until shutdown
+    ///
+    /// When Slack asks for a reconnect, the replacement connection is opened
+    /// before the old one is closed so that no events are lost in between.
+    /// The old socket stays open until acks for its envelopes are sent.
+    /// Failed connections are retried with exponential backoff.
+    async fn run_slot(
+        &self,
+        slot: usize,
+        dispatcher: Arc<Dispatcher>,
+        mut url: Option<String>,
+    ) -> Result<()> {
         let mut backoff = Duration::from_secs(1);
         let max_backoff = Duration::from_secs(60);
+        let mut attempt = 0;
+        let mut current: Option<Connection> = None;
 
         loop {
-            let conn = match self.open_connection().await {
-                Ok(c) => {
+            if self.shutdown.is_shutdown() {
+                // A replaced connection still owes acks for its envelopes
+                if let Some(old) = current.take() {
+                    self.drain_and_close(slot, old, &dispatcher).await;
+                }
+                return Ok(());
+            }
+
+            let url = match url.take() {
+                Some(url) => url,
+                None => match self.open_connection().await {
+                    Ok(c) => c.url,
+                    Err(e) => {
+                        error!("Failed to open Socket Mode connection {}: {}", slot, e);
+                        attempt += 1;
+                        self.status
+                            .set_state(ConnectionState::Reconnecting { attempt });
+                        self.sleep_unless_shutdown(backoff).await;
+                        backoff = (backoff * 2).min(max_backoff);
+                        continue;
+                    }
+                },
+            };
+
+            let mut conn = match self.open_socket(&url, dispatcher.clone()).await {
+                Ok(conn) => {
                     backoff = Duration::from_secs(1); // Reset backoff on successful connection
-                    c
+                    attempt = 0;
+                    conn
                 }
                 Err(e) => {
-                    error!("Failed to open Socket Mode connection: {}", e);
-                    tokio::time::sleep(backoff).await;
+                    warn!("Socket Mode connection {} failed: {}", slot, e);
+                    attempt += 1;
+                    self.status
+                        .set_state(ConnectionState::Reconnecting { attempt });
+                    self.sleep_unless_shutdown(backoff).await;
                     backoff = (backoff * 2).min(max_backoff);
                     continue;
                 }
             };
 
-            match self.run_connection(&conn.url, handler.clone()).await {
-                Ok(()) => {
-                    info!("Socket Mode connection closed normally");
-                    break Ok(());
+            if let Some(old) = current.take() {
+                info!(
+                    "Socket Mode connection {} replaced, closing previous socket after its acks",
+                    slot
+                );
+                old.close();
+            }
+
+            let notice = tokio::select! {
+                notice = conn.notices.recv() => notice,
+                _ = self.shutdown.wait() => {
+                    self.drain_and_close(slot, conn, &dispatcher).await;
+                    return Ok(());
                 }
-                Err(e) => {
-                    warn!("Socket Mode connection error: {}, reconnecting...", e);
-                    tokio::time::sleep(backoff).await;
+            };
+
+            match notice {
+                Some(ConnectionNotice::Disconnect(reason)) if reason == "link_disabled" => {
+                    conn.close();
+                    return Err(SlackError::config_error(
+                        "Socket Mode was disabled for this app (link_disabled)",
+                    ));
+                }
+                Some(ConnectionNotice::Disconnect(reason)) => {
+                    info!(
+                        "Slack requested reconnect of connection {} ({}), opening replacement",
+                        slot, reason
+                    );
+                    current = Some(conn);
+                }
+                Some(ConnectionNotice::Closed(Ok(()))) | None => {
+                    info!("Socket Mode connection {} closed, reconnecting", slot);
+                    attempt += 1;
+                    self.status
+                        .set_state(ConnectionState::Reconnecting { attempt });
+                }
+                Some(ConnectionNotice::Closed(Err(e))) => {
+                    warn!(
+                        "Socket Mode connection {} error: {}, reconnecting...",
+                        slot, e
+                    );
+                    attempt += 1;
+                    self.status
+                        .set_state(ConnectionState::Reconnecting { attempt });
+                    self.sleep_unless_shutdown(backoff).await;
                     backoff = (backoff * 2).min(max_backoff);
                 }
             }
         }
     }
 
-    /// Run a single Socket Mode connection
-    async fn run_connection<F>(&self, url: &str, handler: Arc<F>) -> Result<()>
-    where
-        F: Fn(SocketModeEvent) -> Option<Value> + Send + Sync + 'static,
-    {
-        info!("Connecting to Socket Mode: {}", url);
-
-        let (ws_stream, _) = connect_async(url)
-            .await
-            .map_err(|e| SlackError::websocket_error(format!("Failed to connect: {}", e)))?;
-
-        info!("Socket Mode connected");
+    /// Run a single Socket Mode connection until it closes or shutdown is requested
+    async fn run_once(&self, sink: EventSink) -> Result<()> {
+        self.status.set_state(ConnectionState::Connecting);
+        let conn = self.open_connection().await?;
+        let dispatcher = Arc::new(self.dispatcher(sink));
+        let mut conn = self.open_socket(&conn.url, dispatcher.clone()).await?;
+
+        let result = loop {
+            tokio::select! {
+                notice = conn.notices.recv() => match notice {
+                    Some(ConnectionNotice::Disconnect(reason)) => {
+                        info!("Slack requested disconnect: {}", reason);
+                    }
+                    Some(ConnectionNotice::Closed(result)) => break result,
+                    None => break Ok(()),
+                },
+                _ = self.shutdown.wait() => {
+                    self.drain_and_close(0, conn, &dispatcher).await;
+                    break Ok(());
+                }
+            }
+        };
 
-        let (mut write, mut read) = ws_stream.split();
+        self.status.set_state(ConnectionState::Closed);
+        result
+    }
 
-        // Channel for sending acknowledgments
-        let (ack_tx, mut ack_rx) = mpsc::channel::<SocketModeAck>(100);
+    /// Build the dispatcher shared by this runner's connections
+    ///
+    /// Handler limits and channel ordering do not apply to streams.
+    fn dispatcher(&self, sink: EventSink) -> Dispatcher {
+        let handled = matches!(sink, EventSink::Handler(_));
+        Dispatcher {
+            sink,
+            ack_timeout: self.ack_timeout,
+            seen: Mutex::new(RecentEnvelopes::default()),
+            shutdown: self.shutdown.clone(),
+            status: self.status.clone(),
+            tasks: TaskTracker::default(),
+            recorder: self.recorder.clone(),
+            limit: self
+                .max_in_flight
+                .filter(|_| handled)
+                .map(|max| Arc::new(Semaphore::new(max))),
+            overflow: self.overflow,
+            channel_order: (handled && self.ordered_by_channel).then(ChannelOrder::default),
+            metrics: self.metrics.clone(),
+            timing_observer: self.timing_observer.clone(),
+        }
+    }
 
-        // Spawn task to send acknowledgments
-        let write_task = tokio::spawn(async move {
-            while let Some(ack) = ack_rx.recv().await {
-                let msg =
-                    serde_json::to_string(&ack).expect("SocketModeAck is always serializable");
-                debug!("Sending ack: {}", msg);
-                if let Err(e) = write.send(WsMessage::Text(msg)).await {
-                    error!("Failed to send ack: {}", e);
-                    break;
+    /// Feed recorded frames through the dispatch path used for live connections
+    ///
+    /// No connection is opened. Frames are parsed, deduplicated and handed
+    /// to `handler` as they would be from Slack, with the configured
+    /// [`ack_timeout`](Self::ack_timeout); acknowledgements are collected
+    /// into the report instead of being sent. Returns once every handler
+    /// has finished.
+    pub async fn replay<F, Fut>(&self, replay: Replay, handler: F) -> Result<ReplayReport>
+    where
+        F: Fn(SocketModeEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut dispatcher = self.dispatcher(EventSink::Handler(Self::async_handler(handler)));
+        dispatcher.recorder = None;
+
+        let (out_tx, mut out_rx) = mpsc::channel::<WsMessage>(100);
+        let collector = tokio::spawn(async move {
+            let mut acks = Vec::new();
+            while let Some(msg) = out_rx.recv().await {
+                if let WsMessage::Text(text) = msg {
+                    match serde_json::from_str::<ReplayedAck>(&text) {
+                        Ok(ack) => acks.push(ack),
+                        Err(e) => warn!("Unexpected frame during replay: {}", e),
+                    }
                 }
             }
+            acks
         });
 
-        // Process incoming messages
-        while let Some(msg) = read.next().await {
-            match msg {
-                Ok(WsMessage::Text(text)) => {
-                    debug!("Socket Mode received: {}", text);
-
-                    match serde_json::from_str::<SocketModeEnvelope>(&text) {
-                        Ok(envelope) => {
-                            let event = Self::parse_envelope(envelope.clone());
-
-                            // Always acknowledge (except for hello which doesn't need it)
-                            if event.envelope_type != SocketModeEventType::Hello {
-                                let response = handler(event);
-                                let ack = SocketModeAck {
-                                    envelope_id: envelope.envelope_id,
-                                    payload: response,
-                                };
-                                if ack_tx.send(ack).await.is_err() {
-                                    error!("Failed to queue ack");
-                                    break;
-                                }
-                            } else {
-                                // Just log hello, call handler but don't ack
-                                info!("Socket Mode hello received");
-                                let _ = handler(event);
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to parse Socket Mode message: {}", e);
-                        }
-                    }
-                }
-                Ok(WsMessage::Close(frame)) => {
-                    info!("Socket Mode close frame: {:?}", frame);
+        let acks = TaskTracker::default();
+        let (frames, timing) = replay.into_parts();
+        let started = tokio::time::Instant::now();
+        for frame in &frames {
+            if let Some(delay) = timing.delay(Duration::from_millis(frame.offset_ms)) {
+                tokio::time::sleep_until(started + delay).await;
+            }
+            dispatcher
+                .dispatch(&frame.frame.to_string(), &out_tx, &acks)
+                .await;
+        }
+
+        dispatcher.tasks.wait_idle().await;
+        drop(out_tx);
+        let acks = collector
+            .await
+            .map_err(|e| SlackError::Other(format!("Replay collector failed: {}", e)))?;
+
+        Ok(ReplayReport {
+            frames: frames.len(),
+            acks,
+        })
+    }
+
+    /// Wait for in-flight handlers and acks, then close the socket
+    async fn drain_and_close(&self, slot: usize, mut conn: Connection, dispatcher: &Dispatcher) {
+        self.status.set_state(ConnectionState::ShuttingDown);
+        info!(
+            "Socket Mode connection {} shutting down, waiting for {} in-flight tasks",
+            slot,
+            dispatcher.tasks.len()
+        );
+        dispatcher.tasks.wait_idle().await;
+
+        let mut notices = std::mem::replace(&mut conn.notices, mpsc::unbounded_channel().1);
+        conn.close();
+        let closed = async {
+            while let Some(notice) = notices.recv().await {
+                if matches!(notice, ConnectionNotice::Closed(_)) {
                     break;
                 }
-                Ok(WsMessage::Ping(data)) => {
-                    debug!("Socket Mode ping received, data: {:?}", data);
-                    // Pong is handled automatically by tungstenite
-                }
-                Ok(_) => {
-                    debug!("Socket Mode received other message type");
-                }
-                Err(e) => {
-                    error!("Socket Mode error: {}", e);
-                    write_task.abort();
-                    return Err(SlackError::websocket_error(format!(
-                        "WebSocket error: {}",
-                        e
-                    )));
-                }
             }
+        };
+        if tokio::time::timeout(Duration::from_secs(5), closed)
+            .await
+            .is_err()
+        {
+            warn!("Socket Mode connection {} did not close in time", slot);
         }
+        info!("Socket Mode connection {} closed", slot);
+    }
 
-        write_task.abort();
-        Ok(())
+    /// Sleep for the backoff duration, returning early on shutdown
+    async fn sleep_unless_shutdown(&self, duration: Duration) {
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => {}
+            _ = self.shutdown.wait() => {}
+        }
+    }
+
+    /// Connect a WebSocket and spawn its read loop
+    async fn open_socket(&self, url: &str, dispatcher: Arc<Dispatcher>) -> Result<Connection> {
+        info!("Connecting to Socket Mode: {}", url);
+
+        let (ws_stream, _) = connect_async(url)
+            .await
+            .map_err(|e| SlackError::websocket_error(format!("Failed to connect: {}", e)))?;
+
+        info!("Socket Mode connected");
+        dispatcher.status.connection_opened();
+
+        let (notice_tx, notices) = mpsc::unbounded_channel();
+        let (close_tx, close_rx) = oneshot::channel();
+        let task = tokio::spawn(connection_task(
+            ws_stream,
+            dispatcher,
+            notice_tx,
+            close_rx,
+            self.ping_interval,
+            self.ping_timeout,
+        ));
+
+        Ok(Connection {
+            notices,
+            close: Some(close_tx),
+            task,
+        })
     }
 
     /// Parse a raw envelope into a typed event
-    fn parse_envelope(envelope: SocketModeEnvelope) -> SocketModeEvent {
+    ///
+    /// Used by the connection and HTTP receivers; also handy for feeding
+    /// recorded envelopes to handlers in tests.
+    pub fn parse_envelope(envelope: SocketModeEnvelope, ack: Ack) -> SocketModeEvent {
         let envelope_type = SocketModeEventType::from(envelope.envelope_type.as_str());
 
         let payload = match &envelope_type {
@@ -486,12 +1341,17 @@ impl SocketModeApi {
             SocketModeEventType::Hello => SocketModePayload::Hello,
             SocketModeEventType::Disconnect => {
                 let reason = envelope
-                    .payload
-                    .as_ref()
-                    .and_then(|p| p.get("reason"))
-                    .and_then(|r| r.as_str())
-                    .unwrap_or("unknown")
-                    .to_string();
+                    .reason
+                    .clone()
+                    .or_else(|| {
+                        envelope
+                            .payload
+                            .as_ref()
+                            .and_then(|p| p.get("reason"))
+                            .and_then(|r| r.as_str())
+                            .map(String::from)
+                    })
+                    .unwrap_or_else(|| "unknown".to_string());
                 SocketModePayload::Disconnect { reason }
             }
             SocketModeEventType::Unknown(_) => {
@@ -504,6 +1364,527 @@ impl SocketModeApi {
             envelope_type,
             accepts_response_payload: envelope.accepts_response_payload,
             payload,
+            retry_attempt: envelope.retry_attempt,
+            retry_reason: envelope.retry_reason,
+            ack,
+        }
+    }
+}
+
+// ============================================
+// Connection Internals
+// ============================================
+
+/// Shared state for dispatching envelopes from every open connection
+struct Dispatcher {
+    sink: EventSink,
+    ack_timeout: Duration,
+    seen: Mutex<RecentEnvelopes>,
+    shutdown: ShutdownHandle,
+    status: StatusReporter,
+    tasks: TaskTracker,
+    recorder: Option<Recorder>,
+    limit: Option<Arc<Semaphore>>,
+    overflow: OverflowPolicy,
+    channel_order: Option<ChannelOrder>,
+    metrics: MetricsReporter,
+    timing_observer: Option<TimingObserver>,
+}
+
+impl Dispatcher {
+    /// Parse and dispatch a text frame
+    ///
+    /// Returns the reason when the frame is a disconnect request. Never
+    /// waits for a handler slot, so the read loop keeps answering pings.
+    /// Pending acks are tracked in `acks` so the connection can stay open
+    /// until they are sent.
+    async fn dispatch(
+        &self,
+        text: &str,
+        out: &mpsc::Sender<WsMessage>,
+        acks: &TaskTracker,
+    ) -> Option<String> {
+        self.status.event_received();
+        if let Some(recorder) = &self.recorder {
+            recorder.record(text);
+        }
+
+        let envelope = match serde_json::from_str::<SocketModeEnvelope>(text) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                warn!("Failed to parse Socket Mode message: {}", e);
+                return None;
+            }
+        };
+
+        // hello and disconnect messages are not acknowledged
+        if envelope.envelope_id.is_empty() || envelope.envelope_type == "hello" {
+            if envelope.envelope_type == "hello" {
+                info!("Socket Mode hello received");
+            }
+            let ack = Ack::noop(envelope.envelope_id.clone());
+            let event = SocketModeApi::parse_envelope(envelope, ack);
+            let reason = match &event.payload {
+                SocketModePayload::Disconnect { reason } => Some(reason.clone()),
+                _ => None,
+            };
+            self.deliver(event, Slot::Free).await;
+            return reason;
+        }
+
+        // Unacknowledged envelopes are redelivered by Slack after shutdown
+        if self.shutdown.is_shutdown() {
+            debug!(
+                "Shutting down, leaving envelope {} unacknowledged",
+                envelope.envelope_id
+            );
+            return None;
+        }
+
+        // Dropped envelopes never reach the dedupe set, so redeliveries run
+        let mut slot = Slot::Free;
+        if let Some(limit) = &self.limit {
+            match limit.clone().try_acquire_owned() {
+                Ok(permit) => slot = Slot::Held(permit),
+                Err(_) if self.overflow == OverflowPolicy::Wait => {
+                    slot = Slot::Queued(limit.clone());
+                }
+                Err(_) => {
+                    warn!(
+                        "Handler limit reached, dropping envelope {} ({:?})",
+                        envelope.envelope_id, self.overflow
+                    );
+                    self.metrics.dropped();
+                    if self.overflow == OverflowPolicy::Drop {
+                        let ack = SocketModeAck {
+                            envelope_id: envelope.envelope_id,
+                            payload: None,
+                        };
+                        queue_ack(out, ack);
+                    }
+                    return None;
+                }
+            }
+        }
+
+        let first_delivery = self
+            .seen
+            .lock()
+            .map(|mut seen| seen.insert(&envelope.envelope_id))
+            .unwrap_or(true);
+        if !first_delivery {
+            debug!(
+                "Duplicate envelope {}, acknowledging without dispatch",
+                envelope.envelope_id
+            );
+            let ack = SocketModeAck {
+                envelope_id: envelope.envelope_id,
+                payload: None,
+            };
+            queue_ack(out, ack);
+            return None;
+        }
+
+        let (ack, rx) = Ack::new(
+            envelope.envelope_id.clone(),
+            envelope.accepts_response_payload,
+        );
+        tokio::spawn(self.tasks.track(acks.track(await_ack(
+            envelope.envelope_id.clone(),
+            rx,
+            out.clone(),
+            self.ack_timeout,
+        ))));
+
+        let event = SocketModeApi::parse_envelope(envelope, ack);
+        let reason = match &event.payload {
+            SocketModePayload::Disconnect { reason } => Some(reason.clone()),
+            _ => None,
+        };
+        self.deliver(event, slot).await;
+        reason
+    }
+
+    /// Spawn the handler for an event, or forward it to the stream
+    ///
+    /// Forwarding waits for room in the stream, keeping arrival order.
+    async fn deliver(&self, event: SocketModeEvent, slot: Slot) {
+        match &self.sink {
+            EventSink::Handler(handler) => self.spawn_handler(handler.clone(), event, slot),
+            EventSink::Stream(tx) => {
+                if tx.send(event).await.is_err() {
+                    debug!("Socket Mode stream dropped, discarding event");
+                }
+            }
+        }
+    }
+
+    /// Run the handler in its own task, isolating panics and recording timing
+    ///
+    /// A [`Slot::Queued`] handler waits for a permit after its channel
+    /// turn, so the read loop is never blocked.
+    fn spawn_handler(&self, handler: EventHandler, event: SocketModeEvent, slot: Slot) {
+        let turn = match (&self.channel_order, event.channel_id()) {
+            (Some(order), Some(channel)) => Some(order.enqueue(channel)),
+            _ => None,
+        };
+        let metrics = self.metrics.clone();
+        let observer = self.timing_observer.clone();
+        let ack = event.ack.clone();
+        let envelope_id = event.envelope_id.clone();
+        let kind = event.kind().map(String::from);
+        let queued_at = Instant::now();
+        metrics.queued();
+
+        tokio::spawn(self.tasks.track(async move {
+            let mut turn = turn;
+            if let Some(turn) = &mut turn {
+                turn.wait().await;
+            }
+            // Waiting for the turn first keeps permits with runnable handlers
+            let _permit = match slot {
+                Slot::Free => None,
+                Slot::Held(permit) => Some(permit),
+                Slot::Queued(limit) => {
+                    debug!(
+                        "Handler limit reached, envelope {} waiting for a slot",
+                        envelope_id
+                    );
+                    limit.acquire_owned().await.ok()
+                }
+            };
+            let queued = queued_at.elapsed();
+
+            let started = Instant::now();
+            let run = AssertUnwindSafe(async move { handler(event).await }).catch_unwind();
+            let outcome = match run.await {
+                Ok(()) => HandlerOutcome::Completed,
+                Err(panic) => {
+                    error!(
+                        "Handler panicked on envelope {}: {}",
+                        envelope_id,
+                        panic_message(&panic)
+                    );
+                    ack.ack();
+                    HandlerOutcome::Panicked
+                }
+            };
+            drop(turn);
+
+            let timing = HandlerTiming {
+                envelope_id,
+                kind,
+                queued,
+                duration: started.elapsed(),
+                outcome,
+            };
+            debug!(
+                "Handler for {} ({}) took {:?} after {:?} queued",
+                timing.envelope_id,
+                timing.kind.as_deref().unwrap_or("-"),
+                timing.duration,
+                timing.queued
+            );
+            metrics.finished(&timing);
+            if let Some(observer) = observer {
+                observer(&timing);
+            }
+        }));
+    }
+}
+
+/// A handler's place under the handler limit
+enum Slot {
+    /// No limit applies
+    Free,
+    /// Holding one of the limit's permits
+    Held(OwnedSemaphorePermit),
+    /// Waiting for a permit in the handler task ([`OverflowPolicy::Wait`])
+    Queued(Arc<Semaphore>),
+}
+
+/// Text of a caught panic payload
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("non-string panic")
+}
+
+/// Per-channel queues keeping handlers for one channel in arrival order
+#[derive(Clone, Default)]
+struct ChannelOrder {
+    inner: Arc<Mutex<ChannelQueues>>,
+}
+
+#[derive(Default)]
+struct ChannelQueues {
+    next: u64,
+    /// Completion signal of the last handler queued for each channel
+    tails: HashMap<String, (u64, oneshot::Receiver<()>)>,
+}
+
+impl ChannelOrder {
+    /// Queue a handler behind the previous one for `channel`
+    fn enqueue(&self, channel: &str) -> Turn {
+        let mut queues = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let seq = queues.next;
+        queues.next += 1;
+        let (done, tail) = oneshot::channel();
+        let previous = queues
+            .tails
+            .insert(channel.to_string(), (seq, tail))
+            .map(|(_, rx)| rx);
+        Turn {
+            order: self.clone(),
+            channel: channel.to_string(),
+            seq,
+            previous,
+            _done: done,
+        }
+    }
+}
+
+/// A handler's place in its channel queue; dropping it lets the next one run
+struct Turn {
+    order: ChannelOrder,
+    channel: String,
+    seq: u64,
+    previous: Option<oneshot::Receiver<()>>,
+    _done: oneshot::Sender<()>,
+}
+
+impl Turn {
+    /// Wait until the previous handler for the channel has finished
+    async fn wait(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            // Resolves with an error once the previous turn is dropped
+            let _ = previous.await;
+        }
+    }
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        let mut queues = self.order.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if queues.tails.get(&self.channel).map(|(seq, _)| *seq) == Some(self.seq) {
+            queues.tails.remove(&self.channel);
+        }
+    }
+}
+
+/// Bounded set of recently seen envelope IDs
+#[derive(Default)]
+struct RecentEnvelopes {
+    order: VecDeque<String>,
+    ids: HashSet<String>,
+}
+
+impl RecentEnvelopes {
+    /// Record an envelope ID, returning `false` if it was already seen
+    fn insert(&mut self, id: &str) -> bool {
+        if !self.ids.insert(id.to_string()) {
+            return false;
+        }
+        self.order.push_back(id.to_string());
+        if self.order.len() > DEDUP_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+/// Notifications from a connection's read loop
+enum ConnectionNotice {
+    /// Slack asked for the connection to be replaced
+    Disconnect(String),
+    /// The socket closed, with an error if it failed
+    Closed(Result<()>),
+}
+
+/// A live connection whose read loop runs in a background task
+struct Connection {
+    notices: mpsc::UnboundedReceiver<ConnectionNotice>,
+    close: Option<oneshot::Sender<()>>,
+    task: JoinHandle<()>,
+}
+
+impl Connection {
+    /// Ask the connection to send a close frame once its pending acks are sent
+    fn close(mut self) {
+        if let Some(close) = self.close.take() {
+            let _ = close.send(());
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // Connections dropped without a graceful close are torn down
+        if self.close.is_some() {
+            self.task.abort();
+        }
+    }
+}
+
+/// Read loop for a single connection
+///
+/// Sends client-side pings and treats a connection that receives no frames
+/// within `ping_timeout` as dead. When asked to close, keeps reading until
+/// every ack for this socket's envelopes has been sent (each is bounded by
+/// the ack timeout), then sends a close frame.
+async fn connection_task(
+    ws_stream: tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >,
+    dispatcher: Arc<Dispatcher>,
+    notices: mpsc::UnboundedSender<ConnectionNotice>,
+    mut close_rx: oneshot::Receiver<()>,
+    ping_interval: Duration,
+    ping_timeout: Duration,
+) {
+    let (mut write, mut read) = ws_stream.split();
+
+    // Channel for outgoing frames (acknowledgments, pings, close)
+    let (out_tx, mut out_rx) = mpsc::channel::<WsMessage>(100);
+
+    let mut write_task = tokio::spawn(async move {
+        while let Some(msg) = out_rx.recv().await {
+            let closing = matches!(msg, WsMessage::Close(_));
+            if let Err(e) = write.send(msg).await {
+                error!("Failed to send Socket Mode frame: {}", e);
+                break;
+            }
+            if closing {
+                break;
+            }
+        }
+    });
+
+    let mut ping =
+        tokio::time::interval_at(tokio::time::Instant::now() + ping_interval, ping_interval);
+    let mut last_seen = Instant::now();
+
+    // Acks still pending for envelopes read from this socket
+    let acks = TaskTracker::default();
+    let mut closing = false;
+
+    let result = loop {
+        tokio::select! {
+            _ = &mut close_rx, if !closing => {
+                debug!(
+                    "Closing Socket Mode connection after {} pending acks",
+                    acks.len()
+                );
+                closing = true;
+            }
+            _ = acks.wait_idle(), if closing => {
+                let _ = out_tx.send(WsMessage::Close(None)).await;
+                let _ = tokio::time::timeout(Duration::from_secs(1), &mut write_task).await;
+                break Ok(());
+            }
+            _ = ping.tick() => {
+                if last_seen.elapsed() > ping_timeout {
+                    break Err(SlackError::websocket_error(format!(
+                        "No frames received for {:?}, connection presumed dead",
+                        last_seen.elapsed()
+                    )));
+                }
+                let _ = out_tx.try_send(WsMessage::Ping(Vec::new()));
+            }
+            msg = read.next() => {
+                last_seen = Instant::now();
+                match msg {
+                    Some(Ok(WsMessage::Text(text))) => {
+                        debug!("Socket Mode received: {}", text);
+                        if let Some(reason) = dispatcher.dispatch(&text, &out_tx, &acks).await {
+                            let _ = notices.send(ConnectionNotice::Disconnect(reason));
+                        }
+                        // Waiting for a free handler slot does not count as silence
+                        last_seen = Instant::now();
+                    }
+                    Some(Ok(WsMessage::Close(frame))) => {
+                        info!("Socket Mode close frame: {:?}", frame);
+                        break Ok(());
+                    }
+                    Some(Ok(WsMessage::Ping(data))) => {
+                        debug!("Socket Mode ping received, data: {:?}", data);
+                        // Pong is handled automatically by tungstenite
+                    }
+                    Some(Ok(WsMessage::Pong(_))) => {
+                        debug!("Socket Mode pong received");
+                    }
+                    Some(Ok(_)) => {
+                        debug!("Socket Mode received other message type");
+                    }
+                    Some(Err(e)) => {
+                        error!("Socket Mode error: {}", e);
+                        break Err(SlackError::websocket_error(format!(
+                            "WebSocket error: {}",
+                            e
+                        )));
+                    }
+                    None => break Ok(()),
+                }
+            }
+        }
+    };
+
+    write_task.abort();
+    dispatcher.status.connection_closed();
+    let _ = notices.send(ConnectionNotice::Closed(result));
+}
+
+/// Queue an ack frame on a connection's outgoing channel
+fn queue_ack(out: &mpsc::Sender<WsMessage>, ack: SocketModeAck) {
+    let msg = serde_json::to_string(&ack).expect("SocketModeAck is always serializable");
+    debug!("Sending ack: {}", msg);
+    if out.try_send(WsMessage::Text(msg)).is_err() {
+        error!("Failed to queue ack");
+    }
+}
+
+/// Wait for an envelope to be acknowledged and queue the ack frame
+///
+/// Enforces the ack deadline: if the handler has not acknowledged in
+/// time, the envelope is acknowledged with an empty payload.
+async fn await_ack(
+    envelope_id: String,
+    rx: oneshot::Receiver<Option<Value>>,
+    out: mpsc::Sender<WsMessage>,
+    timeout: Duration,
+) {
+    let started = Instant::now();
+
+    let payload = match tokio::time::timeout(timeout, rx).await {
+        Ok(Ok(payload)) => {
+            debug!(
+                "Envelope {} acknowledged in {:?}",
+                envelope_id,
+                started.elapsed()
+            );
+            payload
+        }
+        Ok(Err(_)) => None,
+        Err(_) => {
+            warn!(
+                "Envelope {} not acknowledged within {:?}; sending empty ack",
+                envelope_id, timeout
+            );
+            None
         }
+    };
+
+    let ack = SocketModeAck {
+        envelope_id,
+        payload,
+    };
+    let msg = serde_json::to_string(&ack).expect("SocketModeAck is always serializable");
+    debug!("Sending ack: {}", msg);
+    if out.send(WsMessage::Text(msg)).await.is_err() {
+        error!("Failed to queue ack, connection closed");
     }
 }
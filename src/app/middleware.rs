@@ -0,0 +1,392 @@
+//! Middleware for [`App`](super::App) listeners
+//!
+//! Middleware runs around listeners and decides whether to continue the
+//! chain by calling [`Next::run`]. Global middleware (registered with
+//! [`App::middleware`](super::App::middleware)) runs once per event before
+//! routing; listener middleware (registered with
+//! [`App::with_middleware`](super::App::with_middleware)) runs only for
+//! that listener.
+//!
+//! Closures become middleware with [`from_fn`]:
+//!
+//! ```no_run
+//! use slacko::app::App;
+//! use slacko::app::middleware::{from_fn, DedupeRetries, IgnoreSelf, ReportErrors};
+//! # use slacko::{AuthConfig, SlackClient};
+//! # let client = SlackClient::new(AuthConfig::bot("xoxb-token")).unwrap();
+//!
+//! let app = App::new(client)
+//!     .middleware(IgnoreSelf::new())
+//!     .middleware(DedupeRetries::new())
+//!     .middleware(ReportErrors::new())
+//!     .command("/deploy", |ctx| async move {
+//!         ctx.ack();
+//!         ctx.respond("Deploying").await
+//!     })
+//!     // Only let the release managers deploy
+//!     .with_middleware(from_fn(|ctx, next| async move {
+//!         match ctx.user_id() {
+//!             Some("U0RELEASE") => next.run(ctx).await,
+//!             _ => Ok(()),
+//!         }
+//!     }));
+//! ```
+
+use super::{Context, Handler};
+use crate::api::socket_mode::SocketModePayload;
+use crate::error::{Result, SlackError};
+use crate::responder::ResponseMessage;
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+use tracing::{debug, warn};
+
+/// Logic that runs around listeners
+pub trait Middleware: Send + Sync + 'static {
+    /// Handle an event, calling `next.run(ctx)` to continue the chain
+    fn call(&self, ctx: Context, next: Next) -> BoxFuture<'static, Result<()>>;
+}
+
+/// Middleware built from a closure with [`from_fn`]
+#[derive(Clone)]
+pub struct FromFn<F>(F);
+
+/// Turn an async closure taking the context and the rest of the chain into middleware
+pub fn from_fn<F, Fut>(f: F) -> FromFn<F>
+where
+    F: Fn(Context, Next) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    FromFn(f)
+}
+
+impl<F, Fut> Middleware for FromFn<F>
+where
+    F: Fn(Context, Next) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    fn call(&self, ctx: Context, next: Next) -> BoxFuture<'static, Result<()>> {
+        (self.0)(ctx, next).boxed()
+    }
+}
+
+/// Middleware in registration order
+pub(crate) type Chain = Arc<Vec<Arc<dyn Middleware>>>;
+
+/// The rest of a middleware chain
+pub struct Next {
+    chain: Chain,
+    index: usize,
+    endpoint: Handler,
+}
+
+impl Next {
+    pub(crate) fn new(chain: Chain, endpoint: Handler) -> Self {
+        Self {
+            chain,
+            index: 0,
+            endpoint,
+        }
+    }
+
+    /// Run the remaining middleware and then the listener
+    pub async fn run(self, ctx: Context) -> Result<()> {
+        match self.chain.get(self.index).cloned() {
+            Some(middleware) => {
+                let next = Next {
+                    chain: self.chain,
+                    index: self.index + 1,
+                    endpoint: self.endpoint,
+                };
+                middleware.call(ctx, next).await
+            }
+            None => (self.endpoint)(ctx).await,
+        }
+    }
+}
+
+impl std::fmt::Debug for Next {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Next")
+            .field("remaining", &(self.chain.len() - self.index))
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================
+// Self-Message Filter
+// ============================================
+
+/// Drops events caused by the app's own bot user
+///
+/// The bot user and bot IDs are looked up once with `auth.test` using the
+/// app's client, unless given with [`with_ids`](Self::with_ids).
+/// `member_joined_channel` and `member_left_channel` always pass, so apps
+/// can react to being added to channels.
+#[derive(Clone, Default)]
+pub struct IgnoreSelf {
+    ids: Arc<OnceCell<SelfIds>>,
+}
+
+#[derive(Debug)]
+struct SelfIds {
+    user_id: String,
+    bot_id: Option<String>,
+}
+
+impl IgnoreSelf {
+    /// Create the filter, resolving the bot's IDs on first use
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the filter with known bot user and bot IDs
+    pub fn with_ids(user_id: impl Into<String>, bot_id: Option<String>) -> Self {
+        let ids = SelfIds {
+            user_id: user_id.into(),
+            bot_id,
+        };
+        Self {
+            ids: Arc::new(OnceCell::new_with(Some(ids))),
+        }
+    }
+
+    async fn is_self(&self, ctx: &Context) -> bool {
+        let Some(event) = ctx.body() else {
+            return false;
+        };
+        if matches!(
+            event.get("type").and_then(|t| t.as_str()),
+            Some("member_joined_channel" | "member_left_channel")
+        ) {
+            return false;
+        }
+
+        let ids = self
+            .ids
+            .get_or_try_init(|| async {
+                let auth = ctx.client().auth().test().await?;
+                Ok::<_, SlackError>(SelfIds {
+                    user_id: auth.user_id,
+                    bot_id: auth.bot_id,
+                })
+            })
+            .await;
+        let ids = match ids {
+            Ok(ids) => ids,
+            Err(e) => {
+                warn!("Could not resolve own bot ID, not filtering: {}", e);
+                return false;
+            }
+        };
+
+        // Edited messages carry the author in the nested message
+        [Some(event), event.get("message")]
+            .into_iter()
+            .flatten()
+            .any(|e| {
+                e.get("user").and_then(|u| u.as_str()) == Some(ids.user_id.as_str())
+                    || (ids.bot_id.is_some()
+                        && e.get("bot_id").and_then(|b| b.as_str()) == ids.bot_id.as_deref())
+            })
+    }
+}
+
+impl Middleware for IgnoreSelf {
+    fn call(&self, ctx: Context, next: Next) -> BoxFuture<'static, Result<()>> {
+        let filter = self.clone();
+        async move {
+            if filter.is_self(&ctx).await {
+                debug!("Ignoring own event in envelope {}", ctx.event().envelope_id);
+                return Ok(());
+            }
+            next.run(ctx).await
+        }
+        .boxed()
+    }
+}
+
+// ============================================
+// Retry Deduplication
+// ============================================
+
+/// Storage for event IDs that have already been handled
+///
+/// Implement this over a shared store (e.g. Redis) to deduplicate across
+/// several app instances.
+pub trait EventStore: Send + Sync + 'static {
+    /// Record an event ID, returning `true` if it had not been seen before
+    fn insert(&self, event_id: &str) -> BoxFuture<'_, Result<bool>>;
+}
+
+/// In-memory [`EventStore`] remembering the most recent event IDs
+pub struct MemoryEventStore {
+    capacity: usize,
+    seen: Mutex<(VecDeque<String>, HashSet<String>)>,
+}
+
+impl MemoryEventStore {
+    /// Remember up to `capacity` event IDs
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            seen: Mutex::new((VecDeque::new(), HashSet::new())),
+        }
+    }
+}
+
+impl Default for MemoryEventStore {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+impl EventStore for MemoryEventStore {
+    fn insert(&self, event_id: &str) -> BoxFuture<'_, Result<bool>> {
+        let mut guard = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        let (order, ids) = &mut *guard;
+        let inserted = ids.insert(event_id.to_string());
+        if inserted {
+            order.push_back(event_id.to_string());
+            while order.len() > self.capacity {
+                if let Some(oldest) = order.pop_front() {
+                    ids.remove(&oldest);
+                }
+            }
+        }
+        futures_util::future::ready(Ok(inserted)).boxed()
+    }
+}
+
+/// Drops redelivered Events API events that were already handled
+///
+/// Slack retries events that were not acknowledged in time; retries carry
+/// the original `event_id`. Events without an `event_id` always pass, as
+/// do events the store fails to record.
+#[derive(Clone)]
+pub struct DedupeRetries {
+    store: Arc<dyn EventStore>,
+}
+
+impl DedupeRetries {
+    /// Deduplicate with an in-memory store
+    pub fn new() -> Self {
+        Self::with_store(MemoryEventStore::default())
+    }
+
+    /// Deduplicate with a custom store
+    pub fn with_store(store: impl EventStore) -> Self {
+        Self {
+            store: Arc::new(store),
+        }
+    }
+}
+
+impl Default for DedupeRetries {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware for DedupeRetries {
+    fn call(&self, ctx: Context, next: Next) -> BoxFuture<'static, Result<()>> {
+        let store = self.store.clone();
+        async move {
+            let event_id = match ctx.payload() {
+                SocketModePayload::EventsApi(p) => p.event_id.clone(),
+                _ => None,
+            };
+            if let Some(event_id) = event_id {
+                match store.insert(&event_id).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        debug!(
+                            "Dropping duplicate event {} (retry {:?}, reason {:?})",
+                            event_id,
+                            ctx.event().retry_attempt,
+                            ctx.event().retry_reason
+                        );
+                        return Ok(());
+                    }
+                    Err(e) => warn!("Event store failed, handling {} anyway: {}", event_id, e),
+                }
+            }
+            next.run(ctx).await
+        }
+        .boxed()
+    }
+}
+
+// ============================================
+// Error Reporting
+// ============================================
+
+type ErrorFormatter = Arc<dyn Fn(&SlackError) -> String + Send + Sync>;
+
+/// Reports listener errors to the user who triggered the event
+///
+/// Posts an ephemeral message in the event's channel, or an ephemeral
+/// response through the response URL when there is no channel. The error
+/// is still returned so it is logged.
+#[derive(Clone)]
+pub struct ReportErrors {
+    formatter: ErrorFormatter,
+}
+
+impl ReportErrors {
+    /// Report errors as `:warning: Something went wrong: {error}`
+    pub fn new() -> Self {
+        Self {
+            formatter: Arc::new(|e| format!(":warning: Something went wrong: {}", e)),
+        }
+    }
+
+    /// Set how errors are turned into message text
+    pub fn formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: Fn(&SlackError) -> String + Send + Sync + 'static,
+    {
+        self.formatter = Arc::new(formatter);
+        self
+    }
+}
+
+impl Default for ReportErrors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware for ReportErrors {
+    fn call(&self, ctx: Context, next: Next) -> BoxFuture<'static, Result<()>> {
+        let formatter = self.formatter.clone();
+        async move {
+            let err = match next.run(ctx.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+
+            let text = formatter(&err);
+            let reported = match (ctx.channel_id(), ctx.user_id()) {
+                (Some(channel), Some(user)) => ctx
+                    .client()
+                    .chat()
+                    .post_ephemeral(channel, user, &text)
+                    .await
+                    .map(|_| ()),
+                _ => {
+                    ctx.respond_with(ResponseMessage::text(text).ephemeral())
+                        .await
+                }
+            };
+            if let Err(e) = reported {
+                warn!("Failed to report error to user: {}", e);
+            }
+            Err(err)
+        }
+        .boxed()
+    }
+}
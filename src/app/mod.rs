@@ -0,0 +1,896 @@
+//! Event routing for Slack apps
+//!
+//! [`App`] routes incoming events to listeners registered by event type,
+//! message pattern, slash command, action ID, callback ID, custom function
+//! or unfurled URL pattern, so handlers no longer match on raw payloads.
+//! The same app runs over Socket Mode or, with the `http-receiver`
+//! feature, the HTTP Events API.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use slacko::app::{App, Regex};
+//! use slacko::api::socket_mode::SocketModeApi;
+//! use slacko::{AuthConfig, SlackClient};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let bot = SlackClient::new(AuthConfig::bot("xoxb-token"))?;
+//!
+//! App::new(bot.clone())
+//!     .event("app_mention", |ctx| async move {
+//!         ctx.say("You rang?").await?;
+//!         Ok(())
+//!     })
+//!     .message(Regex::new(r"^deploy (\w+)$")?, |ctx| async move {
+//!         let target = ctx.matches().get(1).cloned().unwrap_or_default();
+//!         ctx.say(&format!("Deploying {}", target)).await?;
+//!         Ok(())
+//!     })
+//!     .command("/deploy", |ctx| async move {
+//!         ctx.ack();
+//!         ctx.respond("Deploy started").await
+//!     })
+//!     .start_socket_mode(SocketModeApi::from_app_token("xapp-token", bot)?)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod middleware;
+
+use crate::api::assistant::AssistantThread;
+use crate::api::chat::PostMessageResponse;
+use crate::api::functions::FunctionExecutedEvent;
+use crate::api::socket_mode::{SocketModeApi, SocketModeEvent, SocketModePayload};
+use crate::auth::AuthConfig;
+use crate::client::SlackClient;
+use crate::error::{Result, SlackError};
+use crate::interactions::{BlockSuggestion, Interaction};
+use crate::metadata::MessageMetadataPostedEvent;
+use crate::replay::{Replay, ReplayReport};
+use crate::responder::{OptionsResponse, Responder, ResponseMessage};
+use crate::unfurl::{LinkSharedEvent, SharedLink, Unfurl, UnfurlResults, UrlPattern};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::future::Future;
+use std::sync::Arc;
+use tracing::{debug, error, warn};
+
+use middleware::{Chain, Middleware, Next};
+pub use regex::Regex;
+
+/// Boxed async listener
+type Handler = Arc<dyn Fn(Context) -> BoxFuture<'static, Result<()>> + Send + Sync>;
+
+/// Routes Slack events to registered listeners
+///
+/// Listeners run in registration order; every listener whose matcher
+/// accepts an event is invoked. Events API envelopes are acknowledged
+/// before listeners run. Commands and interactions are acknowledged
+/// through [`Context::ack`] or [`Context::ack_with`], and with an empty
+/// payload once the listeners return if they did not acknowledge.
+///
+/// Global [`middleware`](Self::middleware) runs once per event before
+/// routing; [listener middleware](Self::with_middleware) runs before a
+/// single listener. See [`middleware`] for the built-in middleware.
+#[derive(Clone)]
+pub struct App {
+    client: SlackClient,
+    middleware: Chain,
+    listeners: Arc<Vec<Listener>>,
+}
+
+#[derive(Clone)]
+struct Listener {
+    matcher: Matcher,
+    middleware: Chain,
+    handler: Handler,
+}
+
+/// What a listener responds to
+#[derive(Clone)]
+enum Matcher {
+    Event(String),
+    Message(Regex),
+    Command(String),
+    Action(String),
+    ViewSubmission(String),
+    Shortcut(String),
+    Options(String),
+    Function(String),
+    Unfurl(UrlPattern),
+}
+
+impl App {
+    /// Create an app whose listeners make Web API calls with `client`
+    pub fn new(client: SlackClient) -> Self {
+        Self {
+            client,
+            middleware: Arc::default(),
+            listeners: Arc::default(),
+        }
+    }
+
+    /// Client handed to listeners
+    pub fn client(&self) -> &SlackClient {
+        &self.client
+    }
+
+    /// Listen for an Events API event type (e.g. `"app_mention"`, `"reaction_added"`)
+    pub fn event<F, Fut>(self, event_type: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.listen(Matcher::Event(event_type.into()), handler)
+    }
+
+    /// Listen for messages whose text matches a pattern
+    ///
+    /// Capture groups are available from [`Context::matches`].
+    pub fn message<F, Fut>(self, pattern: Regex, handler: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.listen(Matcher::Message(pattern), handler)
+    }
+
+    /// Listen for a slash command (e.g. `"/deploy"`)
+    pub fn command<F, Fut>(self, command: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.listen(Matcher::Command(command.into()), handler)
+    }
+
+    /// Listen for `block_actions` containing an action with this `action_id`
+    pub fn action<F, Fut>(self, action_id: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.listen(Matcher::Action(action_id.into()), handler)
+    }
+
+    /// Listen for `view_submission` of a modal with this `callback_id`
+    pub fn view_submission<F, Fut>(self, callback_id: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.listen(Matcher::ViewSubmission(callback_id.into()), handler)
+    }
+
+    /// Listen for global or message shortcuts with this `callback_id`
+    pub fn shortcut<F, Fut>(self, callback_id: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.listen(Matcher::Shortcut(callback_id.into()), handler)
+    }
+
+    /// Listen for `block_suggestion` options requests from this `action_id`
+    ///
+    /// Reply with an [`OptionsResponse`] through [`Context::ack_response`],
+    /// or use [`options_request`](Self::options_request) to return it.
+    pub fn options<F, Fut>(self, action_id: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.listen(Matcher::Options(action_id.into()), handler)
+    }
+
+    /// Answer external select options requests from this `action_id`
+    ///
+    /// The handler receives the request, with what the user typed in
+    /// [`BlockSuggestion::value`], and the returned options are sent as the
+    /// acknowledgement. If the handler fails, no options are shown.
+    pub fn options_request<F, Fut>(self, action_id: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Context, BlockSuggestion) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<OptionsResponse>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.options(action_id, move |ctx| {
+            let handler = handler.clone();
+            async move {
+                let Some(Interaction::BlockSuggestion(request)) = ctx.interaction() else {
+                    return Ok(());
+                };
+                let response = handler(ctx.clone(), request).await?;
+                ctx.ack_response(&response)?;
+                Ok(())
+            }
+        })
+    }
+
+    /// Run the custom workflow function with this `callback_id`
+    ///
+    /// The handler receives the `function_executed` inputs deserialized into
+    /// `I`. Returned outputs complete the execution with
+    /// `functions.completeSuccess`; an error, or inputs that fail to
+    /// deserialize, complete it with `functions.completeError`. The
+    /// context's client uses the execution's `bot_access_token` when Slack
+    /// provides one; the full event is available from
+    /// [`Context::function_executed`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use slacko::app::App;
+    /// # use slacko::{AuthConfig, SlackClient};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Inputs {
+    ///     user_id: String,
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Outputs {
+    ///     greeting: String,
+    /// }
+    ///
+    /// # let client = SlackClient::new(AuthConfig::bot("xoxb-token")).unwrap();
+    /// let app = App::new(client).function("greet", |_ctx, inputs: Inputs| async move {
+    ///     Ok(Outputs {
+    ///         greeting: format!("Hello <@{}>", inputs.user_id),
+    ///     })
+    /// });
+    /// ```
+    pub fn function<I, O, F, Fut>(self, callback_id: impl Into<String>, handler: F) -> Self
+    where
+        I: DeserializeOwned + Send + 'static,
+        O: Serialize + Send + 'static,
+        F: Fn(Context, I) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<O>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.listen(Matcher::Function(callback_id.into()), move |ctx| {
+            let handler = handler.clone();
+            async move {
+                let Some(execution) = ctx.function_executed() else {
+                    // Matched by callback ID, so the event did not parse
+                    let Some(id) = inner_str(ctx.body(), "function_execution_id") else {
+                        return Ok(());
+                    };
+                    let e = SlackError::InvalidParameter(format!(
+                        "malformed function_executed event for execution {}",
+                        id
+                    ));
+                    complete_function_error(ctx.client(), id, &e).await;
+                    return Err(e);
+                };
+                let ctx = match &execution.bot_access_token {
+                    Some(token) => Context {
+                        client: ctx.client.with_auth(AuthConfig::bot(token.clone())),
+                        ..ctx
+                    },
+                    None => ctx,
+                };
+
+                let outputs = match execution.inputs_as::<I>() {
+                    Ok(inputs) => handler(ctx.clone(), inputs).await,
+                    Err(e) => Err(SlackError::InvalidParameter(format!(
+                        "invalid inputs for function {}: {}",
+                        execution.function.callback_id, e
+                    ))),
+                };
+
+                let functions = ctx.client().functions();
+                match outputs {
+                    Ok(outputs) => {
+                        let outputs = serde_json::to_value(outputs)?;
+                        functions
+                            .complete_success(&execution.function_execution_id, &outputs)
+                            .await?;
+                        Ok(())
+                    }
+                    Err(e) => {
+                        complete_function_error(ctx.client(), &execution.function_execution_id, &e)
+                            .await;
+                        Err(e)
+                    }
+                }
+            }
+        })
+    }
+
+    /// Unfurl links matching a URL pattern in `link_shared` events
+    ///
+    /// `*` in the pattern matches any text; see [`UrlPattern`]. The handler
+    /// runs once per matching link, with the matched text in
+    /// [`Context::matches`]. A link matching several patterns is handled by
+    /// the first one registered. The results of every pattern are sent in
+    /// one `chat.unfurl` call, using `unfurl_id` and `source` when the event
+    /// has them. If any handler returns [`Unfurl::AuthRequired`], a second
+    /// call asks the user to authenticate. Links whose handler fails are
+    /// skipped and the first error is returned after the others are sent.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use slacko::{SlackClient, AuthConfig};
+    /// use slacko::app::App;
+    /// use slacko::unfurl::Unfurl;
+    ///
+    /// # let client = SlackClient::new(AuthConfig::bot("xoxb-token")).unwrap();
+    /// let app = App::new(client).unfurl("https://jira.example.com/browse/*", |ctx, link| async move {
+    ///     let issue = &ctx.matches()[1];
+    ///     Ok(Unfurl::Attachment(serde_json::json!({
+    ///         "title": issue,
+    ///         "title_link": link.url,
+    ///     })))
+    /// });
+    /// ```
+    pub fn unfurl<F, Fut>(self, pattern: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Context, SharedLink) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Unfurl>> + Send + 'static,
+    {
+        let pattern = UrlPattern::new(pattern);
+        let handler = Arc::new(handler);
+        let matcher = Matcher::Unfurl(pattern.clone());
+        self.listen(matcher, move |ctx| {
+            let handler = handler.clone();
+            let pattern = pattern.clone();
+            async move {
+                let Some(event) = ctx.link_shared() else {
+                    return Ok(());
+                };
+
+                let mut first_error = None;
+                for link in &event.links {
+                    let Some(matches) = pattern.captures(&link.url) else {
+                        continue;
+                    };
+                    if !ctx.unfurls.claim(&link.url) {
+                        continue;
+                    }
+                    let link_ctx = Context {
+                        matches,
+                        ..ctx.clone()
+                    };
+                    match handler(link_ctx, link.clone()).await {
+                        Ok(unfurl) => ctx.unfurls.set(&link.url, unfurl),
+                        Err(e) => {
+                            warn!("Unfurl handler for {} failed: {}", link.url, e);
+                            first_error.get_or_insert(e);
+                        }
+                    }
+                }
+                first_error.map_or(Ok(()), Err)
+            }
+        })
+    }
+
+    fn listen<F, Fut>(mut self, matcher: Matcher, handler: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        Arc::make_mut(&mut self.listeners).push(Listener {
+            matcher,
+            middleware: Arc::default(),
+            handler: Arc::new(move |ctx| handler(ctx).boxed()),
+        });
+        self
+    }
+
+    /// Add middleware that runs for every event before routing
+    ///
+    /// Middleware runs in registration order.
+    pub fn middleware(mut self, middleware: impl Middleware) -> Self {
+        Arc::make_mut(&mut self.middleware).push(Arc::new(middleware));
+        self
+    }
+
+    /// Add middleware to the most recently registered listener
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use slacko::app::App;
+    /// use slacko::app::middleware::from_fn;
+    /// # use slacko::{AuthConfig, SlackClient};
+    /// # let client = SlackClient::new(AuthConfig::bot("xoxb-token")).unwrap();
+    /// let app = App::new(client)
+    ///     .command("/deploy", |ctx| async move {
+    ///         ctx.ack();
+    ///         Ok(())
+    ///     })
+    ///     .with_middleware(from_fn(|ctx, next| async move {
+    ///         if ctx.channel_id() == Some("C0DEPLOYS") {
+    ///             next.run(ctx).await
+    ///         } else {
+    ///             Ok(())
+    ///         }
+    ///     }));
+    /// ```
+    pub fn with_middleware(mut self, middleware: impl Middleware) -> Self {
+        match Arc::make_mut(&mut self.listeners).last_mut() {
+            Some(listener) => Arc::make_mut(&mut listener.middleware).push(Arc::new(middleware)),
+            None => warn!("with_middleware() called before any listener was registered"),
+        }
+        self
+    }
+
+    /// Route a single event through the middleware to the matching listeners
+    ///
+    /// Transport-agnostic entry point used by the runners; call it directly
+    /// to drive the app from a custom receiver or from tests.
+    pub async fn dispatch(&self, event: SocketModeEvent) {
+        if matches!(event.payload, SocketModePayload::EventsApi(_)) {
+            event.ack.ack();
+        }
+
+        let listeners = self.listeners.clone();
+        let endpoint: Handler = Arc::new(move |ctx| Self::route(listeners.clone(), ctx).boxed());
+        let ctx = Context::new(self.client.clone(), event.clone());
+
+        if let Err(e) = Next::new(self.middleware.clone(), endpoint).run(ctx).await {
+            warn!("Failed to handle envelope {}: {}", event.envelope_id, e);
+        }
+        event.ack.ack();
+    }
+
+    /// Run every matching listener, returning the first error
+    async fn route(listeners: Arc<Vec<Listener>>, ctx: Context) -> Result<()> {
+        let mut result = Ok(());
+        let mut matched = false;
+
+        for listener in listeners.iter() {
+            let Some(matches) = listener.matcher.matches(ctx.payload()) else {
+                continue;
+            };
+            matched = true;
+
+            let ctx = Context {
+                matches,
+                ..ctx.clone()
+            };
+            let next = Next::new(listener.middleware.clone(), listener.handler.clone());
+            if let Err(e) = next.run(ctx).await {
+                if result.is_ok() {
+                    result = Err(e);
+                } else {
+                    warn!("Listener for {} failed: {}", listener.matcher, e);
+                }
+            }
+        }
+
+        if !matched {
+            debug!("No listener for envelope {}", ctx.event.envelope_id);
+        }
+        if let Err(e) = Self::send_unfurls(&ctx).await {
+            if result.is_ok() {
+                result = Err(e);
+            } else {
+                warn!("Failed to unfurl links: {}", e);
+            }
+        }
+        result
+    }
+
+    /// Send what the unfurl listeners collected for a `link_shared` event
+    async fn send_unfurls(ctx: &Context) -> Result<()> {
+        let results = ctx.unfurls.take();
+        if results.is_empty() {
+            return Ok(());
+        }
+        let Some(event) = ctx.link_shared() else {
+            return Ok(());
+        };
+        let mut result = Ok(());
+        for request in crate::unfurl::unfurl_requests(&event, &results) {
+            if let Err(e) = ctx.client().chat().unfurl_with_options(request).await {
+                if result.is_ok() {
+                    result = Err(e);
+                } else {
+                    warn!("Failed to unfurl links: {}", e);
+                }
+            }
+        }
+        result
+    }
+
+    /// Run the app over Socket Mode with automatic reconnection
+    ///
+    /// Returns when Socket Mode is disabled for the app or shutdown is
+    /// requested through the runner's [`ShutdownHandle`](crate::ShutdownHandle).
+    pub async fn start_socket_mode(self, socket_mode: SocketModeApi) -> Result<()> {
+        let app = Arc::new(self);
+        socket_mode
+            .start_async_with_reconnect(move |event| {
+                let app = app.clone();
+                async move { app.dispatch(event).await }
+            })
+            .await
+    }
+
+    /// Replay recorded Socket Mode frames through the app offline
+    ///
+    /// See [`SocketModeApi::replay`]. Point the app's client at a
+    /// `replay::MockApi` (`test-util` feature) to capture its Web API calls.
+    pub async fn replay(&self, replay: Replay) -> Result<ReplayReport> {
+        let app = Arc::new(self.clone());
+        self.client
+            .socket_mode()
+            .replay(replay, move |event| {
+                let app = app.clone();
+                async move { app.dispatch(event).await }
+            })
+            .await
+    }
+
+    /// Run the app as an HTTP Events API server
+    #[cfg(feature = "http-receiver")]
+    pub async fn start_http(
+        self,
+        receiver: crate::receiver::HttpReceiver,
+        addr: impl tokio::net::ToSocketAddrs,
+    ) -> Result<()> {
+        let app = Arc::new(self);
+        receiver
+            .serve(addr, move |event| {
+                let app = app.clone();
+                async move { app.dispatch(event).await }
+            })
+            .await
+    }
+}
+
+impl Matcher {
+    /// Returns the captured groups when the payload matches
+    fn matches(&self, payload: &SocketModePayload) -> Option<Vec<String>> {
+        let matched = match (self, payload) {
+            (Self::Event(event_type), SocketModePayload::EventsApi(p)) => {
+                inner_str(p.event.as_ref(), "type") == Some(event_type.as_str())
+            }
+            (Self::Message(pattern), SocketModePayload::EventsApi(p)) => {
+                if inner_str(p.event.as_ref(), "type") != Some("message") {
+                    return None;
+                }
+                let text = inner_str(p.event.as_ref(), "text")?;
+                let captures = pattern.captures(text)?;
+                return Some(
+                    captures
+                        .iter()
+                        .map(|m| m.map(|m| m.as_str().to_string()).unwrap_or_default())
+                        .collect(),
+                );
+            }
+            (Self::Command(command), SocketModePayload::SlashCommand(p)) => &p.command == command,
+            (Self::Action(action_id), SocketModePayload::Interactive(p)) => {
+                p.interaction_type == "block_actions"
+                    && p.actions.iter().any(|a| {
+                        a.get("action_id").and_then(|v| v.as_str()) == Some(action_id.as_str())
+                    })
+            }
+            (Self::ViewSubmission(callback_id), SocketModePayload::Interactive(p)) => {
+                p.interaction_type == "view_submission"
+                    && inner_str(p.view.as_ref(), "callback_id") == Some(callback_id.as_str())
+            }
+            (Self::Shortcut(callback_id), SocketModePayload::Interactive(p)) => {
+                matches!(p.interaction_type.as_str(), "shortcut" | "message_action")
+                    && inner_str(Some(&p.extra), "callback_id") == Some(callback_id.as_str())
+            }
+            (Self::Options(action_id), SocketModePayload::Interactive(p)) => {
+                p.interaction_type == "block_suggestion"
+                    && inner_str(Some(&p.extra), "action_id") == Some(action_id.as_str())
+            }
+            (Self::Unfurl(pattern), SocketModePayload::EventsApi(p)) => {
+                let event = p.event.as_ref();
+                inner_str(event, "type") == Some("link_shared")
+                    && event
+                        .and_then(|e| e.get("links")?.as_array())
+                        .is_some_and(|links| {
+                            links
+                                .iter()
+                                .filter_map(|l| l.get("url")?.as_str())
+                                .any(|url| pattern.is_match(url))
+                        })
+            }
+            (Self::Function(callback_id), SocketModePayload::EventsApi(p)) => {
+                let event = p.event.as_ref();
+                inner_str(event, "type") == Some("function_executed")
+                    && inner_str(event.and_then(|e| e.get("function")), "callback_id")
+                        == Some(callback_id.as_str())
+            }
+            _ => false,
+        };
+        matched.then(Vec::new)
+    }
+}
+
+impl std::fmt::Display for Matcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Event(t) => write!(f, "event {}", t),
+            Self::Message(p) => write!(f, "message /{}/", p),
+            Self::Command(c) => write!(f, "command {}", c),
+            Self::Action(id) => write!(f, "action {}", id),
+            Self::ViewSubmission(id) => write!(f, "view_submission {}", id),
+            Self::Shortcut(id) => write!(f, "shortcut {}", id),
+            Self::Options(id) => write!(f, "options {}", id),
+            Self::Function(id) => write!(f, "function {}", id),
+            Self::Unfurl(pattern) => write!(f, "unfurl {}", pattern),
+        }
+    }
+}
+
+impl std::fmt::Debug for App {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let listeners: Vec<String> = self
+            .listeners
+            .iter()
+            .map(|l| l.matcher.to_string())
+            .collect();
+        f.debug_struct("App")
+            .field("listeners", &listeners)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Complete a function execution with `error`, logging if that fails
+///
+/// The handler's error is what gets returned, not the completion's.
+async fn complete_function_error(
+    client: &SlackClient,
+    function_execution_id: &str,
+    error: &SlackError,
+) {
+    if let Err(e) = client
+        .functions()
+        .complete_error(function_execution_id, &error.to_string())
+        .await
+    {
+        error!(
+            "Failed to complete function execution {} with error: {}",
+            function_execution_id, e
+        );
+    }
+}
+
+fn inner_str<'a>(value: Option<&'a Value>, key: &str) -> Option<&'a str> {
+    value?.get(key)?.as_str()
+}
+
+// ============================================
+// Listener Context
+// ============================================
+
+/// Context handed to a listener
+#[derive(Clone)]
+pub struct Context {
+    client: SlackClient,
+    event: SocketModeEvent,
+    matches: Vec<String>,
+    responder: Option<Responder>,
+    unfurls: UnfurlResults,
+}
+
+impl Context {
+    fn new(client: SlackClient, event: SocketModeEvent) -> Self {
+        // Created once per event so every listener shares the use count
+        let responder = response_url(&event.payload).map(|url| client.responder(url));
+        Self {
+            client,
+            event,
+            matches: Vec::new(),
+            responder,
+            unfurls: UnfurlResults::default(),
+        }
+    }
+
+    /// Client for Web API calls
+    pub fn client(&self) -> &SlackClient {
+        &self.client
+    }
+
+    /// The event being handled
+    pub fn event(&self) -> &SocketModeEvent {
+        &self.event
+    }
+
+    /// The typed payload of the event
+    pub fn payload(&self) -> &SocketModePayload {
+        &self.event.payload
+    }
+
+    /// The inner `event` object of an Events API payload
+    pub fn body(&self) -> Option<&Value> {
+        match &self.event.payload {
+            SocketModePayload::EventsApi(p) => p.event.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The typed interaction, for interactive payloads
+    pub fn interaction(&self) -> Option<Interaction> {
+        match &self.event.payload {
+            SocketModePayload::Interactive(p) => match p.interaction() {
+                Ok(interaction) => Some(interaction),
+                Err(e) => {
+                    warn!("Failed to parse {} payload: {}", p.interaction_type, e);
+                    None
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// The typed `function_executed` event, for custom function executions
+    pub fn function_executed(&self) -> Option<FunctionExecutedEvent> {
+        let body = self.body()?;
+        if body.get("type")?.as_str()? != "function_executed" {
+            return None;
+        }
+        match serde_json::from_value(body.clone()) {
+            Ok(event) => Some(event),
+            Err(e) => {
+                warn!("Failed to parse function_executed event: {}", e);
+                None
+            }
+        }
+    }
+
+    /// The typed `message_metadata_posted` event
+    ///
+    /// Parse its payload with a [`MetadataRegistry`](crate::metadata::MetadataRegistry).
+    pub fn metadata_posted(&self) -> Option<MessageMetadataPostedEvent> {
+        let body = self.body()?;
+        if body.get("type")?.as_str()? != "message_metadata_posted" {
+            return None;
+        }
+        match serde_json::from_value(body.clone()) {
+            Ok(event) => Some(event),
+            Err(e) => {
+                warn!("Failed to parse message_metadata_posted event: {}", e);
+                None
+            }
+        }
+    }
+
+    /// The typed `link_shared` event
+    pub fn link_shared(&self) -> Option<LinkSharedEvent> {
+        let body = self.body()?;
+        if body.get("type")?.as_str()? != "link_shared" {
+            return None;
+        }
+        match serde_json::from_value(body.clone()) {
+            Ok(event) => Some(event),
+            Err(e) => {
+                warn!("Failed to parse link_shared event: {}", e);
+                None
+            }
+        }
+    }
+
+    /// The assistant thread of `assistant_thread_started` and
+    /// `assistant_thread_context_changed` events
+    pub fn assistant_thread(&self) -> Option<AssistantThread> {
+        let thread = self.body()?.get("assistant_thread")?;
+        match serde_json::from_value(thread.clone()) {
+            Ok(thread) => Some(thread),
+            Err(e) => {
+                warn!("Failed to parse assistant thread: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Regex captures for [`App::message`] listeners (index 0 is the whole match)
+    pub fn matches(&self) -> &[String] {
+        &self.matches
+    }
+
+    /// Acknowledge the envelope with an empty payload
+    ///
+    /// Returns `false` if it was already acknowledged.
+    pub fn ack(&self) -> bool {
+        self.event.ack.ack()
+    }
+
+    /// Acknowledge the envelope with a response payload
+    pub fn ack_with(&self, payload: Value) -> bool {
+        self.event.ack.ack_with(payload)
+    }
+
+    /// Acknowledge the envelope with a typed response payload
+    ///
+    /// See [`Ack::ack_response`](crate::api::socket_mode::Ack::ack_response).
+    pub fn ack_response(&self, response: &impl Serialize) -> Result<bool> {
+        self.event.ack.ack_response(response)
+    }
+
+    /// Channel the event happened in
+    pub fn channel_id(&self) -> Option<&str> {
+        self.event.channel_id()
+    }
+
+    /// User who triggered the event
+    pub fn user_id(&self) -> Option<&str> {
+        match &self.event.payload {
+            SocketModePayload::EventsApi(p) => {
+                let user = p.event.as_ref()?.get("user")?;
+                user.as_str().or_else(|| user.get("id")?.as_str())
+            }
+            SocketModePayload::Interactive(p) => p.user.as_ref().map(|u| u.id.as_str()),
+            SocketModePayload::SlashCommand(p) => Some(&p.user_id),
+            _ => None,
+        }
+    }
+
+    /// Trigger ID for opening modals
+    pub fn trigger_id(&self) -> Option<&str> {
+        match &self.event.payload {
+            SocketModePayload::Interactive(p) => p.trigger_id.as_deref(),
+            SocketModePayload::SlashCommand(p) => p.trigger_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Response URL for commands and interactions
+    pub fn response_url(&self) -> Option<&str> {
+        response_url(&self.event.payload)
+    }
+
+    /// Responder for the response URL, shared by every listener of the event
+    pub fn responder(&self) -> Option<&Responder> {
+        self.responder.as_ref()
+    }
+
+    /// Post a message to the channel the event happened in
+    pub async fn say(&self, text: &str) -> Result<PostMessageResponse> {
+        let channel = self
+            .channel_id()
+            .ok_or_else(|| SlackError::InvalidParameter("event has no channel".into()))?;
+        self.client.chat().post_message(channel, text).await
+    }
+
+    /// Reply to a command or interaction through its response URL
+    pub async fn respond(&self, text: &str) -> Result<()> {
+        self.respond_with(ResponseMessage::text(text)).await
+    }
+
+    /// Post a message to the response URL
+    pub async fn respond_with(&self, message: impl Into<ResponseMessage>) -> Result<()> {
+        self.responder
+            .as_ref()
+            .ok_or_else(|| SlackError::InvalidParameter("event has no response_url".into()))?
+            .send(message)
+            .await
+    }
+}
+
+fn response_url(payload: &SocketModePayload) -> Option<&str> {
+    match payload {
+        // Modals with a conversation select carry `response_urls` instead
+        SocketModePayload::Interactive(p) => p.response_url.as_deref().or_else(|| {
+            p.extra
+                .get("response_urls")?
+                .get(0)?
+                .get("response_url")?
+                .as_str()
+        }),
+        SocketModePayload::SlashCommand(p) => Some(&p.response_url),
+        _ => None,
+    }
+}
+
+impl std::fmt::Debug for Context {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Context")
+            .field("event", &self.event)
+            .field("matches", &self.matches)
+            .finish_non_exhaustive()
+    }
+}
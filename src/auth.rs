@@ -24,6 +24,9 @@ pub enum AuthType {
 
     /// Bot token (xoxb-)
     Bot { token: String },
+
+    /// App-level token (xapp-), used to open Socket Mode connections
+    App { token: String },
 }
 
 impl AuthConfig {
@@ -92,12 +95,56 @@ impl AuthConfig {
         }
     }
 
+    /// Create an app-level token authentication configuration
+    ///
+    /// App-level tokens are required by `apps.connections.open` to open
+    /// Socket Mode connections. They cannot call most other Web API methods,
+    /// so Socket Mode apps usually pair them with a bot token.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The app-level token (starts with xapp-)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slacko::AuthConfig;
+    ///
+    /// let auth = AuthConfig::app("xapp-1-123...");
+    /// ```
+    pub fn app(token: impl Into<String>) -> Self {
+        Self {
+            auth_type: AuthType::App {
+                token: token.into(),
+            },
+        }
+    }
+
+    /// Load an app-level token from the `SLACK_APP_TOKEN` environment variable
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use slacko::AuthConfig;
+    ///
+    /// let auth = AuthConfig::app_from_env().expect("SLACK_APP_TOKEN not set");
+    /// ```
+    pub fn app_from_env() -> Result<Self> {
+        std::env::var("SLACK_APP_TOKEN")
+            .map(Self::app)
+            .map_err(|_| SlackError::config_error("SLACK_APP_TOKEN is not set in environment"))
+    }
+
     /// Load authentication from environment variables
     ///
     /// Checks for the following environment variables in order:
     /// 1. `SLACK_XOXC_TOKEN` and `SLACK_XOXD_COOKIE` for stealth mode
     /// 2. `SLACK_XOXP_TOKEN` for OAuth
     /// 3. `SLACK_BOT_TOKEN` or `SLACK_TOKEN` for bot tokens
+    /// 4. `SLACK_APP_TOKEN` for app-level tokens
+    ///
+    /// Use [`AuthConfig::app_from_env`] to load the app-level token alongside
+    /// a bot or user token.
     ///
     /// # Example
     ///
@@ -127,8 +174,13 @@ impl AuthConfig {
             return Ok(Self::bot(token));
         }
 
+        // Try app-level token
+        if let Ok(token) = std::env::var("SLACK_APP_TOKEN") {
+            return Ok(Self::app(token));
+        }
+
         Err(SlackError::config_error(
-            "No Slack credentials found in environment. Set SLACK_XOXC_TOKEN + SLACK_XOXD_COOKIE, SLACK_XOXP_TOKEN, or SLACK_BOT_TOKEN",
+            "No Slack credentials found in environment. Set SLACK_XOXC_TOKEN + SLACK_XOXD_COOKIE, SLACK_XOXP_TOKEN, SLACK_BOT_TOKEN, or SLACK_APP_TOKEN",
         ))
     }
 
@@ -152,7 +204,7 @@ impl AuthConfig {
                         .unwrap_or_else(|_| HeaderValue::from_static("")),
                 );
             }
-            AuthType::OAuth { token } | AuthType::Bot { token } => {
+            AuthType::OAuth { token } | AuthType::Bot { token } | AuthType::App { token } => {
                 headers.insert(
                     AUTHORIZATION,
                     HeaderValue::from_str(&format!("Bearer {}", token))
@@ -166,12 +218,24 @@ impl AuthConfig {
         headers
     }
 
+    /// The xoxc token and xoxd cookie in stealth mode
+    pub(crate) fn stealth_session(&self) -> Option<(&str, &str)> {
+        match &self.auth_type {
+            AuthType::Stealth {
+                xoxc_token,
+                xoxd_cookie,
+            } => Some((xoxc_token, xoxd_cookie)),
+            _ => None,
+        }
+    }
+
     /// Get the authentication type as a string
     pub fn auth_type_str(&self) -> &str {
         match &self.auth_type {
             AuthType::Stealth { .. } => "stealth",
             AuthType::OAuth { .. } => "oauth",
             AuthType::Bot { .. } => "bot",
+            AuthType::App { .. } => "app",
         }
     }
 }
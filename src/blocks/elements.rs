@@ -94,6 +94,8 @@ pub struct SelectElement {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub initial_option: Option<OptionObject>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_query_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub confirm: Option<ConfirmationDialog>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub focus_on_load: Option<bool>,
@@ -108,6 +110,7 @@ impl SelectElement {
             placeholder: TextObject::plain(placeholder),
             options: None,
             initial_option: None,
+            min_query_length: None,
             confirm: None,
             focus_on_load: None,
         }
@@ -121,6 +124,7 @@ impl SelectElement {
             placeholder: TextObject::plain(placeholder),
             options: None,
             initial_option: None,
+            min_query_length: None,
             confirm: None,
             focus_on_load: None,
         }
@@ -134,6 +138,7 @@ impl SelectElement {
             placeholder: TextObject::plain(placeholder),
             options: None,
             initial_option: None,
+            min_query_length: None,
             confirm: None,
             focus_on_load: None,
         }
@@ -147,6 +152,24 @@ impl SelectElement {
             placeholder: TextObject::plain(placeholder),
             options: None,
             initial_option: None,
+            min_query_length: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// Create an external select menu
+    ///
+    /// Options are loaded from the app with `block_suggestion` requests as
+    /// the user types.
+    pub fn external(action_id: impl Into<String>, placeholder: impl Into<String>) -> Self {
+        Self {
+            type_field: "external_select".to_string(),
+            action_id: action_id.into(),
+            placeholder: TextObject::plain(placeholder),
+            options: None,
+            initial_option: None,
+            min_query_length: None,
             confirm: None,
             focus_on_load: None,
         }
@@ -164,6 +187,12 @@ impl SelectElement {
         self
     }
 
+    /// Characters typed before options are requested (for external select, default 3)
+    pub fn min_query_length(mut self, length: u32) -> Self {
+        self.min_query_length = Some(length);
+        self
+    }
+
     /// Add confirmation dialog
     pub fn confirm(mut self, confirm: ConfirmationDialog) -> Self {
         self.confirm = Some(confirm);
@@ -194,6 +223,8 @@ pub struct MultiSelectElement {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub initial_options: Option<Vec<OptionObject>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_query_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_selected_items: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confirm: Option<ConfirmationDialog>,
@@ -210,6 +241,25 @@ impl MultiSelectElement {
             placeholder: TextObject::plain(placeholder),
             options: None,
             initial_options: None,
+            min_query_length: None,
+            max_selected_items: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// Create an external multi-select menu
+    ///
+    /// Options are loaded from the app with `block_suggestion` requests as
+    /// the user types.
+    pub fn external(action_id: impl Into<String>, placeholder: impl Into<String>) -> Self {
+        Self {
+            type_field: "multi_external_select".to_string(),
+            action_id: action_id.into(),
+            placeholder: TextObject::plain(placeholder),
+            options: None,
+            initial_options: None,
+            min_query_length: None,
             max_selected_items: None,
             confirm: None,
             focus_on_load: None,
@@ -222,6 +272,18 @@ impl MultiSelectElement {
         self
     }
 
+    /// Set initially selected options
+    pub fn initial_options(mut self, options: Vec<OptionObject>) -> Self {
+        self.initial_options = Some(options);
+        self
+    }
+
+    /// Characters typed before options are requested (for external select, default 3)
+    pub fn min_query_length(mut self, length: u32) -> Self {
+        self.min_query_length = Some(length);
+        self
+    }
+
     /// Set max selected items
     pub fn max_selected_items(mut self, max: u32) -> Self {
         self.max_selected_items = Some(max);
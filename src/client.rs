@@ -1,16 +1,18 @@
 //! Core Slack API client
 
 use crate::api::{
-    admin::AdminApi, api_test::ApiApi, apps::AppsApi, auth::AuthApi, bookmarks::BookmarksApi,
-    bots::BotsApi, calls::CallsApi, chat::ChatApi, conversations::ConversationsApi,
-    dialog::DialogApi, dnd::DndApi, emoji::EmojiApi, files::FilesApi, lists::ListsApi,
-    oauth::OAuthApi, openid::OpenIDApi, pins::PinsApi, reactions::ReactionsApi,
-    reminders::RemindersApi, rtm::RtmApi, search::SearchApi, socket_mode::SocketModeApi,
-    stars::StarsApi, team::TeamApi, usergroups::UsergroupsApi, users::UsersApi, views::ViewsApi,
-    workflows::WorkflowsApi,
+    admin::AdminApi, api_test::ApiApi, apps::AppsApi, assistant::AssistantApi, auth::AuthApi,
+    bookmarks::BookmarksApi, bots::BotsApi, calls::CallsApi, chat::ChatApi,
+    conversations::ConversationsApi, dialog::DialogApi, dnd::DndApi, emoji::EmojiApi,
+    files::FilesApi, functions::FunctionsApi, lists::ListsApi, oauth::OAuthApi, openid::OpenIDApi,
+    pins::PinsApi, reactions::ReactionsApi, reminders::RemindersApi, rtm::RtmApi,
+    search::SearchApi, socket_mode::SocketModeApi, stars::StarsApi, team::TeamApi,
+    usergroups::UsergroupsApi, users::UsersApi, views::ViewsApi, workflows::WorkflowsApi,
 };
 use crate::auth::AuthConfig;
 use crate::error::{Result, SlackError};
+use crate::responder::Responder;
+use crate::streaming::ChannelThrottle;
 use crate::types::SlackResponse;
 use reqwest::header::HeaderMap;
 use std::sync::Arc;
@@ -49,6 +51,8 @@ pub struct SlackClient {
     pub(crate) http: reqwest::Client,
     pub(crate) auth: Arc<AuthConfig>,
     pub(crate) base_url: String,
+    /// Spaces `chat.update` edits from message streams per channel
+    pub(crate) update_throttle: Arc<ChannelThrottle>,
 }
 
 impl SlackClient {
@@ -77,9 +81,39 @@ impl SlackClient {
             http,
             auth: Arc::new(auth),
             base_url: SLACK_API_BASE.to_string(),
+            update_throttle: Arc::default(),
         })
     }
 
+    /// Send Web API calls to a different base URL
+    ///
+    /// Defaults to `https://slack.com/api`. Useful for GovSlack, proxies and
+    /// mock servers such as `replay::MockApi` (`test-util` feature).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// The same client authenticating with different credentials
+    pub(crate) fn with_auth(&self, auth: AuthConfig) -> Self {
+        Self {
+            http: self.http.clone(),
+            auth: Arc::new(auth),
+            base_url: self.base_url.clone(),
+            update_throttle: self.update_throttle.clone(),
+        }
+    }
+
+    /// The base URL Web API calls are sent to
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Get the authentication type of this client as a string
+    pub fn auth_type_str(&self) -> &str {
+        self.auth.auth_type_str()
+    }
+
     /// Get the API test client
     ///
     /// Provides methods for testing the Slack API connection.
@@ -154,11 +188,20 @@ impl SlackClient {
     ///
     /// Provides methods for receiving events via WebSocket using Socket Mode.
     /// This is the modern alternative to RTM for receiving events.
-    /// Note: Requires an app-level token (xapp-...).
+    /// Note: Requires an app-level token (xapp-...), see [`AuthConfig::app`].
+    /// Use [`SocketModeApi::from_app_token`] to pair it with a bot token.
     pub fn socket_mode(&self) -> SocketModeApi {
         SocketModeApi::new(self.clone())
     }
 
+    /// Get a responder for a slash command or interaction response URL
+    ///
+    /// Response URLs need no token; the responder reuses this client's
+    /// connection pool.
+    pub fn responder(&self, response_url: impl Into<String>) -> Responder {
+        Responder::with_http(self.http.clone(), response_url)
+    }
+
     /// Get the Auth API client
     ///
     /// Provides methods for testing and managing authentication.
@@ -273,6 +316,20 @@ impl SlackClient {
         WorkflowsApi::new(self.clone())
     }
 
+    /// Get the Assistant API client
+    ///
+    /// Provides methods for AI assistant threads.
+    pub fn assistant(&self) -> AssistantApi {
+        AssistantApi::new(self.clone())
+    }
+
+    /// Get the Functions API client
+    ///
+    /// Provides methods for completing custom workflow functions.
+    pub fn functions(&self) -> FunctionsApi {
+        FunctionsApi::new(self.clone())
+    }
+
     /// Get the Lists API client
     ///
     /// Provides methods for managing Slack Lists.
@@ -455,4 +512,13 @@ mod tests {
         let client = SlackClient::new(AuthConfig::stealth("xoxc-token", "xoxd-cookie"));
         assert!(client.is_ok());
     }
+
+    #[test]
+    fn test_client_creation_app() {
+        let auth = AuthConfig::app("xapp-1-token");
+        assert_eq!(auth.auth_type_str(), "app");
+
+        let client = SlackClient::new(auth);
+        assert!(client.is_ok());
+    }
 }
@@ -0,0 +1,547 @@
+//! Typed interaction payloads
+//!
+//! Interactive payloads (button clicks, modal submissions, shortcuts, ...)
+//! arrive as [`InteractivePayload`] with raw JSON fields. Convert them with
+//! [`InteractivePayload::interaction`] to match on the interaction type and
+//! read element values without digging through JSON.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use slacko::api::socket_mode::SocketModePayload;
+//! use slacko::interactions::{ElementValue, Interaction};
+//! # fn example(payload: SocketModePayload) -> slacko::Result<()> {
+//!
+//! if let SocketModePayload::Interactive(interactive) = payload {
+//!     match interactive.interaction()? {
+//!         Interaction::BlockActions(actions) => {
+//!             for action in &actions.actions {
+//!                 if let ElementValue::Button { value, .. } = &action.value {
+//!                     println!("{} clicked {:?}", actions.user.id, value);
+//!                 }
+//!             }
+//!         }
+//!         Interaction::ViewSubmission(submission) => {
+//!             let title = submission.view.state.find("title").and_then(|v| v.text());
+//!             println!("Submitted title: {:?}", title);
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::socket_mode::{InteractiveChannel, InteractivePayload, InteractiveUser};
+use crate::blocks::{OptionObject, TextObject};
+use crate::error::Result;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// An interaction, by type
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Interaction {
+    /// Interactive elements in a message, modal or App Home were used
+    BlockActions(BlockActions),
+    /// A modal was submitted
+    ViewSubmission(ViewSubmission),
+    /// A modal was closed (requires `notify_on_close`)
+    ViewClosed(ViewClosed),
+    /// A global shortcut was invoked
+    Shortcut(Shortcut),
+    /// A message shortcut was invoked
+    MessageAction(MessageAction),
+    /// An external select requested options
+    BlockSuggestion(BlockSuggestion),
+    /// An interaction type this SDK does not model
+    #[serde(other)]
+    Unknown,
+}
+
+impl InteractivePayload {
+    /// Parse this payload into a typed [`Interaction`]
+    pub fn interaction(&self) -> Result<Interaction> {
+        let mut raw = match &self.extra {
+            Value::Object(map) => map.clone(),
+            _ => Default::default(),
+        };
+        raw.insert("type".into(), Value::String(self.interaction_type.clone()));
+        if let Some(user) = &self.user {
+            raw.insert("user".into(), serde_json::to_value(user)?);
+        }
+        if let Some(channel) = &self.channel {
+            raw.insert("channel".into(), serde_json::to_value(channel)?);
+        }
+        if let Some(trigger_id) = &self.trigger_id {
+            raw.insert("trigger_id".into(), Value::String(trigger_id.clone()));
+        }
+        if let Some(response_url) = &self.response_url {
+            raw.insert("response_url".into(), Value::String(response_url.clone()));
+        }
+        raw.insert("actions".into(), Value::Array(self.actions.clone()));
+        if let Some(view) = &self.view {
+            raw.insert("view".into(), view.clone());
+        }
+        if let Some(message) = &self.message {
+            raw.insert("message".into(), message.clone());
+        }
+
+        Ok(serde_json::from_value(Value::Object(raw))?)
+    }
+}
+
+/// Team in an interaction payload
+#[derive(Debug, Clone, Deserialize)]
+pub struct InteractiveTeam {
+    pub id: String,
+    #[serde(default)]
+    pub domain: Option<String>,
+}
+
+/// `block_actions` payload
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockActions {
+    /// User who used the element
+    pub user: InteractiveUser,
+
+    /// Team the interaction happened in
+    #[serde(default)]
+    pub team: Option<InteractiveTeam>,
+
+    /// Channel, for actions in messages
+    #[serde(default)]
+    pub channel: Option<InteractiveChannel>,
+
+    /// Trigger ID for opening modals
+    #[serde(default)]
+    pub trigger_id: Option<String>,
+
+    /// Response URL, for actions in messages
+    #[serde(default)]
+    pub response_url: Option<String>,
+
+    /// The actions that were taken
+    #[serde(default)]
+    pub actions: Vec<BlockAction>,
+
+    /// The view, for actions in modals and App Home
+    #[serde(default)]
+    pub view: Option<View>,
+
+    /// The message, for actions in messages
+    #[serde(default)]
+    pub message: Option<Value>,
+
+    /// Where the action happened
+    #[serde(default)]
+    pub container: Option<Value>,
+
+    /// Current input values, for actions in messages with input blocks
+    #[serde(default)]
+    pub state: Option<ViewState>,
+}
+
+impl BlockActions {
+    /// Find an action by its `action_id`
+    pub fn action(&self, action_id: &str) -> Option<&BlockAction> {
+        self.actions.iter().find(|a| a.action_id == action_id)
+    }
+}
+
+/// A single action in a `block_actions` payload
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockAction {
+    pub action_id: String,
+
+    #[serde(default)]
+    pub block_id: Option<String>,
+
+    #[serde(default)]
+    pub action_ts: Option<String>,
+
+    /// The element and its value
+    #[serde(flatten)]
+    pub value: ElementValue,
+}
+
+/// `view_submission` payload
+#[derive(Debug, Clone, Deserialize)]
+pub struct ViewSubmission {
+    pub user: InteractiveUser,
+
+    #[serde(default)]
+    pub team: Option<InteractiveTeam>,
+
+    #[serde(default)]
+    pub trigger_id: Option<String>,
+
+    /// The submitted view, including its input values
+    pub view: View,
+
+    /// Response URLs for conversation selects with `response_url_enabled`
+    #[serde(default)]
+    pub response_urls: Vec<ResponseUrl>,
+}
+
+/// Response URL from a modal conversation select
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResponseUrl {
+    pub block_id: String,
+    pub action_id: String,
+    pub channel_id: String,
+    pub response_url: String,
+}
+
+/// `view_closed` payload
+#[derive(Debug, Clone, Deserialize)]
+pub struct ViewClosed {
+    pub user: InteractiveUser,
+
+    #[serde(default)]
+    pub team: Option<InteractiveTeam>,
+
+    pub view: View,
+
+    /// Whether the whole view stack was cleared
+    #[serde(default)]
+    pub is_cleared: bool,
+}
+
+/// Global `shortcut` payload
+#[derive(Debug, Clone, Deserialize)]
+pub struct Shortcut {
+    pub callback_id: String,
+
+    pub trigger_id: String,
+
+    pub user: InteractiveUser,
+
+    #[serde(default)]
+    pub team: Option<InteractiveTeam>,
+}
+
+/// Message shortcut (`message_action`) payload
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageAction {
+    pub callback_id: String,
+
+    pub trigger_id: String,
+
+    pub user: InteractiveUser,
+
+    #[serde(default)]
+    pub team: Option<InteractiveTeam>,
+
+    pub channel: InteractiveChannel,
+
+    /// The message the shortcut was invoked on
+    pub message: Value,
+
+    #[serde(default)]
+    pub response_url: Option<String>,
+}
+
+/// `block_suggestion` payload from an external select
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockSuggestion {
+    pub action_id: String,
+
+    #[serde(default)]
+    pub block_id: Option<String>,
+
+    /// What the user has typed so far
+    #[serde(default)]
+    pub value: String,
+
+    pub user: InteractiveUser,
+
+    #[serde(default)]
+    pub team: Option<InteractiveTeam>,
+
+    #[serde(default)]
+    pub channel: Option<InteractiveChannel>,
+
+    /// The view, when the select is in a modal or App Home
+    #[serde(default)]
+    pub view: Option<View>,
+
+    #[serde(default)]
+    pub container: Option<Value>,
+}
+
+// ============================================
+// Views and Element Values
+// ============================================
+
+/// A modal or App Home view in an interaction payload
+#[derive(Debug, Clone, Deserialize)]
+pub struct View {
+    pub id: String,
+
+    /// `modal` or `home`
+    #[serde(rename = "type", default)]
+    pub view_type: Option<String>,
+
+    #[serde(default)]
+    pub callback_id: Option<String>,
+
+    #[serde(default)]
+    pub private_metadata: Option<String>,
+
+    #[serde(default)]
+    pub external_id: Option<String>,
+
+    #[serde(default)]
+    pub hash: Option<String>,
+
+    #[serde(default)]
+    pub root_view_id: Option<String>,
+
+    #[serde(default)]
+    pub previous_view_id: Option<String>,
+
+    /// Current input values
+    #[serde(default)]
+    pub state: ViewState,
+
+    #[serde(default)]
+    pub blocks: Vec<Value>,
+
+    /// Remaining view fields
+    #[serde(flatten)]
+    pub extra: Value,
+}
+
+/// Input values of a view, keyed by block ID and then action ID
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ViewState {
+    #[serde(default)]
+    pub values: HashMap<String, HashMap<String, ElementValue>>,
+}
+
+impl ViewState {
+    /// Value of the element `action_id` in block `block_id`
+    pub fn get(&self, block_id: &str, action_id: &str) -> Option<&ElementValue> {
+        self.values.get(block_id)?.get(action_id)
+    }
+
+    /// Value of the first element with this `action_id` in any block
+    ///
+    /// Useful when block IDs are generated by Slack.
+    pub fn find(&self, action_id: &str) -> Option<&ElementValue> {
+        self.values.values().find_map(|block| block.get(action_id))
+    }
+}
+
+/// An interactive element and its current value
+///
+/// Used both for actions in `block_actions` and for input values in
+/// [`ViewState`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ElementValue {
+    Button {
+        #[serde(default)]
+        value: Option<String>,
+        #[serde(default)]
+        text: Option<TextObject>,
+    },
+    StaticSelect {
+        #[serde(default)]
+        selected_option: Option<OptionObject>,
+    },
+    ExternalSelect {
+        #[serde(default)]
+        selected_option: Option<OptionObject>,
+    },
+    MultiStaticSelect {
+        #[serde(default)]
+        selected_options: Vec<OptionObject>,
+    },
+    MultiExternalSelect {
+        #[serde(default)]
+        selected_options: Vec<OptionObject>,
+    },
+    UsersSelect {
+        #[serde(default)]
+        selected_user: Option<String>,
+    },
+    MultiUsersSelect {
+        #[serde(default)]
+        selected_users: Vec<String>,
+    },
+    ConversationsSelect {
+        #[serde(default)]
+        selected_conversation: Option<String>,
+    },
+    MultiConversationsSelect {
+        #[serde(default)]
+        selected_conversations: Vec<String>,
+    },
+    ChannelsSelect {
+        #[serde(default)]
+        selected_channel: Option<String>,
+    },
+    MultiChannelsSelect {
+        #[serde(default)]
+        selected_channels: Vec<String>,
+    },
+    Overflow {
+        #[serde(default)]
+        selected_option: Option<OptionObject>,
+    },
+    RadioButtons {
+        #[serde(default)]
+        selected_option: Option<OptionObject>,
+    },
+    Checkboxes {
+        #[serde(default)]
+        selected_options: Vec<OptionObject>,
+    },
+    Datepicker {
+        /// `YYYY-MM-DD`
+        #[serde(default)]
+        selected_date: Option<String>,
+    },
+    Timepicker {
+        /// `HH:mm`
+        #[serde(default)]
+        selected_time: Option<String>,
+        #[serde(default)]
+        timezone: Option<String>,
+    },
+    Datetimepicker {
+        /// Unix timestamp
+        #[serde(default)]
+        selected_date_time: Option<i64>,
+    },
+    PlainTextInput {
+        #[serde(default)]
+        value: Option<String>,
+    },
+    NumberInput {
+        #[serde(default)]
+        value: Option<String>,
+    },
+    EmailTextInput {
+        #[serde(default)]
+        value: Option<String>,
+    },
+    UrlTextInput {
+        #[serde(default)]
+        value: Option<String>,
+    },
+    RichTextInput {
+        #[serde(default)]
+        rich_text_value: Option<Value>,
+    },
+    FileInput {
+        #[serde(default)]
+        files: Vec<Value>,
+    },
+    /// An element type this SDK does not model
+    #[serde(other)]
+    Unknown,
+}
+
+impl ElementValue {
+    /// Text value of a button or text input
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            Self::Button { value, .. }
+            | Self::PlainTextInput { value }
+            | Self::NumberInput { value }
+            | Self::EmailTextInput { value }
+            | Self::UrlTextInput { value } => value.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Selected options of a select, overflow, radio group or checkbox group
+    pub fn selected_options(&self) -> Vec<&OptionObject> {
+        match self {
+            Self::StaticSelect { selected_option }
+            | Self::ExternalSelect { selected_option }
+            | Self::Overflow { selected_option }
+            | Self::RadioButtons { selected_option } => selected_option.iter().collect(),
+            Self::MultiStaticSelect { selected_options }
+            | Self::MultiExternalSelect { selected_options }
+            | Self::Checkboxes { selected_options } => selected_options.iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Values of the selected options
+    pub fn selected_values(&self) -> Vec<&str> {
+        self.selected_options()
+            .into_iter()
+            .map(|o| o.value.as_str())
+            .collect()
+    }
+
+    /// Selected user IDs of a user select
+    pub fn selected_users(&self) -> Vec<&str> {
+        match self {
+            Self::UsersSelect { selected_user } => {
+                selected_user.iter().map(String::as_str).collect()
+            }
+            Self::MultiUsersSelect { selected_users } => {
+                selected_users.iter().map(String::as_str).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Selected conversation or channel IDs of a conversation or channel select
+    pub fn selected_conversations(&self) -> Vec<&str> {
+        match self {
+            Self::ConversationsSelect {
+                selected_conversation: selected,
+            }
+            | Self::ChannelsSelect {
+                selected_channel: selected,
+            } => selected.iter().map(String::as_str).collect(),
+            Self::MultiConversationsSelect {
+                selected_conversations: selected,
+            }
+            | Self::MultiChannelsSelect {
+                selected_channels: selected,
+            } => selected.iter().map(String::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Selected date of a date picker
+    pub fn selected_date(&self) -> Option<NaiveDate> {
+        match self {
+            Self::Datepicker {
+                selected_date: Some(date),
+            } => NaiveDate::parse_from_str(date, "%Y-%m-%d").ok(),
+            _ => None,
+        }
+    }
+
+    /// Selected time of a time picker
+    pub fn selected_time(&self) -> Option<NaiveTime> {
+        match self {
+            Self::Timepicker {
+                selected_time: Some(time),
+                ..
+            } => NaiveTime::parse_from_str(time, "%H:%M").ok(),
+            _ => None,
+        }
+    }
+
+    /// Selected date and time of a datetime picker
+    pub fn selected_date_time(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Datetimepicker {
+                selected_date_time: Some(ts),
+            } => DateTime::from_timestamp(*ts, 0),
+            _ => None,
+        }
+    }
+}
@@ -48,6 +48,9 @@
 //!
 //! // User token (xoxp-...)
 //! let client = SlackClient::new(AuthConfig::oauth("xoxp-token")).unwrap();
+//!
+//! // App-level token (xapp-...) for Socket Mode connections
+//! let client = SlackClient::new(AuthConfig::app("xapp-token")).unwrap();
 //! ```
 //!
 //! ### Stealth Mode
@@ -69,7 +72,8 @@
 //! ```no_run
 //! use slacko::{SlackClient, AuthConfig};
 //!
-//! // Checks SLACK_XOXC_TOKEN/SLACK_XOXD_COOKIE, then SLACK_XOXP_TOKEN, then SLACK_BOT_TOKEN
+//! // Checks SLACK_XOXC_TOKEN/SLACK_XOXD_COOKIE, then SLACK_XOXP_TOKEN, then SLACK_BOT_TOKEN,
+//! // then SLACK_APP_TOKEN
 //! let client = SlackClient::new(AuthConfig::from_env().unwrap()).unwrap();
 //! ```
 //!
@@ -79,6 +83,7 @@
 //!
 //! - [`api::admin`] - Enterprise Grid administration
 //! - [`api::apps`] - App management and permissions
+//! - [`api::assistant`] - AI assistant threads
 //! - [`api::auth`] - Authentication verification
 //! - [`api::bookmarks`] - Channel bookmarks
 //! - [`api::calls`] - Slack Calls integration
@@ -101,7 +106,13 @@
 //! - [`api::usergroups`] - User groups
 //! - [`api::users`] - User information and presence
 //! - [`api::views`] - Modals and App Home
-//! - [`api::workflows`] - Workflow Builder
+//! - [`api::workflows`] - Workflow Builder (legacy steps)
+//! - [`api::functions`] - Custom workflow functions
+//!
+//! ## Event Routing
+//!
+//! [`app::App`] routes events, messages, commands and interactions to async
+//! listeners and runs over Socket Mode or the HTTP Events API.
 //!
 //! ## Block Kit
 //!
@@ -143,11 +154,24 @@
 //! }
 //! ```
 
+pub mod app;
 pub mod auth;
 pub mod blocks;
 pub mod client;
 pub mod error;
+pub mod interactions;
+pub mod lifecycle;
+pub mod metadata;
+#[cfg(feature = "http-receiver")]
+pub mod receiver;
+pub mod replay;
+pub mod responder;
+pub mod scheduled;
+pub mod signature;
+pub mod streaming;
+pub mod thread;
 pub mod types;
+pub mod unfurl;
 
 pub mod api;
 
@@ -155,6 +179,10 @@ pub mod api;
 pub use auth::{AuthConfig, AuthType};
 pub use client::SlackClient;
 pub use error::{Result, SlackError};
+pub use lifecycle::{
+    ConnectionState, ConnectionStatus, DispatchMetrics, HandlerOutcome, HandlerTiming,
+    OverflowPolicy, ShutdownHandle,
+};
 
 // Re-export Block Kit builders for convenience
 pub use blocks::{
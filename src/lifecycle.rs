@@ -0,0 +1,307 @@
+//! Lifecycle control for long-running connections
+//!
+//! Socket Mode and RTM loops run until they are told to stop. A
+//! [`ShutdownHandle`] requests a graceful shutdown, and a
+//! [`ConnectionStatus`] watch channel reports connection health.
+//! [`DispatchMetrics`] and [`HandlerTiming`] report how Socket Mode
+//! handlers are performing.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use slacko::{SlackClient, AuthConfig};
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = SlackClient::new(AuthConfig::app("xapp-token"))?;
+//! let socket_mode = client.socket_mode();
+//! let shutdown = socket_mode.shutdown_handle();
+//! let status = socket_mode.status();
+//!
+//! let runner = tokio::spawn(async move {
+//!     socket_mode
+//!         .start_async_with_reconnect(|event| async move {
+//!             event.ack.ack();
+//!         })
+//!         .await
+//! });
+//!
+//! println!("State: {:?}", status.borrow().state);
+//!
+//! // Stop accepting envelopes, wait for in-flight handlers, close the socket
+//! shutdown.shutdown();
+//! runner.await??;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Notify};
+
+/// Handle used to request a graceful shutdown
+///
+/// Cloning the handle shares the same shutdown signal.
+#[derive(Clone, Debug)]
+pub struct ShutdownHandle {
+    tx: Arc<watch::Sender<bool>>,
+}
+
+impl ShutdownHandle {
+    /// Create a new handle that has not been triggered
+    pub fn new() -> Self {
+        let (tx, _) = watch::channel(false);
+        Self { tx: Arc::new(tx) }
+    }
+
+    /// Request shutdown
+    pub fn shutdown(&self) {
+        self.tx.send_replace(true);
+    }
+
+    /// Whether shutdown has been requested
+    pub fn is_shutdown(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Wait until shutdown is requested
+    pub async fn wait(&self) {
+        let mut rx = self.tx.subscribe();
+        let _ = rx.wait_for(|requested| *requested).await;
+    }
+}
+
+impl Default for ShutdownHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State of a long-running connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Opening the first connection
+    Connecting,
+    /// Connected and receiving events
+    Connected,
+    /// Connection lost, trying again
+    Reconnecting {
+        /// Reconnection attempt number, starting at 1
+        attempt: u32,
+    },
+    /// Shutdown requested, draining in-flight work
+    ShuttingDown,
+    /// Stopped
+    Closed,
+}
+
+/// Snapshot of connection health published on a watch channel
+#[derive(Debug, Clone)]
+pub struct ConnectionStatus {
+    /// Current state
+    pub state: ConnectionState,
+    /// Number of open sockets
+    pub open_connections: usize,
+    /// When the last event was received
+    pub last_event_at: Option<Instant>,
+}
+
+impl Default for ConnectionStatus {
+    fn default() -> Self {
+        Self {
+            state: ConnectionState::Connecting,
+            open_connections: 0,
+            last_event_at: None,
+        }
+    }
+}
+
+/// Publisher side of a [`ConnectionStatus`] watch channel
+#[derive(Clone, Debug)]
+pub(crate) struct StatusReporter {
+    tx: Arc<watch::Sender<ConnectionStatus>>,
+}
+
+impl StatusReporter {
+    pub(crate) fn new() -> Self {
+        let (tx, _) = watch::channel(ConnectionStatus::default());
+        Self { tx: Arc::new(tx) }
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<ConnectionStatus> {
+        self.tx.subscribe()
+    }
+
+    pub(crate) fn set_state(&self, state: ConnectionState) {
+        self.tx.send_modify(|status| status.state = state);
+    }
+
+    pub(crate) fn connection_opened(&self) {
+        self.tx.send_modify(|status| {
+            status.open_connections += 1;
+            status.state = ConnectionState::Connected;
+        });
+    }
+
+    pub(crate) fn connection_closed(&self) {
+        self.tx.send_modify(|status| {
+            status.open_connections = status.open_connections.saturating_sub(1);
+        });
+    }
+
+    pub(crate) fn event_received(&self) {
+        self.tx
+            .send_modify(|status| status.last_event_at = Some(Instant::now()));
+    }
+}
+
+/// What happens to an envelope when the handler limit is reached
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Queue the handler until another one finishes
+    ///
+    /// The connection keeps reading frames and answering pings while the
+    /// handler waits, and the envelope is still acknowledged within the
+    /// ack deadline.
+    #[default]
+    Wait,
+    /// Acknowledge the envelope without running the handler
+    Drop,
+    /// Leave the envelope unacknowledged so Slack delivers it again
+    Redeliver,
+}
+
+/// How a handler run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandlerOutcome {
+    /// The handler returned
+    Completed,
+    /// The handler panicked; the envelope was acknowledged without a payload
+    Panicked,
+}
+
+/// Timing of a single handler run
+#[derive(Debug, Clone)]
+pub struct HandlerTiming {
+    /// Envelope the handler ran for
+    pub envelope_id: String,
+    /// What the envelope carried: the event type, interaction type or command
+    pub kind: Option<String>,
+    /// Time spent waiting for a free slot or for earlier events in the channel
+    pub queued: Duration,
+    /// Time the handler ran
+    pub duration: Duration,
+    /// How the handler ended
+    pub outcome: HandlerOutcome,
+}
+
+/// Counters for dispatched handlers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DispatchMetrics {
+    /// Handlers queued or running
+    pub in_flight: usize,
+    /// Handlers that returned
+    pub completed: u64,
+    /// Handlers that panicked
+    pub panicked: u64,
+    /// Envelopes dropped by the overflow policy
+    pub dropped: u64,
+    /// Total time spent in handlers
+    pub total_time: Duration,
+    /// Longest handler run
+    pub max_time: Duration,
+}
+
+impl DispatchMetrics {
+    /// Mean handler run time
+    pub fn average_time(&self) -> Option<Duration> {
+        let runs = self.completed + self.panicked;
+        (runs > 0).then(|| self.total_time / runs as u32)
+    }
+}
+
+/// Shared [`DispatchMetrics`] updated by the dispatcher
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MetricsReporter {
+    inner: Arc<Mutex<DispatchMetrics>>,
+}
+
+impl MetricsReporter {
+    pub(crate) fn snapshot(&self) -> DispatchMetrics {
+        self.update(|m| m.clone())
+    }
+
+    pub(crate) fn queued(&self) {
+        self.update(|m| m.in_flight += 1);
+    }
+
+    pub(crate) fn dropped(&self) {
+        self.update(|m| m.dropped += 1);
+    }
+
+    pub(crate) fn finished(&self, timing: &HandlerTiming) {
+        self.update(|m| {
+            m.in_flight = m.in_flight.saturating_sub(1);
+            match timing.outcome {
+                HandlerOutcome::Completed => m.completed += 1,
+                HandlerOutcome::Panicked => m.panicked += 1,
+            }
+            m.total_time += timing.duration;
+            m.max_time = m.max_time.max(timing.duration);
+        });
+    }
+
+    fn update<T>(&self, f: impl FnOnce(&mut DispatchMetrics) -> T) -> T {
+        f(&mut self.inner.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Counts in-flight tasks so shutdown can wait for them to finish
+#[derive(Clone, Default)]
+pub(crate) struct TaskTracker {
+    inner: Arc<TrackerInner>,
+}
+
+#[derive(Default)]
+struct TrackerInner {
+    count: AtomicUsize,
+    idle: Notify,
+}
+
+struct TrackerGuard(Arc<TrackerInner>);
+
+impl Drop for TrackerGuard {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+impl TaskTracker {
+    /// Wrap a future so it counts as in flight until it completes or is dropped
+    pub(crate) fn track<F: Future>(&self, fut: F) -> impl Future<Output = F::Output> {
+        self.inner.count.fetch_add(1, Ordering::AcqRel);
+        let guard = TrackerGuard(self.inner.clone());
+        async move {
+            let _guard = guard;
+            fut.await
+        }
+    }
+
+    /// Number of tasks still in flight
+    pub(crate) fn len(&self) -> usize {
+        self.inner.count.load(Ordering::Acquire)
+    }
+
+    /// Wait until no tasks are in flight
+    pub(crate) async fn wait_idle(&self) {
+        loop {
+            let idle = self.inner.idle.notified();
+            if self.len() == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Typed message metadata
+//!
+//! Messages can carry an `event_type` and a JSON `event_payload`. Post it
+//! with [`ChatApi::post_with_metadata`](crate::api::chat::ChatApi::post_with_metadata)
+//! and read it with [`Message::metadata_as`](crate::types::Message::metadata_as).
+//!
+//! Slack sends `message_metadata_posted` when a message with metadata is
+//! posted. A [`MetadataRegistry`] maps event types to Rust types so one
+//! listener can parse payloads of several kinds.
+//!
+//! # Example
+//!
+//! ```
+//! use serde::Deserialize;
+//! use slacko::metadata::MetadataRegistry;
+//! use slacko::types::MessageMetadata;
+//!
+//! #[derive(Deserialize)]
+//! struct TaskCreated {
+//!     id: String,
+//! }
+//!
+//! let registry = MetadataRegistry::new().register::<TaskCreated>("task_created");
+//!
+//! let metadata = MessageMetadata {
+//!     event_type: "task_created".into(),
+//!     event_payload: serde_json::json!({ "id": "TK-1" }),
+//! };
+//! let parsed = registry.parse(&metadata).unwrap().unwrap();
+//! assert_eq!(parsed.downcast_ref::<TaskCreated>().unwrap().id, "TK-1");
+//! ```
+
+use crate::error::Result;
+use crate::types::MessageMetadata;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+type Parser = Arc<dyn Fn(&Value) -> Result<Box<dyn Any + Send + Sync>> + Send + Sync>;
+
+/// Maps metadata event types to the Rust types their payloads parse into
+#[derive(Clone, Default)]
+pub struct MetadataRegistry {
+    parsers: HashMap<String, Parser>,
+}
+
+impl MetadataRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse payloads of `event_type` into `T`
+    ///
+    /// Registering the same event type again replaces the earlier type.
+    pub fn register<T>(mut self, event_type: impl Into<String>) -> Self
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        let parser: Parser = Arc::new(|payload| {
+            let value: T = serde_json::from_value(payload.clone())?;
+            Ok(Box::new(value))
+        });
+        self.parsers.insert(event_type.into(), parser);
+        self
+    }
+
+    /// Whether `event_type` has a registered type
+    pub fn contains(&self, event_type: &str) -> bool {
+        self.parsers.contains_key(event_type)
+    }
+
+    /// Parse the payload with the type registered for its event type
+    ///
+    /// Returns `None` for unregistered event types and an error when the
+    /// payload does not match the registered type.
+    pub fn parse(&self, metadata: &MessageMetadata) -> Option<Result<TypedMetadata>> {
+        let parser = self.parsers.get(&metadata.event_type)?;
+        Some(
+            parser(&metadata.event_payload).map(|payload| TypedMetadata {
+                event_type: metadata.event_type.clone(),
+                payload,
+            }),
+        )
+    }
+
+    /// Parse the metadata of a `message_metadata_posted` event
+    pub fn parse_event(&self, event: &MessageMetadataPostedEvent) -> Option<Result<TypedMetadata>> {
+        self.parse(&event.metadata)
+    }
+}
+
+impl fmt::Debug for MetadataRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetadataRegistry")
+            .field("event_types", &self.parsers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// A payload parsed by a [`MetadataRegistry`]
+pub struct TypedMetadata {
+    event_type: String,
+    payload: Box<dyn Any + Send + Sync>,
+}
+
+impl TypedMetadata {
+    /// Event type the payload was registered under
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    /// Whether the payload is a `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    /// The payload, if it is a `T`
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+
+    /// Take the payload, if it is a `T`
+    pub fn downcast<T: 'static>(self) -> std::result::Result<T, Self> {
+        match self.payload.downcast() {
+            Ok(payload) => Ok(*payload),
+            Err(payload) => Err(Self {
+                event_type: self.event_type,
+                payload,
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for TypedMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedMetadata")
+            .field("event_type", &self.event_type)
+            .finish_non_exhaustive()
+    }
+}
+
+// Event types
+
+/// `message_metadata_posted` event
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageMetadataPostedEvent {
+    pub app_id: Option<String>,
+    pub bot_id: Option<String>,
+    pub user_id: Option<String>,
+    pub team_id: Option<String>,
+    pub channel_id: String,
+    pub metadata: MessageMetadata,
+    pub message_ts: String,
+    pub event_ts: Option<String>,
+}
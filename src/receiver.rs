@@ -0,0 +1,415 @@
+//! HTTP Events API receiver
+//!
+//! Receives events, interactivity payloads and slash commands over HTTP for
+//! apps that cannot use Socket Mode. Requests are verified with the app's
+//! signing secret and parsed into the same [`SocketModeEvent`] types used by
+//! Socket Mode, so handlers work with either transport.
+//!
+//! Requires the `http-receiver` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use slacko::receiver::HttpReceiver;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! HttpReceiver::new("signing-secret")
+//!     .path("/slack/events")
+//!     .serve("0.0.0.0:3000", |event| async move {
+//!         event.ack.ack();
+//!         println!("Received: {:?}", event.envelope_type);
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::socket_mode::{
+    Ack, EventHandler, SocketModeApi, SocketModeEnvelope, SocketModeEvent,
+};
+use crate::error::{Result, SlackError};
+use crate::lifecycle::{ShutdownHandle, TaskTracker};
+use crate::signature::SignatureVerifier;
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use serde_json::{Map, Value};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tracing::{debug, info, warn};
+
+/// Default path Slack is configured to post to
+pub const DEFAULT_PATH: &str = "/slack/events";
+
+pub use crate::signature::DEFAULT_MAX_TIMESTAMP_SKEW;
+
+/// Largest request body accepted
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// HTTP receiver for the Slack Events API
+#[derive(Clone)]
+pub struct HttpReceiver {
+    verifier: SignatureVerifier,
+    path: String,
+    ack_timeout: Duration,
+    shutdown: ShutdownHandle,
+    /// Handlers still running, awaited on shutdown
+    tasks: TaskTracker,
+    request_counter: Arc<AtomicU64>,
+}
+
+/// A response to send back to Slack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiverResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Content type of the body, if any
+    pub content_type: Option<&'static str>,
+    /// Response body
+    pub body: String,
+}
+
+impl ReceiverResponse {
+    fn empty(status: u16) -> Self {
+        Self {
+            status,
+            content_type: None,
+            body: String::new(),
+        }
+    }
+
+    fn text(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: Some("text/plain"),
+            body: body.into(),
+        }
+    }
+
+    fn json(body: &Value) -> Self {
+        Self {
+            status: 200,
+            content_type: Some("application/json"),
+            body: body.to_string(),
+        }
+    }
+}
+
+impl HttpReceiver {
+    /// Create a receiver verifying requests with the app's signing secret
+    pub fn new(signing_secret: impl Into<String>) -> Self {
+        Self {
+            verifier: SignatureVerifier::new(signing_secret),
+            path: DEFAULT_PATH.to_string(),
+            ack_timeout: crate::api::socket_mode::DEFAULT_ACK_TIMEOUT,
+            shutdown: ShutdownHandle::new(),
+            tasks: TaskTracker::default(),
+            request_counter: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Create a receiver using the `SLACK_SIGNING_SECRET` environment variable
+    pub fn from_env() -> Result<Self> {
+        std::env::var("SLACK_SIGNING_SECRET")
+            .map(Self::new)
+            .map_err(|_| SlackError::config_error("SLACK_SIGNING_SECRET is not set in environment"))
+    }
+
+    /// Set the request path to accept (defaults to [`DEFAULT_PATH`])
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Set how long to wait for a handler to acknowledge before responding
+    ///
+    /// Slack expects a response within 3 seconds.
+    pub fn ack_timeout(mut self, timeout: Duration) -> Self {
+        self.ack_timeout = timeout;
+        self
+    }
+
+    /// Set the maximum accepted age of `X-Slack-Request-Timestamp`
+    pub fn max_timestamp_skew(mut self, skew: Duration) -> Self {
+        self.verifier = self.verifier.max_timestamp_skew(skew);
+        self
+    }
+
+    /// Use an existing shutdown handle
+    pub fn with_shutdown(mut self, shutdown: ShutdownHandle) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Handle for gracefully stopping the server
+    ///
+    /// On shutdown the server stops accepting connections and waits for
+    /// in-flight requests and their handlers to complete.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Bind to an address and serve requests until shutdown
+    pub async fn serve<F, Fut>(self, addr: impl ToSocketAddrs, handler: F) -> Result<()>
+    where
+        F: Fn(SocketModeEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| SlackError::config_error(format!("Failed to bind: {}", e)))?;
+        self.serve_listener(listener, handler).await
+    }
+
+    /// Serve requests on an existing listener until shutdown
+    pub async fn serve_listener<F, Fut>(self, listener: TcpListener, handler: F) -> Result<()>
+    where
+        F: Fn(SocketModeEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handler = SocketModeApi::async_handler(handler);
+        let receiver = Arc::new(self);
+        let connections = TaskTracker::default();
+
+        if let Ok(addr) = listener.local_addr() {
+            info!("HTTP receiver listening on {}{}", addr, receiver.path);
+        }
+
+        loop {
+            let (stream, peer) = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        warn!("Failed to accept connection: {}", e);
+                        continue;
+                    }
+                },
+                _ = receiver.shutdown.wait() => break,
+            };
+            debug!("HTTP receiver connection from {}", peer);
+
+            let shutdown = receiver.shutdown.clone();
+            let receiver = receiver.clone();
+            let handler = handler.clone();
+            let service = service_fn(move |req: hyper::Request<Incoming>| {
+                let receiver = receiver.clone();
+                let handler = handler.clone();
+                async move {
+                    let response = receiver.serve_http(req, handler).await;
+                    Ok::<_, std::convert::Infallible>(response)
+                }
+            });
+
+            tokio::spawn(connections.track(async move {
+                let conn = http1::Builder::new().serve_connection(TokioIo::new(stream), service);
+                tokio::pin!(conn);
+
+                // Finish in-flight requests and close idle keep-alive connections on shutdown
+                let result = tokio::select! {
+                    result = conn.as_mut() => result,
+                    _ = shutdown.wait() => {
+                        conn.as_mut().graceful_shutdown();
+                        conn.await
+                    }
+                };
+                if let Err(e) = result {
+                    debug!("HTTP receiver connection error: {}", e);
+                }
+            }));
+        }
+
+        info!("HTTP receiver shutting down");
+        connections.wait_idle().await;
+        info!(
+            "HTTP receiver waiting for {} in-flight handlers",
+            receiver.tasks.len()
+        );
+        receiver.tasks.wait_idle().await;
+        Ok(())
+    }
+
+    /// Adapt a hyper request to [`handle`](Self::handle)
+    async fn serve_http(
+        &self,
+        req: hyper::Request<Incoming>,
+        handler: EventHandler,
+    ) -> hyper::Response<Full<Bytes>> {
+        let response = if req.uri().path() != self.path {
+            ReceiverResponse::empty(404)
+        } else if req.method() != hyper::Method::POST {
+            ReceiverResponse::empty(405)
+        } else {
+            let (parts, body) = req.into_parts();
+            match Limited::new(body, MAX_BODY_SIZE).collect().await {
+                Ok(body) => {
+                    self.handle_with(&parts.headers, &body.to_bytes(), handler)
+                        .await
+                }
+                Err(e) => {
+                    warn!("Failed to read request body: {}", e);
+                    ReceiverResponse::empty(413)
+                }
+            }
+        };
+
+        let mut builder = hyper::Response::builder().status(response.status);
+        if let Some(content_type) = response.content_type {
+            builder = builder.header(CONTENT_TYPE, content_type);
+        }
+        builder
+            .body(Full::new(Bytes::from(response.body)))
+            .unwrap_or_else(|_| hyper::Response::new(Full::new(Bytes::new())))
+    }
+
+    /// Verify, parse and dispatch a single request
+    ///
+    /// Framework-agnostic entry point for embedding the receiver in an
+    /// existing web server: pass the request headers and raw body and send
+    /// back the returned response.
+    pub async fn handle<F, Fut>(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        handler: F,
+    ) -> ReceiverResponse
+    where
+        F: Fn(SocketModeEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.handle_with(headers, body, SocketModeApi::async_handler(handler))
+            .await
+    }
+
+    async fn handle_with(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        handler: EventHandler,
+    ) -> ReceiverResponse {
+        if let Err(e) = self.verifier.verify_headers(headers, body) {
+            warn!("Rejected Slack request: {}", e);
+            return ReceiverResponse::text(401, "invalid signature");
+        }
+
+        let envelope = match self.parse_request(headers, body) {
+            Ok(ParsedRequest::UrlVerification(challenge)) => {
+                info!("Answering url_verification challenge");
+                return ReceiverResponse::text(200, challenge);
+            }
+            Ok(ParsedRequest::Envelope(envelope)) => envelope,
+            Err(e) => {
+                warn!("Failed to parse Slack request: {}", e);
+                return ReceiverResponse::text(400, "invalid payload");
+            }
+        };
+
+        let (ack, rx) = Ack::new(
+            envelope.envelope_id.clone(),
+            envelope.accepts_response_payload,
+        );
+        let event = SocketModeApi::parse_envelope(envelope, ack);
+        let envelope_id = event.envelope_id.clone();
+        tokio::spawn(self.tasks.track(handler(event)));
+
+        match tokio::time::timeout(self.ack_timeout, rx).await {
+            Ok(Ok(Some(payload))) => ReceiverResponse::json(&payload),
+            Ok(Ok(None)) | Ok(Err(_)) => ReceiverResponse::empty(200),
+            Err(_) => {
+                warn!(
+                    "Request {} not acknowledged within {:?}; responding with empty body",
+                    envelope_id, self.ack_timeout
+                );
+                ReceiverResponse::empty(200)
+            }
+        }
+    }
+
+    /// Turn a verified request body into an envelope
+    fn parse_request(&self, headers: &HeaderMap, body: &[u8]) -> Result<ParsedRequest> {
+        let is_form = header_str(headers, CONTENT_TYPE.as_str())
+            .map(|ct| ct.starts_with("application/x-www-form-urlencoded"))
+            .unwrap_or(false);
+
+        let retry_attempt = header_str(headers, "x-slack-retry-num").and_then(|n| n.parse().ok());
+        let retry_reason = header_str(headers, "x-slack-retry-reason").map(String::from);
+
+        let (envelope_type, payload, accepts_response_payload) = if is_form {
+            let fields: Map<String, Value> = url::form_urlencoded::parse(body)
+                .map(|(k, v)| (k.into_owned(), Value::String(v.into_owned())))
+                .collect();
+
+            match fields.get("payload").and_then(|p| p.as_str()) {
+                // Interactivity posts a form with a JSON `payload` field
+                Some(payload) => ("interactive", serde_json::from_str(payload)?, true),
+                // Slash commands post the command fields directly
+                None if fields.contains_key("command") => {
+                    ("slash_commands", Value::Object(fields), true)
+                }
+                None => return Err(SlackError::InvalidParameter("unknown form payload".into())),
+            }
+        } else {
+            let payload: Value = serde_json::from_slice(body)?;
+            if payload.get("type").and_then(|t| t.as_str()) == Some("url_verification") {
+                let challenge = payload
+                    .get("challenge")
+                    .and_then(|c| c.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                return Ok(ParsedRequest::UrlVerification(challenge));
+            }
+            ("events_api", payload, false)
+        };
+
+        Ok(ParsedRequest::Envelope(SocketModeEnvelope {
+            envelope_id: self.envelope_id(&payload),
+            envelope_type: envelope_type.to_string(),
+            accepts_response_payload,
+            payload: Some(payload),
+            retry_attempt,
+            retry_reason,
+            reason: None,
+        }))
+    }
+
+    /// Derive an envelope ID from the payload, falling back to a counter
+    fn envelope_id(&self, payload: &Value) -> String {
+        ["event_id", "trigger_id"]
+            .iter()
+            .find_map(|key| payload.get(*key).and_then(|v| v.as_str()))
+            .map(String::from)
+            .unwrap_or_else(|| {
+                format!(
+                    "http-{}",
+                    self.request_counter.fetch_add(1, Ordering::Relaxed)
+                )
+            })
+    }
+}
+
+/// Result of parsing a verified request
+enum ParsedRequest {
+    /// Endpoint verification handshake
+    UrlVerification(String),
+    /// A deliverable envelope
+    Envelope(SocketModeEnvelope),
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+impl std::fmt::Debug for HttpReceiver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpReceiver")
+            .field("path", &self.path)
+            .field("ack_timeout", &self.ack_timeout)
+            .field("verifier", &self.verifier)
+            .finish_non_exhaustive()
+    }
+}
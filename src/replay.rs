@@ -0,0 +1,363 @@
+//! Record and replay Socket Mode traffic
+//!
+//! A [`Recorder`] attached with
+//! [`SocketModeApi::record`](crate::api::socket_mode::SocketModeApi::record)
+//! writes every frame read from Slack to a JSONL file, redacting secrets.
+//! [`SocketModeApi::replay`](crate::api::socket_mode::SocketModeApi::replay)
+//! later feeds the file through the same parsing and dispatch path without
+//! a connection, collecting the acknowledgements. Pair it with `MockApi`,
+//! built with the `test-util` feature, to capture the Web API calls handlers
+//! make.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use slacko::app::App;
+//! use slacko::replay::{MockApi, Recorder, Replay};
+//! # use slacko::{AuthConfig, SlackClient};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! // Capture production traffic
+//! # let bot = SlackClient::new(AuthConfig::bot("xoxb-token"))?;
+//! let socket_mode = bot
+//!     .socket_mode()
+//!     .record(Recorder::create("traffic.jsonl")?.redact_key("email"));
+//!
+//! // Later, offline
+//! let api = MockApi::start().await?;
+//! api.respond("chat.postMessage", serde_json::json!({
+//!     "ok": true, "channel": "C1", "ts": "1.2", "message": { "ts": "1.2" }
+//! }));
+//! let app = App::new(api.client()).event("app_mention", |ctx| async move {
+//!     ctx.say("Hi!").await?;
+//!     Ok(())
+//! });
+//! let report = app.replay(Replay::open("traffic.jsonl")?).await?;
+//!
+//! assert_eq!(report.acks.len(), report.frames);
+//! assert_eq!(api.calls_to("chat.postMessage").len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Result, SlackError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+#[cfg(feature = "test-util")]
+mod mock;
+#[cfg(feature = "test-util")]
+pub use mock::{ApiCall, MockApi};
+
+/// Keys whose values are redacted by default
+pub const DEFAULT_REDACTED_KEYS: &[&str] =
+    &["token", "response_url", "response_urls", "authorizations"];
+
+/// Replacement for redacted values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A frame read from a Socket Mode connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// Milliseconds since recording started
+    #[serde(default)]
+    pub offset_ms: u64,
+    /// The envelope as received, after redaction
+    pub frame: Value,
+}
+
+// ============================================
+// Recording
+// ============================================
+
+type Redactor = Arc<dyn Fn(&mut Value) + Send + Sync>;
+
+/// Writes Socket Mode frames to a JSONL sink
+///
+/// Values of [`DEFAULT_REDACTED_KEYS`] are replaced with [`REDACTED`] at
+/// any depth. Frames that are not JSON are not recorded. Frames are
+/// buffered and written by a dedicated thread, so recording never blocks
+/// the connection's read loop; the thread flushes the sink whenever it
+/// catches up and exits once every clone is dropped. Clones share the
+/// sink and the start time.
+#[derive(Clone)]
+pub struct Recorder {
+    sink: mpsc::Sender<SinkCommand>,
+    started: Instant,
+    keys: HashSet<String>,
+    redactors: Vec<Redactor>,
+}
+
+impl Recorder {
+    /// Record to a writer
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        let (sink, commands) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("slacko-recorder".into())
+            .spawn(move || write_frames(writer, commands))
+            .expect("failed to spawn the recorder thread");
+        Self {
+            sink,
+            started: Instant::now(),
+            keys: DEFAULT_REDACTED_KEYS
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            redactors: Vec::new(),
+        }
+    }
+
+    /// Record to a file, truncating it
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::create(path).map_err(|e| {
+            SlackError::config_error(format!("Failed to create {}: {}", path.display(), e))
+        })?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+
+    /// Also redact values of this key
+    pub fn redact_key(mut self, key: impl Into<String>) -> Self {
+        self.keys.insert(key.into());
+        self
+    }
+
+    /// Stop redacting values of this key
+    pub fn keep_key(mut self, key: &str) -> Self {
+        self.keys.remove(key);
+        self
+    }
+
+    /// Run a custom redaction on every frame after the key redaction
+    pub fn redact_with<F>(mut self, redactor: F) -> Self
+    where
+        F: Fn(&mut Value) + Send + Sync + 'static,
+    {
+        self.redactors.push(Arc::new(redactor));
+        self
+    }
+
+    /// Redact a raw frame and queue it for writing
+    pub fn record(&self, text: &str) {
+        let Ok(mut frame) = serde_json::from_str::<Value>(text) else {
+            debug!("Not recording non-JSON frame");
+            return;
+        };
+        self.redact(&mut frame);
+
+        let line = RecordedFrame {
+            offset_ms: self.started.elapsed().as_millis() as u64,
+            frame,
+        };
+        let line = serde_json::to_string(&line).expect("RecordedFrame is always serializable");
+        if self.sink.send(SinkCommand::Frame(line)).is_err() {
+            warn!("Recorder thread stopped, dropping Socket Mode frame");
+        }
+    }
+
+    /// Wait until every frame recorded so far is written and flushed
+    ///
+    /// Blocks the calling thread; from async code, call it through
+    /// `tokio::task::spawn_blocking`.
+    pub fn flush(&self) {
+        let (done, written) = mpsc::channel();
+        if self.sink.send(SinkCommand::Flush(done)).is_ok() {
+            let _ = written.recv();
+        }
+    }
+
+    /// Apply the redactions to a frame
+    pub fn redact(&self, frame: &mut Value) {
+        redact_keys(frame, &self.keys);
+        for redactor in &self.redactors {
+            redactor(frame);
+        }
+    }
+}
+
+/// Work for the recorder thread
+enum SinkCommand {
+    Frame(String),
+    Flush(mpsc::Sender<()>),
+}
+
+/// Write queued frames until every [`Recorder`] is dropped
+fn write_frames(mut writer: impl Write, commands: mpsc::Receiver<SinkCommand>) {
+    while let Ok(command) = commands.recv() {
+        let mut next = Some(command);
+        let mut flushed = Vec::new();
+        // Write everything queued, then flush once
+        while let Some(command) = next {
+            match command {
+                SinkCommand::Frame(line) => {
+                    if let Err(e) = writeln!(writer, "{}", line) {
+                        warn!("Failed to record Socket Mode frame: {}", e);
+                    }
+                }
+                SinkCommand::Flush(done) => flushed.push(done),
+            }
+            next = commands.try_recv().ok();
+        }
+        if let Err(e) = writer.flush() {
+            warn!("Failed to flush Socket Mode recording: {}", e);
+        }
+        for done in flushed {
+            let _ = done.send(());
+        }
+    }
+}
+
+fn redact_keys(value: &mut Value, keys: &HashSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if keys.contains(key) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_keys(value, keys);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| redact_keys(v, keys)),
+        _ => {}
+    }
+}
+
+impl std::fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder")
+            .field("redacted_keys", &self.keys)
+            .field("redactors", &self.redactors.len())
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================
+// Replay
+// ============================================
+
+/// How recorded gaps between frames are reproduced
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Timing {
+    /// Dispatch every frame without waiting
+    #[default]
+    Immediate,
+    /// Wait as long as the recording did
+    Recorded,
+    /// Multiply recorded gaps by a factor (`0.5` replays twice as fast)
+    Scaled(f64),
+}
+
+impl Timing {
+    /// When a frame recorded at `offset` is dispatched, relative to the start
+    pub(crate) fn delay(&self, offset: Duration) -> Option<Duration> {
+        match self {
+            Timing::Immediate => None,
+            Timing::Recorded => Some(offset),
+            Timing::Scaled(factor) => Some(offset.mul_f64(factor.max(0.0))),
+        }
+    }
+}
+
+/// Recorded frames to feed through the dispatch path
+#[derive(Debug, Clone, Default)]
+pub struct Replay {
+    frames: Vec<RecordedFrame>,
+    timing: Timing,
+}
+
+impl Replay {
+    /// Load a JSONL recording
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path).map_err(|e| {
+            SlackError::config_error(format!("Failed to open {}: {}", path.display(), e))
+        })?;
+        Self::from_reader(BufReader::new(file))
+    }
+
+    /// Load JSONL from a reader
+    ///
+    /// Lines are [`RecordedFrame`]s or bare envelopes; blank lines are skipped.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut frames = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| SlackError::Other(format!("Failed to read: {}", e)))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let value: Value = serde_json::from_str(&line)
+                .map_err(|e| SlackError::InvalidParameter(format!("line {}: {}", index + 1, e)))?;
+            frames.push(match value.get("frame") {
+                Some(_) => serde_json::from_value(value)?,
+                None => RecordedFrame {
+                    offset_ms: 0,
+                    frame: value,
+                },
+            });
+        }
+        Ok(Self {
+            frames,
+            timing: Timing::default(),
+        })
+    }
+
+    /// Replay envelopes without recorded timing
+    pub fn from_envelopes(envelopes: impl IntoIterator<Item = Value>) -> Self {
+        Self {
+            frames: envelopes
+                .into_iter()
+                .map(|frame| RecordedFrame {
+                    offset_ms: 0,
+                    frame,
+                })
+                .collect(),
+            timing: Timing::default(),
+        }
+    }
+
+    /// Set how gaps between frames are reproduced
+    pub fn timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// The recorded frames
+    pub fn frames(&self) -> &[RecordedFrame] {
+        &self.frames
+    }
+
+    pub(crate) fn into_parts(self) -> (Vec<RecordedFrame>, Timing) {
+        (self.frames, self.timing)
+    }
+}
+
+/// Acknowledgement produced during a replay
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ReplayedAck {
+    pub envelope_id: String,
+    #[serde(default)]
+    pub payload: Option<Value>,
+}
+
+/// Outcome of a replay
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    /// Number of frames dispatched
+    pub frames: usize,
+    /// Acknowledgements in the order they were sent
+    pub acks: Vec<ReplayedAck>,
+}
+
+impl ReplayReport {
+    /// The acknowledgement for an envelope
+    pub fn ack(&self, envelope_id: &str) -> Option<&ReplayedAck> {
+        self.acks.iter().find(|a| a.envelope_id == envelope_id)
+    }
+}
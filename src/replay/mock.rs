@@ -0,0 +1,269 @@
+//! Local mock of the Slack Web API
+//!
+//! Only built with the `test-util` feature.
+
+use crate::auth::AuthConfig;
+use crate::client::SlackClient;
+use crate::error::{Result, SlackError};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+/// A Web API call captured by [`MockApi`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiCall {
+    /// Method name (e.g. `chat.postMessage`)
+    pub method: String,
+    /// JSON body, or the query or form parameters as a JSON object
+    pub params: Value,
+    /// Bearer token from the `Authorization` header
+    pub token: Option<String>,
+}
+
+#[derive(Default)]
+struct MockState {
+    calls: Mutex<Vec<ApiCall>>,
+    responses: Mutex<HashMap<String, Value>>,
+    /// One-off answers per method, used before `responses`
+    queued: Mutex<HashMap<String, Vec<Value>>>,
+    /// Pending `429` answers per method, with their `Retry-After` seconds
+    rate_limits: Mutex<HashMap<String, Vec<u64>>>,
+}
+
+/// Local HTTP server standing in for the Slack Web API
+///
+/// Records every call and answers with the next queued response for its
+/// method, the response set for it, or `{"ok": true}`. The server stops when this is dropped.
+pub struct MockApi {
+    addr: SocketAddr,
+    state: Arc<MockState>,
+    task: JoinHandle<()>,
+}
+
+impl MockApi {
+    /// Start the server on a free local port
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| SlackError::config_error(format!("Failed to bind: {}", e)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| SlackError::config_error(format!("Failed to bind: {}", e)))?;
+        let state = Arc::new(MockState::default());
+
+        let shared = state.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = shared.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_call(stream, &state).await {
+                        warn!("Mock API connection failed: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self { addr, state, task })
+    }
+
+    /// Base URL to pass to [`SlackClient::with_base_url`]
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// A bot client pointed at this server
+    pub fn client(&self) -> SlackClient {
+        SlackClient::new(AuthConfig::bot("xoxb-mock"))
+            .expect("client with a static token always builds")
+            .with_base_url(self.url())
+    }
+
+    /// Answer calls to `method` with this JSON
+    pub fn respond(&self, method: impl Into<String>, response: Value) {
+        self.state
+            .responses
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(method.into(), response);
+    }
+
+    /// Answer the next call to `method` with this JSON
+    ///
+    /// Repeated calls queue further answers; once they are used up calls
+    /// get the [`respond`](Self::respond) answer again.
+    pub fn respond_once(&self, method: impl Into<String>, response: Value) {
+        self.state
+            .queued
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(method.into())
+            .or_default()
+            .push(response);
+    }
+
+    /// Answer the next call to `method` with `429 Too Many Requests`
+    ///
+    /// Repeated calls queue further rate-limited answers.
+    pub fn rate_limit(&self, method: impl Into<String>, retry_after: u64) {
+        self.state
+            .rate_limits
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(method.into())
+            .or_default()
+            .push(retry_after);
+    }
+
+    /// Every call received so far
+    pub fn calls(&self) -> Vec<ApiCall> {
+        self.state
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Calls received for one method
+    pub fn calls_to(&self, method: &str) -> Vec<ApiCall> {
+        self.calls()
+            .into_iter()
+            .filter(|c| c.method == method)
+            .collect()
+    }
+}
+
+impl Drop for MockApi {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl std::fmt::Debug for MockApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockApi")
+            .field("addr", &self.addr)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Read one request, record it and answer with `Connection: close`
+async fn serve_call(mut stream: TcpStream, state: &MockState) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let (head, body) = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        let Some(split) = buf.windows(4).position(|w| w == b"\r\n\r\n") else {
+            continue;
+        };
+        let head = String::from_utf8_lossy(&buf[..split]).to_string();
+        let length = head
+            .lines()
+            .find_map(|l| {
+                let (name, value) = l.split_once(':')?;
+                if name.eq_ignore_ascii_case("content-length") {
+                    value.trim().parse::<usize>().ok()
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(0);
+        if buf.len() >= split + 4 + length {
+            break (head, buf[split + 4..split + 4 + length].to_vec());
+        }
+    };
+
+    let target = head.split_whitespace().nth(1).unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let method = path.rsplit('/').next().unwrap_or_default().to_string();
+    let header = |wanted: &str| {
+        head.lines().find_map(|l| {
+            let (name, value) = l.split_once(':')?;
+            name.eq_ignore_ascii_case(wanted)
+                .then(|| value.trim().to_string())
+        })
+    };
+    let content_type = header("content-type").unwrap_or_default();
+    let token = header("authorization").and_then(|v| v.strip_prefix("Bearer ").map(str::to_string));
+
+    let params = if content_type.starts_with("application/json") {
+        serde_json::from_slice(&body).unwrap_or(Value::Null)
+    } else if content_type.starts_with("application/x-www-form-urlencoded") {
+        form_params(&body)
+    } else if !query.is_empty() {
+        form_params(query.as_bytes())
+    } else {
+        Value::Null
+    };
+    debug!("Mock API call {}: {}", method, params);
+
+    let rate_limited = state
+        .rate_limits
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_mut(&method)
+        .filter(|queue| !queue.is_empty())
+        .map(|queue| queue.remove(0));
+    let queued = match rate_limited {
+        Some(_) => None,
+        None => state
+            .queued
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(&method)
+            .filter(|queue| !queue.is_empty())
+            .map(|queue| queue.remove(0)),
+    };
+    let response = queued.unwrap_or_else(|| {
+        state
+            .responses
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&method)
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({ "ok": true }))
+    });
+    state
+        .calls
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(ApiCall {
+            method,
+            params,
+            token,
+        });
+
+    let (status, extra, body) = match rate_limited {
+        Some(retry_after) => (
+            "429 Too Many Requests",
+            format!("Retry-After: {}\r\n", retry_after),
+            serde_json::json!({ "ok": false, "error": "ratelimited" }).to_string(),
+        ),
+        None => ("200 OK", String::new(), response.to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\n{}Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        extra,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn form_params(input: &[u8]) -> Value {
+    Value::Object(
+        url::form_urlencoded::parse(input)
+            .map(|(k, v)| (k.into_owned(), Value::String(v.into_owned())))
+            .collect::<Map<_, _>>(),
+    )
+}
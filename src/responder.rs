@@ -0,0 +1,426 @@
+//! Replies to slash commands and interactions
+//!
+//! Slash commands and interactions carry a `response_url` that accepts up
+//! to [`MAX_USES`] messages within [`URL_LIFETIME`]. A [`Responder`] posts
+//! to it, tracks those limits, and maps failures into [`SlackError`].
+//!
+//! Immediate replies go in the acknowledgement instead:
+//! [`ViewSubmissionResponse`] for `view_submission` response actions and
+//! [`CommandResponse`] for slash commands and [`OptionsResponse`] for
+//! external select `block_suggestion` requests, sent with
+//! [`Ack::ack_response`](crate::api::socket_mode::Ack::ack_response).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use slacko::responder::ResponseMessage;
+//! use slacko::{AuthConfig, MessageBuilder, SlackClient};
+//!
+//! # async fn example(response_url: &str) -> Result<(), Box<dyn std::error::Error>> {
+//! let client = SlackClient::new(AuthConfig::bot("xoxb-token"))?;
+//! let responder = client.responder(response_url);
+//!
+//! responder.send(ResponseMessage::text("Working on it...")).await?;
+//!
+//! let done = MessageBuilder::new().text("Deployed").section("*api* is live");
+//! responder
+//!     .send(ResponseMessage::from(done).in_channel().replace_original(true))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::blocks::{MessageBuilder, OptionGroupObject, OptionObject};
+use crate::error::{Result, SlackError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Number of messages a response URL accepts
+pub const MAX_USES: u32 = 5;
+
+/// How long a response URL stays valid
+pub const URL_LIFETIME: Duration = Duration::from_secs(30 * 60);
+
+/// Visibility of a response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseType {
+    /// Only visible to the user who triggered the interaction
+    Ephemeral,
+    /// Visible to everyone in the channel
+    InChannel,
+}
+
+/// Message posted to a response URL
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResponseMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocks: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_type: Option<ResponseType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replace_original: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_original: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_ts: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mrkdwn: Option<bool>,
+}
+
+impl ResponseMessage {
+    /// Create an empty response
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a text response
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: Some(text.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Create a response deleting the original message
+    pub fn delete() -> Self {
+        Self {
+            delete_original: Some(true),
+            ..Self::default()
+        }
+    }
+
+    /// Set the blocks
+    pub fn blocks(mut self, blocks: Vec<Value>) -> Self {
+        self.blocks = Some(blocks);
+        self
+    }
+
+    /// Only show the response to the user (Slack's default)
+    pub fn ephemeral(mut self) -> Self {
+        self.response_type = Some(ResponseType::Ephemeral);
+        self
+    }
+
+    /// Show the response to everyone in the channel
+    pub fn in_channel(mut self) -> Self {
+        self.response_type = Some(ResponseType::InChannel);
+        self
+    }
+
+    /// Replace the message the interaction came from
+    pub fn replace_original(mut self, replace: bool) -> Self {
+        self.replace_original = Some(replace);
+        self
+    }
+
+    /// Post the response as a thread reply
+    pub fn thread_ts(mut self, ts: impl Into<String>) -> Self {
+        self.thread_ts = Some(ts.into());
+        self
+    }
+}
+
+impl From<MessageBuilder> for ResponseMessage {
+    fn from(message: MessageBuilder) -> Self {
+        Self {
+            text: message.text,
+            blocks: message.blocks,
+            thread_ts: message.thread_ts,
+            mrkdwn: message.mrkdwn,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<&str> for ResponseMessage {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+// ============================================
+// Ack Payloads
+// ============================================
+
+/// `response_action` returned when acknowledging a `view_submission`
+///
+/// Views are the same JSON accepted by `views.open` / `views.update`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "response_action", rename_all = "snake_case")]
+pub enum ViewSubmissionResponse {
+    /// Keep the modal open and show errors under the given blocks
+    Errors { errors: BTreeMap<String, String> },
+    /// Replace the submitted view
+    Update { view: Value },
+    /// Push a new view onto the modal stack
+    Push { view: Value },
+    /// Close every view in the modal stack
+    Clear,
+}
+
+impl ViewSubmissionResponse {
+    /// Show validation errors, keyed by input `block_id`
+    pub fn errors<K, V>(errors: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self::Errors {
+            errors: errors
+                .into_iter()
+                .map(|(block, message)| (block.into(), message.into()))
+                .collect(),
+        }
+    }
+
+    /// Replace the submitted view
+    pub fn update(view: Value) -> Self {
+        Self::Update { view }
+    }
+
+    /// Push a new view
+    pub fn push(view: Value) -> Self {
+        Self::Push { view }
+    }
+
+    /// Close the whole modal stack
+    pub fn clear() -> Self {
+        Self::Clear
+    }
+}
+
+/// Immediate reply returned when acknowledging a slash command
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommandResponse {
+    pub response_type: ResponseType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocks: Option<Vec<Value>>,
+}
+
+impl CommandResponse {
+    /// Reply with blocks only the invoking user sees
+    pub fn ephemeral(blocks: Vec<Value>) -> Self {
+        Self::with_blocks(ResponseType::Ephemeral, blocks)
+    }
+
+    /// Reply with blocks visible to the whole channel
+    pub fn in_channel(blocks: Vec<Value>) -> Self {
+        Self::with_blocks(ResponseType::InChannel, blocks)
+    }
+
+    /// Reply with text only the invoking user sees
+    pub fn ephemeral_text(text: impl Into<String>) -> Self {
+        Self::with_text(ResponseType::Ephemeral, text)
+    }
+
+    /// Reply with text visible to the whole channel
+    pub fn in_channel_text(text: impl Into<String>) -> Self {
+        Self::with_text(ResponseType::InChannel, text)
+    }
+
+    /// Reply with a built message
+    pub fn message(response_type: ResponseType, message: MessageBuilder) -> Self {
+        Self {
+            response_type,
+            text: message.text,
+            blocks: message.blocks,
+        }
+    }
+
+    /// Set the fallback text shown in notifications
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    fn with_blocks(response_type: ResponseType, blocks: Vec<Value>) -> Self {
+        Self {
+            response_type,
+            text: None,
+            blocks: Some(blocks),
+        }
+    }
+
+    fn with_text(response_type: ResponseType, text: impl Into<String>) -> Self {
+        Self {
+            response_type,
+            text: Some(text.into()),
+            blocks: None,
+        }
+    }
+}
+
+/// Options returned when acknowledging a `block_suggestion` request
+///
+/// Slack shows at most 100 options or option groups.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OptionsResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<OptionObject>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option_groups: Option<Vec<OptionGroupObject>>,
+}
+
+impl OptionsResponse {
+    /// Offer a flat list of options
+    pub fn options(options: Vec<OptionObject>) -> Self {
+        Self {
+            options: Some(options),
+            option_groups: None,
+        }
+    }
+
+    /// Offer options in labelled groups
+    pub fn option_groups(groups: Vec<OptionGroupObject>) -> Self {
+        Self {
+            options: None,
+            option_groups: Some(groups),
+        }
+    }
+
+    /// Offer no options
+    pub fn empty() -> Self {
+        Self::options(Vec::new())
+    }
+}
+
+// ============================================
+// Response URLs
+// ============================================
+
+/// Posts messages to a response URL
+///
+/// Clones share the use count, so one responder can be handed to several
+/// tasks. The lifetime is counted from when the responder was created;
+/// create it as soon as the command or interaction arrives.
+#[derive(Clone)]
+pub struct Responder {
+    http: reqwest::Client,
+    url: String,
+    created_at: Instant,
+    uses: Arc<AtomicU32>,
+}
+
+impl Responder {
+    /// Create a responder with its own HTTP client
+    ///
+    /// Prefer [`SlackClient::responder`](crate::SlackClient::responder) to
+    /// share the client's connection pool.
+    pub fn new(response_url: impl Into<String>) -> Self {
+        Self::with_http(reqwest::Client::new(), response_url)
+    }
+
+    pub(crate) fn with_http(http: reqwest::Client, response_url: impl Into<String>) -> Self {
+        Self {
+            http,
+            url: response_url.into(),
+            created_at: Instant::now(),
+            uses: Arc::new(AtomicU32::new(0)),
+        }
+    }
+
+    /// The response URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Number of messages that can still be sent
+    pub fn remaining_uses(&self) -> u32 {
+        MAX_USES.saturating_sub(self.uses.load(Ordering::Acquire))
+    }
+
+    /// Whether the URL is past its [`URL_LIFETIME`]
+    pub fn is_expired(&self) -> bool {
+        self.created_at.elapsed() >= URL_LIFETIME
+    }
+
+    /// Post a message
+    ///
+    /// Fails without a request when the URL has expired or was used
+    /// [`MAX_USES`] times, with the same error codes Slack would return
+    /// (`expired_url`, `used_url`).
+    pub async fn send(&self, message: impl Into<ResponseMessage>) -> Result<()> {
+        if self.is_expired() {
+            return Err(SlackError::api_error("response_url", "expired_url"));
+        }
+        let claimed = self
+            .uses
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < MAX_USES).then_some(n + 1)
+            });
+        if claimed.is_err() {
+            return Err(SlackError::api_error("response_url", "used_url"));
+        }
+
+        let response = self
+            .http
+            .post(&self.url)
+            .json(&message.into())
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.as_u16() == 429 {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60);
+            return Err(SlackError::RateLimitExceeded { retry_after });
+        }
+
+        // Slack answers with plain "ok", a plain error code, or JSON
+        let body = response.text().await.unwrap_or_default();
+        let error = match serde_json::from_str::<Value>(&body) {
+            Ok(json) if json.get("ok").and_then(|ok| ok.as_bool()) == Some(false) => json
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown_error")
+                .to_string(),
+            _ if status.is_success() => return Ok(()),
+            _ if !body.trim().is_empty() => body.trim().to_string(),
+            _ => format!("http_{}", status.as_u16()),
+        };
+        Err(SlackError::api_error("response_url", error))
+    }
+
+    /// Post an ephemeral text message
+    pub async fn ephemeral(&self, text: impl Into<String>) -> Result<()> {
+        self.send(ResponseMessage::text(text).ephemeral()).await
+    }
+
+    /// Post a text message visible to the whole channel
+    pub async fn in_channel(&self, text: impl Into<String>) -> Result<()> {
+        self.send(ResponseMessage::text(text).in_channel()).await
+    }
+
+    /// Replace the original message with new text
+    pub async fn replace(&self, text: impl Into<String>) -> Result<()> {
+        self.send(ResponseMessage::text(text).replace_original(true))
+            .await
+    }
+
+    /// Delete the original message
+    pub async fn delete_original(&self) -> Result<()> {
+        self.send(ResponseMessage::delete()).await
+    }
+}
+
+impl std::fmt::Debug for Responder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Responder")
+            .field("remaining_uses", &self.remaining_uses())
+            .field("expired", &self.is_expired())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,315 @@
+//! Scheduled messages
+//!
+//! [`ScheduledMessages`] wraps `chat.scheduleMessage`,
+//! `chat.scheduledMessages.list` and `chat.deleteScheduledMessage`. It
+//! schedules [`MessageBuilder`] messages at a `DateTime` in any time
+//! zone, checks Slack's window of [`MAX_SCHEDULE_DAYS`] days before
+//! calling the API, lists every page across channels, reschedules and
+//! cancels messages matching a [`ScheduledFilter`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use slacko::{SlackClient, AuthConfig};
+//! use chrono::{Duration, Utc};
+//! use slacko::blocks::MessageBuilder;
+//! use slacko::scheduled::ScheduledFilter;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = SlackClient::new(AuthConfig::bot("xoxb-token"))?;
+//! let scheduled = client.chat().scheduled();
+//!
+//! let message = MessageBuilder::new()
+//!     .text("Standup in 5 minutes")
+//!     .section("*Standup* starts in 5 minutes");
+//! scheduled
+//!     .schedule("C123", message, Utc::now() + Duration::hours(2))
+//!     .await?;
+//!
+//! // Cancel everything queued for #incidents
+//! let cancelled = scheduled
+//!     .cancel(ScheduledFilter::new().channel("C456"))
+//!     .await?;
+//! println!("Cancelled {} messages", cancelled.deleted.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::chat::{
+    ScheduleMessageRequest, ScheduleMessageResponse, ScheduledMessage, ScheduledMessagesListRequest,
+};
+use crate::blocks::MessageBuilder;
+use crate::client::SlackClient;
+use crate::error::{Result, SlackError};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tracing::warn;
+
+/// How far ahead Slack accepts scheduled messages
+pub const MAX_SCHEDULE_DAYS: i64 = 120;
+
+/// Scheduled messages listed per page
+const PAGE_SIZE: u32 = 100;
+
+/// Manager for scheduled messages
+///
+/// Created with [`ChatApi::scheduled`](crate::api::chat::ChatApi::scheduled).
+#[derive(Clone)]
+pub struct ScheduledMessages {
+    client: SlackClient,
+}
+
+impl ScheduledMessages {
+    pub(crate) fn new(client: SlackClient) -> Self {
+        Self { client }
+    }
+
+    /// Schedule a message for `at`
+    ///
+    /// Use [`schedule_with_options`](Self::schedule_with_options) for
+    /// attachments and other `chat.scheduleMessage` options.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel ID
+    /// * `message` - Text and blocks of the message
+    /// * `at` - When to post; must be in the future and within [`MAX_SCHEDULE_DAYS`]
+    pub async fn schedule<Tz: TimeZone>(
+        &self,
+        channel: &str,
+        message: MessageBuilder,
+        at: DateTime<Tz>,
+    ) -> Result<ScheduleMessageResponse> {
+        let params = ScheduleMessageRequest {
+            channel: channel.to_string(),
+            ..message.into()
+        };
+        self.schedule_with_options(params, at).await
+    }
+
+    /// Schedule a message with full options for `at`
+    ///
+    /// Overrides the request's `post_at`.
+    pub async fn schedule_with_options<Tz: TimeZone>(
+        &self,
+        mut params: ScheduleMessageRequest,
+        at: DateTime<Tz>,
+    ) -> Result<ScheduleMessageResponse> {
+        params.post_at = validate_post_at(&at)?;
+        self.client
+            .chat()
+            .schedule_message_with_options(params)
+            .await
+    }
+
+    /// Every scheduled message matching `filter`, following all pages
+    ///
+    /// An empty filter lists messages in every channel.
+    pub async fn list(&self, filter: &ScheduledFilter) -> Result<Vec<ScheduledMessage>> {
+        let chat = self.client.chat();
+        let mut messages = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let response = chat
+                .scheduled_messages_list_with_options(ScheduledMessagesListRequest {
+                    channel: filter.channel.clone(),
+                    cursor: cursor.take(),
+                    oldest: filter.after.map(|t| t.timestamp().to_string()),
+                    latest: filter.before.map(|t| t.timestamp().to_string()),
+                    limit: Some(PAGE_SIZE),
+                    team_id: None,
+                })
+                .await?;
+            messages.extend(
+                response
+                    .scheduled_messages
+                    .into_iter()
+                    .filter(|m| filter.matches(m)),
+            );
+
+            match response.response_metadata.and_then(|m| m.next_cursor) {
+                Some(next) if !next.is_empty() => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(messages)
+    }
+
+    /// Move a scheduled message to `at`, keeping its text
+    ///
+    /// Slack cannot edit scheduled messages, so a copy is scheduled and the
+    /// original deleted once the copy exists. The list API only returns
+    /// text, so a message without text is refused with
+    /// [`SlackError::InvalidParameter`] and left as it is; use
+    /// [`reschedule_message`](Self::reschedule_message) to keep blocks or
+    /// attachments.
+    pub async fn reschedule<Tz: TimeZone>(
+        &self,
+        scheduled: &ScheduledMessage,
+        at: DateTime<Tz>,
+    ) -> Result<ScheduleMessageResponse> {
+        let text = match scheduled.text.as_deref() {
+            Some(text) if !text.trim().is_empty() => text,
+            _ => {
+                return Err(SlackError::InvalidParameter(format!(
+                    "scheduled message {} has no text to reschedule; use reschedule_message",
+                    scheduled.id
+                )))
+            }
+        };
+        let message = MessageBuilder::new().text(text);
+        self.reschedule_message(scheduled, message, at).await
+    }
+
+    /// Replace a scheduled message with `message` posted at `at`
+    ///
+    /// The replacement is scheduled before the original is deleted. If the
+    /// delete fails, the replacement is deleted again and the error returned.
+    pub async fn reschedule_message<Tz: TimeZone>(
+        &self,
+        scheduled: &ScheduledMessage,
+        message: MessageBuilder,
+        at: DateTime<Tz>,
+    ) -> Result<ScheduleMessageResponse> {
+        let chat = self.client.chat();
+        let replacement = self.schedule(&scheduled.channel_id, message, at).await?;
+
+        if let Err(e) = chat
+            .delete_scheduled_message(&scheduled.channel_id, &scheduled.id)
+            .await
+        {
+            if let Err(undo) = chat
+                .delete_scheduled_message(&replacement.channel, &replacement.scheduled_message_id)
+                .await
+            {
+                warn!(
+                    "Failed to delete replacement {} after failed reschedule: {}",
+                    replacement.scheduled_message_id, undo
+                );
+            }
+            return Err(e);
+        }
+
+        Ok(replacement)
+    }
+
+    /// Delete every scheduled message matching `filter`
+    ///
+    /// A failed delete does not stop the others; the report lists what was
+    /// deleted and what failed, so failures can be retried. Only listing
+    /// the messages returns an error.
+    pub async fn cancel(&self, filter: ScheduledFilter) -> Result<CancelReport> {
+        let chat = self.client.chat();
+        let mut report = CancelReport::default();
+        for message in self.list(&filter).await? {
+            match chat
+                .delete_scheduled_message(&message.channel_id, &message.id)
+                .await
+            {
+                Ok(_) => report.deleted.push(message),
+                Err(e) => {
+                    warn!("Failed to cancel scheduled message {}: {}", message.id, e);
+                    report.failed.push((message, e));
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Outcome of [`ScheduledMessages::cancel`]
+#[derive(Debug, Default)]
+pub struct CancelReport {
+    /// Messages that were deleted
+    pub deleted: Vec<ScheduledMessage>,
+    /// Messages that are still scheduled, with the error from deleting them
+    pub failed: Vec<(ScheduledMessage, SlackError)>,
+}
+
+impl CancelReport {
+    /// Whether every matching message was deleted
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Selects scheduled messages to list or cancel
+///
+/// Conditions are combined; an empty filter matches every message.
+#[derive(Debug, Clone, Default)]
+pub struct ScheduledFilter {
+    channel: Option<String>,
+    after: Option<DateTime<Utc>>,
+    before: Option<DateTime<Utc>>,
+    text_contains: Option<String>,
+}
+
+impl ScheduledFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only messages in this channel
+    pub fn channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = Some(channel.into());
+        self
+    }
+
+    /// Only messages posted at or after `time`
+    pub fn after<Tz: TimeZone>(mut self, time: DateTime<Tz>) -> Self {
+        self.after = Some(time.with_timezone(&Utc));
+        self
+    }
+
+    /// Only messages posted at or before `time`
+    pub fn before<Tz: TimeZone>(mut self, time: DateTime<Tz>) -> Self {
+        self.before = Some(time.with_timezone(&Utc));
+        self
+    }
+
+    /// Only messages whose text contains `text`
+    pub fn text_contains(mut self, text: impl Into<String>) -> Self {
+        self.text_contains = Some(text.into());
+        self
+    }
+
+    /// Whether `message` passes the filter
+    pub fn matches(&self, message: &ScheduledMessage) -> bool {
+        self.channel
+            .as_ref()
+            .is_none_or(|channel| &message.channel_id == channel)
+            && self
+                .after
+                .is_none_or(|after| message.post_at >= after.timestamp())
+            && self
+                .before
+                .is_none_or(|before| message.post_at <= before.timestamp())
+            && self.text_contains.as_ref().is_none_or(|text| {
+                message
+                    .text
+                    .as_deref()
+                    .is_some_and(|message| message.contains(text.as_str()))
+            })
+    }
+}
+
+/// Unix timestamp for `at`, or an error if Slack would reject it
+fn validate_post_at<Tz: TimeZone>(at: &DateTime<Tz>) -> Result<i64> {
+    let at = at.with_timezone(&Utc);
+    let now = Utc::now();
+    if at <= now {
+        return Err(SlackError::InvalidParameter(format!(
+            "post_at {} is in the past",
+            at.to_rfc3339()
+        )));
+    }
+    if at > now + Duration::days(MAX_SCHEDULE_DAYS) {
+        return Err(SlackError::InvalidParameter(format!(
+            "post_at {} is more than {} days ahead",
+            at.to_rfc3339(),
+            MAX_SCHEDULE_DAYS
+        )));
+    }
+    Ok(at.timestamp())
+}
@@ -0,0 +1,913 @@
+//! Streaming messages from token streams
+//!
+//! [`MessageStream`] writes text into a message started with
+//! `chat.startStream`. Writes are buffered and sent with
+//! `chat.appendStream` once [`flush_size`](MessageStreamBuilder::flush_size)
+//! characters are waiting or [`flush_interval`](MessageStreamBuilder::flush_interval)
+//! has passed, so one API call carries many tokens. Rate-limited appends
+//! are retried after the requested delay, with the text written meanwhile
+//! merged in.
+//!
+//! The stream is finalized with `chat.stopStream` when it is closed, when
+//! it is dropped, and after an append fails, so a message is never left
+//! streaming.
+//!
+//! Workspaces without `chat.startStream` get the same interface through
+//! [`StreamMode::Updates`]: a placeholder message is posted and edited
+//! with throttled `chat.update` calls. Text beyond [`MAX_MESSAGE_CHARS`]
+//! or [`MAX_MESSAGE_BLOCKS`] continues in thread replies. Edits are
+//! spaced per channel across every stream from the same client, since
+//! Slack limits updates per channel. The default [`StreamMode::Auto`] falls
+//! back to updates when the workspace or token cannot stream natively.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures_util::stream;
+//! # use slacko::{SlackClient, AuthConfig};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = SlackClient::new(AuthConfig::bot("xoxb-token"))?;
+//! let tokens = stream::iter(["Hello", ", ", "world"].map(String::from));
+//!
+//! let message = client
+//!     .chat()
+//!     .message_stream("C123")
+//!     .thread_ts("1700000000.000100")
+//!     .start()
+//!     .await?
+//!     .pipe(tokens)
+//!     .await?;
+//! println!("Finished message {}", message.ts);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::chat::{PostMessageRequest, StopStreamResponse, UpdateMessageRequest};
+use crate::client::SlackClient;
+use crate::error::{Result, SlackError};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+use tokio::io::AsyncWrite;
+use tokio::sync::{oneshot, Notify};
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+/// Default time text may wait in the buffer before it is sent
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Default buffered length that triggers an append right away
+pub const DEFAULT_FLUSH_SIZE: usize = 512;
+
+/// Default buffered length at which writers wait for appends to catch up
+pub const DEFAULT_MAX_BUFFER: usize = 16 * 1024;
+
+/// Default number of consecutive rate-limited calls retried
+pub const DEFAULT_MAX_RETRIES: u32 = 5;
+
+/// Default time between `chat.update` edits in [`StreamMode::Updates`]
+///
+/// Slack allows about one message update per second per channel.
+pub const DEFAULT_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Default text of the placeholder message in [`StreamMode::Updates`]
+pub const DEFAULT_PLACEHOLDER: &str = "…";
+
+/// Characters Slack displays in a message's text before truncating it
+pub const MAX_MESSAGE_CHARS: usize = 4000;
+
+/// Blocks allowed in one message
+pub const MAX_MESSAGE_BLOCKS: usize = 50;
+
+/// Characters allowed in a section block's text
+const MAX_SECTION_CHARS: usize = 3000;
+
+/// `chat.startStream` errors meaning native streaming is unavailable
+const STREAMING_UNAVAILABLE: &[&str] = &[
+    "unknown_method",
+    "not_allowed_token_type",
+    "feature_not_enabled",
+    "method_not_supported_for_channel_type",
+];
+
+/// How a [`MessageStream`] delivers text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StreamMode {
+    /// Native streaming, falling back to updates if `chat.startStream` is
+    /// unavailable to the workspace or token
+    #[default]
+    Auto,
+    /// `chat.startStream` / `appendStream` / `stopStream`
+    Native,
+    /// A placeholder message edited with `chat.update`
+    Updates,
+}
+
+// ============================================
+// Builder
+// ============================================
+
+/// Configures and starts a [`MessageStream`]
+///
+/// Created by [`ChatApi::message_stream`](crate::api::chat::ChatApi::message_stream).
+#[derive(Clone)]
+pub struct MessageStreamBuilder {
+    client: SlackClient,
+    channel: String,
+    thread_ts: Option<String>,
+    flush_interval: Duration,
+    flush_size: usize,
+    max_buffer: usize,
+    max_retries: u32,
+    mode: StreamMode,
+    update_interval: Duration,
+    placeholder: String,
+}
+
+impl MessageStreamBuilder {
+    pub(crate) fn new(client: SlackClient, channel: impl Into<String>) -> Self {
+        Self {
+            client,
+            channel: channel.into(),
+            thread_ts: None,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            flush_size: DEFAULT_FLUSH_SIZE,
+            max_buffer: DEFAULT_MAX_BUFFER,
+            max_retries: DEFAULT_MAX_RETRIES,
+            mode: StreamMode::Auto,
+            update_interval: DEFAULT_UPDATE_INTERVAL,
+            placeholder: DEFAULT_PLACEHOLDER.to_string(),
+        }
+    }
+
+    /// Choose native streaming, updates, or native with fallback (default)
+    pub fn mode(mut self, mode: StreamMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the time between edits in [`StreamMode::Updates`]
+    ///
+    /// Defaults to [`DEFAULT_UPDATE_INTERVAL`]. Edits to one channel are
+    /// spaced across every stream from the same client. The flush size
+    /// does not apply to updates.
+    pub fn update_interval(mut self, interval: Duration) -> Self {
+        self.update_interval = interval;
+        self
+    }
+
+    /// Set the text posted before any text is written in [`StreamMode::Updates`]
+    pub fn placeholder(mut self, text: impl Into<String>) -> Self {
+        self.placeholder = text.into();
+        self
+    }
+
+    /// Stream the message as a reply in this thread
+    pub fn thread_ts(mut self, thread_ts: impl Into<String>) -> Self {
+        self.thread_ts = Some(thread_ts.into());
+        self
+    }
+
+    /// Set how long text may wait before it is sent
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = interval;
+        self
+    }
+
+    /// Set the buffered length that is sent without waiting for the interval
+    pub fn flush_size(mut self, size: usize) -> Self {
+        self.flush_size = size.max(1);
+        self
+    }
+
+    /// Set the buffered length at which writes wait (backpressure)
+    pub fn max_buffer(mut self, size: usize) -> Self {
+        self.max_buffer = size.max(1);
+        self
+    }
+
+    /// Set how many consecutive rate-limited calls are retried
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Start the message
+    ///
+    /// Calls `chat.startStream`, or posts the placeholder message in
+    /// [`StreamMode::Updates`] and when [`StreamMode::Auto`] falls back.
+    /// Other `chat.startStream` errors, such as `channel_not_found`, are
+    /// returned as they are.
+    pub async fn start(self) -> Result<MessageStream> {
+        let (channel, ts, backend) = match self.mode {
+            StreamMode::Native => self.start_native().await?,
+            StreamMode::Updates => self.start_updates().await?,
+            StreamMode::Auto => match self.start_native().await {
+                Ok(started) => started,
+                Err(SlackError::ApiError { code, message })
+                    if STREAMING_UNAVAILABLE.contains(&message.as_str()) =>
+                {
+                    info!(
+                        "Native streaming unavailable ({}: {}), falling back to updates",
+                        code, message
+                    );
+                    self.start_updates().await?
+                }
+                Err(e) => return Err(e),
+            },
+        };
+
+        let (flush_interval, flush_size) = match backend {
+            Backend::Native { .. } => (self.flush_interval, self.flush_size),
+            Backend::Updates(_) => (self.update_interval, usize::MAX),
+        };
+        let stream_id = match &backend {
+            Backend::Native { stream_id } => Some(stream_id.clone()),
+            Backend::Updates(_) => None,
+        };
+
+        let shared = Arc::new(Shared::default());
+        let (done_tx, done_rx) = oneshot::channel();
+        let writer = Writer {
+            client: self.client,
+            channel: channel.clone(),
+            backend,
+            flush_interval,
+            flush_size,
+            max_retries: self.max_retries,
+            shared: shared.clone(),
+        };
+        tokio::spawn(async move {
+            let _ = done_tx.send(writer.run().await);
+        });
+
+        Ok(MessageStream {
+            channel,
+            ts,
+            stream_id,
+            shared,
+            max_buffer: self.max_buffer,
+            done: Some(done_rx),
+            finished: None,
+            partial_utf8: Vec::new(),
+        })
+    }
+
+    async fn start_native(&self) -> Result<(String, String, Backend)> {
+        let started = self
+            .client
+            .chat()
+            .start_stream(&self.channel, self.thread_ts.as_deref())
+            .await?;
+        Ok((
+            started.channel,
+            started.ts,
+            Backend::Native {
+                stream_id: started.stream_id,
+            },
+        ))
+    }
+
+    async fn start_updates(&self) -> Result<(String, String, Backend)> {
+        let mut request = PostMessageRequest::new(&self.channel).text(&self.placeholder);
+        if let Some(thread_ts) = &self.thread_ts {
+            request = request.thread_ts(thread_ts);
+        }
+        let chat = self.client.chat();
+        let posted = retrying(self.max_retries, || {
+            chat.post_message_with_options(request.clone())
+        })
+        .await?;
+
+        let updates = Updates {
+            thread_ts: self.thread_ts.clone().unwrap_or_else(|| posted.ts.clone()),
+            first_ts: posted.ts.clone(),
+            current_ts: posted.ts.clone(),
+            content: String::new(),
+            shown: None,
+            placeholder: self.placeholder.clone(),
+            interval: self.update_interval,
+        };
+        Ok((posted.channel, posted.ts, Backend::Updates(updates)))
+    }
+}
+
+// ============================================
+// Message Stream
+// ============================================
+
+/// A message being streamed into Slack
+///
+/// Write text with [`write`](Self::write), [`pipe`](Self::pipe),
+/// [`Sink<String>`](Sink) or [`AsyncWrite`], then [`finish`](Self::finish).
+/// Flushing sends buffered text right away, so prefer `feed` over `send`
+/// to let tokens coalesce. Dropping the stream finalizes it in the
+/// background.
+pub struct MessageStream {
+    channel: String,
+    ts: String,
+    stream_id: Option<String>,
+    shared: Arc<Shared>,
+    max_buffer: usize,
+    done: Option<oneshot::Receiver<Result<StopStreamResponse>>>,
+    finished: Option<StopStreamResponse>,
+    /// Trailing bytes of a UTF-8 sequence split across `AsyncWrite` calls
+    partial_utf8: Vec<u8>,
+}
+
+impl MessageStream {
+    /// Channel the message is in
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// Timestamp of the message
+    ///
+    /// In [`StreamMode::Updates`] this is the first message; overflow is
+    /// posted as thread replies.
+    pub fn ts(&self) -> &str {
+        &self.ts
+    }
+
+    /// Stream ID from `chat.startStream`, or `None` when using updates
+    pub fn stream_id(&self) -> Option<&str> {
+        self.stream_id.as_deref()
+    }
+
+    /// The delivery mode in use ([`StreamMode::Native`] or [`StreamMode::Updates`])
+    pub fn mode(&self) -> StreamMode {
+        match self.stream_id {
+            Some(_) => StreamMode::Native,
+            None => StreamMode::Updates,
+        }
+    }
+
+    /// Queue text, waiting only if the buffer is full
+    pub async fn write(&mut self, text: impl Into<String>) -> Result<()> {
+        self.feed(text.into()).await
+    }
+
+    /// Write every item of `stream`, then finish the message
+    pub async fn pipe<S>(mut self, stream: S) -> Result<StopStreamResponse>
+    where
+        S: Stream<Item = String>,
+    {
+        let mut stream = std::pin::pin!(stream);
+        while let Some(text) = stream.next().await {
+            self.feed(text).await?;
+        }
+        self.finish().await
+    }
+
+    /// Send the remaining text and finalize the message
+    pub async fn finish(mut self) -> Result<StopStreamResponse> {
+        self.close().await?;
+        self.finished
+            .take()
+            .ok_or_else(|| SlackError::Other("message stream already finished".into()))
+    }
+
+    fn poll_done(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let Some(done) = self.done.as_mut() else {
+            return Poll::Ready(Ok(()));
+        };
+        let result = match Pin::new(done).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        self.done = None;
+        match result {
+            Ok(Ok(response)) => {
+                self.finished = Some(response);
+                Poll::Ready(Ok(()))
+            }
+            Ok(Err(e)) => Poll::Ready(Err(e)),
+            Err(_) => Poll::Ready(Err(SlackError::Other(
+                "message stream task stopped unexpectedly".into(),
+            ))),
+        }
+    }
+}
+
+impl Sink<String> for MessageStream {
+    type Error = SlackError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let mut state = self.shared.lock();
+        if let Some(error) = state.failure() {
+            return Poll::Ready(Err(error));
+        }
+        if state.buffer.len() >= self.max_buffer {
+            state.wakers.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, text: String) -> Result<()> {
+        let mut state = self.shared.lock();
+        if let Some(error) = state.failure() {
+            return Err(error);
+        }
+        if state.closed {
+            return Err(SlackError::InvalidParameter(
+                "message stream is finished".into(),
+            ));
+        }
+        state.buffer.push_str(&text);
+        drop(state);
+        self.shared.notify.notify_one();
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let mut state = self.shared.lock();
+        if let Some(error) = state.failure() {
+            return Poll::Ready(Err(error));
+        }
+        if state.buffer.is_empty() && !state.in_flight {
+            return Poll::Ready(Ok(()));
+        }
+        state.flush_requested = true;
+        state.wakers.push(cx.waker().clone());
+        drop(state);
+        self.shared.notify.notify_one();
+        Poll::Pending
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let mut state = self.shared.lock();
+        if !state.closed {
+            state.closed = true;
+            drop(state);
+            self.shared.notify.notify_one();
+        } else {
+            drop(state);
+        }
+        self.poll_done(cx)
+    }
+}
+
+impl AsyncWrite for MessageStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.as_mut().poll_ready(cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(io::Error::other(e))),
+            Poll::Pending => return Poll::Pending,
+        }
+
+        let mut bytes = std::mem::take(&mut self.partial_utf8);
+        bytes.extend_from_slice(buf);
+        let valid = match std::str::from_utf8(&bytes) {
+            Ok(_) => bytes.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidData, e))),
+        };
+        self.partial_utf8 = bytes.split_off(valid);
+        if !bytes.is_empty() {
+            let text = String::from_utf8(bytes).expect("validated above");
+            if let Err(e) = self.as_mut().start_send(text) {
+                return Poll::Ready(Err(io::Error::other(e)));
+            }
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Sink::poll_flush(self, cx).map_err(io::Error::other)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Sink::poll_close(self, cx).map_err(io::Error::other)
+    }
+}
+
+impl Drop for MessageStream {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        if !state.closed {
+            debug!("Message stream dropped, finalizing {}", self.ts);
+            state.closed = true;
+            drop(state);
+            self.shared.notify.notify_one();
+        }
+    }
+}
+
+impl std::fmt::Debug for MessageStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageStream")
+            .field("channel", &self.channel)
+            .field("ts", &self.ts)
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================
+// Writer Task
+// ============================================
+
+/// State shared between a [`MessageStream`] and its writer task
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    /// Wakes the writer task
+    notify: Notify,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[derive(Default)]
+struct State {
+    buffer: String,
+    in_flight: bool,
+    flush_requested: bool,
+    closed: bool,
+    failed: Option<String>,
+    /// Writers waiting for buffer space or a flush
+    wakers: Vec<Waker>,
+}
+
+impl State {
+    fn failure(&self) -> Option<SlackError> {
+        self.failed
+            .as_ref()
+            .map(|e| SlackError::Other(format!("message stream failed: {}", e)))
+    }
+
+    fn wake_writers(&mut self) {
+        for waker in self.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// Sends buffered text until the stream is closed
+struct Writer {
+    client: SlackClient,
+    channel: String,
+    backend: Backend,
+    flush_interval: Duration,
+    flush_size: usize,
+    max_retries: u32,
+    shared: Arc<Shared>,
+}
+
+impl Writer {
+    async fn run(mut self) -> Result<StopStreamResponse> {
+        let mut last_flush = Instant::now();
+        let mut retries = 0;
+
+        while let Some(chunk) = self.next_chunk(last_flush).await {
+            let result = match &mut self.backend {
+                Backend::Native { stream_id } => self
+                    .client
+                    .chat()
+                    .append_stream(&self.channel, stream_id, &chunk)
+                    .await
+                    .map(|_| ()),
+                // Retried inside, since the text is already part of the message
+                Backend::Updates(updates) => {
+                    updates
+                        .append(&self.client, &self.channel, &chunk, self.max_retries)
+                        .await
+                }
+            };
+            let native = matches!(self.backend, Backend::Native { .. });
+            match result {
+                Ok(_) => {
+                    retries = 0;
+                    last_flush = Instant::now();
+                    self.finish_append(None);
+                }
+                Err(SlackError::RateLimitExceeded { retry_after })
+                    if native && retries < self.max_retries =>
+                {
+                    retries += 1;
+                    debug!("appendStream rate limited, retrying in {}s", retry_after);
+                    // Put the text back so anything written meanwhile is merged in
+                    self.finish_append(Some(chunk));
+                    tokio::time::sleep(Duration::from_secs(retry_after)).await;
+                }
+                Err(e) => {
+                    warn!("Append failed, finalizing stream: {}", e);
+                    self.fail(&e);
+                    if let Err(stop) = self.stop().await {
+                        warn!("Failed to finalize stream: {}", stop);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        let result = self.stop().await;
+        if let Err(e) = &result {
+            self.fail(e);
+        }
+        result
+    }
+
+    /// Wait until buffered text is due, or return `None` once closed and drained
+    async fn next_chunk(&self, last_flush: Instant) -> Option<String> {
+        loop {
+            let deadline = {
+                let mut state = self.shared.lock();
+                if !state.buffer.is_empty() {
+                    let due = state.closed
+                        || state.flush_requested
+                        || state.buffer.len() >= self.flush_size
+                        || last_flush.elapsed() >= self.flush_interval;
+                    if due {
+                        state.in_flight = true;
+                        state.flush_requested = false;
+                        let chunk = std::mem::take(&mut state.buffer);
+                        state.wake_writers();
+                        return Some(chunk);
+                    }
+                    Some(last_flush + self.flush_interval)
+                } else if state.closed {
+                    return None;
+                } else {
+                    state.flush_requested = false;
+                    state.wake_writers();
+                    None
+                }
+            };
+
+            match deadline {
+                Some(deadline) => {
+                    tokio::select! {
+                        _ = self.shared.notify.notified() => {}
+                        _ = tokio::time::sleep_until(deadline) => {}
+                    }
+                }
+                None => self.shared.notify.notified().await,
+            }
+        }
+    }
+
+    fn finish_append(&self, unsent: Option<String>) {
+        let mut state = self.shared.lock();
+        if let Some(text) = unsent {
+            state.buffer.insert_str(0, &text);
+            state.flush_requested = true;
+        }
+        state.in_flight = false;
+        state.wake_writers();
+    }
+
+    fn fail(&self, error: &SlackError) {
+        let mut state = self.shared.lock();
+        state.failed = Some(error.to_string());
+        state.in_flight = false;
+        state.closed = true;
+        state.wake_writers();
+    }
+
+    /// Finalize the message, retrying rate-limited calls
+    async fn stop(&mut self) -> Result<StopStreamResponse> {
+        match &mut self.backend {
+            Backend::Native { stream_id } => {
+                let chat = self.client.chat();
+                retrying(self.max_retries, || {
+                    chat.stop_stream(&self.channel, stream_id)
+                })
+                .await
+            }
+            Backend::Updates(updates) => {
+                updates
+                    .finish(&self.client, &self.channel, self.max_retries)
+                    .await?;
+                Ok(StopStreamResponse {
+                    channel: self.channel.clone(),
+                    ts: updates.first_ts.clone(),
+                })
+            }
+        }
+    }
+}
+
+/// Where a writer sends text
+enum Backend {
+    Native { stream_id: String },
+    Updates(Updates),
+}
+
+/// Retry `call` while it is rate limited, up to `max_retries` times
+async fn retrying<T, F, Fut>(max_retries: u32, mut call: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut retries = 0;
+    loop {
+        match call().await {
+            Err(SlackError::RateLimitExceeded { retry_after }) if retries < max_retries => {
+                retries += 1;
+                debug!("Rate limited, retrying in {}s", retry_after);
+                tokio::time::sleep(Duration::from_secs(retry_after)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+// ============================================
+// Progressive Updates
+// ============================================
+
+/// Messages edited with `chat.update` in [`StreamMode::Updates`]
+struct Updates {
+    /// Thread that overflow replies are posted in
+    thread_ts: String,
+    first_ts: String,
+    /// Message currently being edited
+    current_ts: String,
+    /// Text of the current message
+    content: String,
+    /// Text last sent to the current message, `None` while it shows the placeholder
+    shown: Option<String>,
+    placeholder: String,
+    /// Time between edits in the channel
+    interval: Duration,
+}
+
+impl Updates {
+    async fn append(
+        &mut self,
+        client: &SlackClient,
+        channel: &str,
+        text: &str,
+        max_retries: u32,
+    ) -> Result<()> {
+        self.content.push_str(text);
+
+        while !fits(&self.content) {
+            let cut = split_point(&self.content);
+            let head = self.content[..cut].to_string();
+            let rest = self.content[cut..].trim_start().to_string();
+            self.edit(client, channel, &head, max_retries).await?;
+            if rest.is_empty() {
+                // The next write that overflows starts the reply
+                self.content = head;
+                break;
+            }
+
+            let reply = PostMessageRequest::new(channel)
+                .text(&self.placeholder)
+                .thread_ts(&self.thread_ts);
+            let chat = client.chat();
+            let posted = retrying(max_retries, || {
+                chat.post_message_with_options(reply.clone())
+            })
+            .await?;
+            debug!("Stream continues in reply {}", posted.ts);
+            self.current_ts = posted.ts;
+            self.content = rest;
+            self.shown = None;
+        }
+
+        if self.content.trim().is_empty() || self.shown.as_ref() == Some(&self.content) {
+            return Ok(());
+        }
+        let content = self.content.clone();
+        self.edit(client, channel, &content, max_retries).await
+    }
+
+    /// Replace whatever the current message still shows with the final text
+    async fn finish(
+        &mut self,
+        client: &SlackClient,
+        channel: &str,
+        max_retries: u32,
+    ) -> Result<()> {
+        if self.shown.as_ref() == Some(&self.content) {
+            return Ok(());
+        }
+        let content = self.content.clone();
+        self.edit(client, channel, &content, max_retries).await
+    }
+
+    async fn edit(
+        &mut self,
+        client: &SlackClient,
+        channel: &str,
+        content: &str,
+        max_retries: u32,
+    ) -> Result<()> {
+        client.update_throttle.wait(channel, self.interval).await;
+        let chat = client.chat();
+        retrying(max_retries, || {
+            chat.update_message_with_options(UpdateMessageRequest {
+                channel: channel.to_string(),
+                ts: self.current_ts.clone(),
+                text: Some(content.to_string()),
+                blocks: Some(render_blocks(content)),
+                as_user: None,
+            })
+        })
+        .await?;
+        self.shown = Some(content.to_string());
+        Ok(())
+    }
+}
+
+/// Next allowed `chat.update` time per channel, shared by a client's streams
+#[derive(Default)]
+pub(crate) struct ChannelThrottle {
+    next: Mutex<HashMap<String, Instant>>,
+}
+
+impl ChannelThrottle {
+    /// Wait for the channel's next slot and reserve the one `interval` after it
+    async fn wait(&self, channel: &str, interval: Duration) {
+        let slot = {
+            let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            next.retain(|_, at| *at > now);
+            let slot = next.get(channel).copied().unwrap_or(now).max(now);
+            next.insert(channel.to_string(), slot + interval);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// Section blocks for `content`, one or more per paragraph
+fn render_blocks(content: &str) -> Vec<Value> {
+    content
+        .split("\n\n")
+        .filter(|paragraph| !paragraph.trim().is_empty())
+        .flat_map(|paragraph| chunks(paragraph, MAX_SECTION_CHARS))
+        .map(|text| json!({ "type": "section", "text": { "type": "mrkdwn", "text": text } }))
+        .collect()
+}
+
+/// Split `text` into pieces of at most `max` characters
+fn chunks(text: &str, max: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let end = byte_index(rest, max);
+        pieces.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    pieces
+}
+
+/// Byte index of the `chars`th character, or the end of `text`
+fn byte_index(text: &str, chars: usize) -> usize {
+    text.char_indices()
+        .nth(chars)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len())
+}
+
+/// Whether `content` fits in one message
+fn fits(content: &str) -> bool {
+    content.chars().count() <= MAX_MESSAGE_CHARS
+        && render_blocks(content).len() <= MAX_MESSAGE_BLOCKS
+}
+
+/// Byte index where `content` is split so the first part fits in a message
+///
+/// Prefers paragraph breaks, then line breaks and spaces, over cutting
+/// mid-word.
+fn split_point(content: &str) -> usize {
+    let limit = byte_index(content, MAX_MESSAGE_CHARS);
+    let mut cut = limit;
+
+    // Too many blocks: drop whole paragraphs until the rest fits
+    while !fits(&content[..cut]) {
+        match content[..cut].trim_end().rfind("\n\n") {
+            Some(i) if i > 0 => cut = i,
+            _ => break,
+        }
+    }
+    if cut < limit {
+        return cut;
+    }
+
+    let window = &content[..cut];
+    window
+        .rfind("\n\n")
+        .or_else(|| window.rfind('\n'))
+        .or_else(|| window.rfind(' '))
+        .filter(|&i| i >= cut / 2)
+        .unwrap_or(cut)
+}
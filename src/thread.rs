@@ -0,0 +1,224 @@
+//! Message threads
+//!
+//! [`Thread`] is a handle on a thread, identified by its channel and the
+//! timestamp of its root message. It loads every page of
+//! `conversations.replies`, posts replies (optionally broadcast to the
+//! channel), marks the thread read and follows new replies from a Socket
+//! Mode event stream.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use slacko::{SlackClient, AuthConfig};
+//! # use slacko::api::socket_mode::SocketModeBroadcast;
+//! use futures_util::StreamExt;
+//!
+//! # async fn example(client: SlackClient, events: SocketModeBroadcast) -> slacko::Result<()> {
+//! let thread = client.conversations().thread("C123", "1700000000.000100");
+//!
+//! let messages = thread.load().await?;
+//! println!("{} messages from {:?}", messages.len(), thread.participants().await?);
+//!
+//! thread.reply("On it").await?;
+//!
+//! let mut replies = thread.follow(events.subscribe());
+//! while let Some(reply) = replies.next().await {
+//!     println!("New reply: {}", reply.text);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::chat::{PostMessageRequest, PostMessageResponse};
+use crate::api::conversations::ConversationRepliesRequest;
+use crate::api::socket_mode::{SocketModeEvent, SocketModePayload};
+use crate::client::SlackClient;
+use crate::error::Result;
+use crate::types::Message;
+use futures_util::stream::BoxStream;
+use futures_util::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Messages requested per `conversations.replies` page
+const PAGE_SIZE: u32 = 200;
+
+/// Message subtypes that edit or annotate existing messages rather than
+/// add a reply
+const IGNORED_SUBTYPES: &[&str] = &["message_changed", "message_deleted", "message_replied"];
+
+/// Handle on a message thread
+#[derive(Clone)]
+pub struct Thread {
+    client: SlackClient,
+    channel: String,
+    ts: String,
+}
+
+impl Thread {
+    pub(crate) fn new(client: SlackClient, channel: &str, ts: &str) -> Self {
+        Self {
+            client,
+            channel: channel.to_string(),
+            ts: ts.to_string(),
+        }
+    }
+
+    /// Channel the thread is in
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// Timestamp of the root message
+    pub fn ts(&self) -> &str {
+        &self.ts
+    }
+
+    /// Load the whole thread, root message first
+    ///
+    /// Follows `conversations.replies` cursors until every page is read.
+    pub async fn load(&self) -> Result<Vec<Message>> {
+        let conversations = self.client.conversations();
+        let mut messages = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let response = conversations
+                .replies_with_options(ConversationRepliesRequest {
+                    channel: self.channel.clone(),
+                    ts: self.ts.clone(),
+                    limit: Some(PAGE_SIZE),
+                    cursor: cursor.take(),
+                })
+                .await?;
+            messages.extend(response.messages);
+
+            match response.response_metadata.and_then(|m| m.next_cursor) {
+                Some(next) if response.has_more && !next.is_empty() => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(messages)
+    }
+
+    /// Load the replies, without the root message
+    pub async fn replies(&self) -> Result<Vec<Message>> {
+        let mut messages = self.load().await?;
+        messages.retain(|m| m.ts != self.ts);
+        Ok(messages)
+    }
+
+    /// Users and bots that posted in the thread, in order of first post
+    ///
+    /// Users are listed by user ID; bots without a user by bot ID.
+    pub async fn participants(&self) -> Result<Vec<String>> {
+        let mut participants: Vec<String> = Vec::new();
+        for message in self.load().await? {
+            if let Some(id) = message.user.or(message.bot_id) {
+                if !participants.contains(&id) {
+                    participants.push(id);
+                }
+            }
+        }
+        Ok(participants)
+    }
+
+    /// Post a reply in the thread
+    pub async fn reply(&self, text: &str) -> Result<PostMessageResponse> {
+        self.reply_with_options(PostMessageRequest::new(&self.channel).text(text))
+            .await
+    }
+
+    /// Post a reply that is also sent to the channel
+    pub async fn reply_broadcast(&self, text: &str) -> Result<PostMessageResponse> {
+        self.reply_with_options(
+            PostMessageRequest::new(&self.channel)
+                .text(text)
+                .reply_broadcast(true),
+        )
+        .await
+    }
+
+    /// Post a reply with full options
+    ///
+    /// The channel and `thread_ts` are set to this thread's.
+    pub async fn reply_with_options(
+        &self,
+        mut params: PostMessageRequest,
+    ) -> Result<PostMessageResponse> {
+        params.channel = self.channel.clone();
+        params.thread_ts = Some(self.ts.clone());
+        self.client.chat().post_message_with_options(params).await
+    }
+
+    /// Mark the thread read up to the message at `ts`
+    ///
+    /// Uses `subscriptions.thread.mark`, which needs a user token.
+    pub async fn mark_read(&self, ts: &str) -> Result<MarkThreadResponse> {
+        let params = MarkThreadRequest {
+            channel: self.channel.clone(),
+            thread_ts: self.ts.clone(),
+            ts: ts.to_string(),
+            read: true,
+        };
+
+        self.client.post("subscriptions.thread.mark", &params).await
+    }
+
+    /// The reply carried by `event`, if it is a new message in this thread
+    pub fn reply_in(&self, event: &SocketModeEvent) -> Option<Message> {
+        let SocketModePayload::EventsApi(payload) = &event.payload else {
+            return None;
+        };
+        let event = payload.event.as_ref()?;
+        let field = |name: &str| event.get(name).and_then(|v| v.as_str());
+
+        if field("type") != Some("message")
+            || field("channel") != Some(self.channel.as_str())
+            || field("thread_ts") != Some(self.ts.as_str())
+            || field("ts") == Some(self.ts.as_str())
+            || field("subtype").is_some_and(|s| IGNORED_SUBTYPES.contains(&s))
+        {
+            return None;
+        }
+        serde_json::from_value(event.clone()).ok()
+    }
+
+    /// New replies from a stream of Socket Mode events
+    ///
+    /// Pass a [`SocketModeBroadcast::subscribe`](crate::api::socket_mode::SocketModeBroadcast::subscribe)
+    /// stream to follow the thread alongside other consumers. Events are
+    /// not acknowledged here.
+    pub fn follow<S>(&self, events: S) -> BoxStream<'static, Message>
+    where
+        S: Stream<Item = SocketModeEvent> + Send + 'static,
+    {
+        let thread = self.clone();
+        events
+            .filter_map(move |event| std::future::ready(thread.reply_in(&event)))
+            .boxed()
+    }
+}
+
+impl fmt::Debug for Thread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Thread")
+            .field("channel", &self.channel)
+            .field("ts", &self.ts)
+            .finish()
+    }
+}
+
+// Request/Response types
+
+#[derive(Debug, Serialize)]
+pub struct MarkThreadRequest {
+    pub channel: String,
+    pub thread_ts: String,
+    pub ts: String,
+    pub read: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarkThreadResponse {}
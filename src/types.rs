@@ -1,6 +1,9 @@
 //! Common types used across the Slack SDK
 
+use crate::error::Result;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// Standard Slack API response wrapper
 #[derive(Debug, Deserialize, Serialize)]
@@ -130,6 +133,44 @@ pub struct Message {
     pub reactions: Option<Vec<Reaction>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub files: Option<Vec<File>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<MessageMetadata>,
+}
+
+impl Message {
+    /// Deserialize the metadata payload, or `None` if the message has none
+    ///
+    /// `conversations.history` only returns metadata posted by other apps
+    /// when `include_all_metadata` is set.
+    pub fn metadata_as<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        self.metadata
+            .as_ref()
+            .map(MessageMetadata::payload_as)
+            .transpose()
+    }
+}
+
+/// Metadata attached to a message: an event type and a JSON payload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageMetadata {
+    pub event_type: String,
+    #[serde(default)]
+    pub event_payload: Value,
+}
+
+impl MessageMetadata {
+    /// Metadata with a serialized payload
+    pub fn new(event_type: impl Into<String>, payload: &impl Serialize) -> Result<Self> {
+        Ok(Self {
+            event_type: event_type.into(),
+            event_payload: serde_json::to_value(payload)?,
+        })
+    }
+
+    /// Deserialize the payload into a typed struct
+    pub fn payload_as<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_value(self.event_payload.clone())?)
+    }
 }
 
 /// Message attachment
@@ -0,0 +1,273 @@
+//! Link unfurling
+//!
+//! Slack sends a `link_shared` event when a message (or a message being
+//! composed) contains a link on one of the app's registered domains. The
+//! app answers with `chat.unfurl`, mapping each URL to a preview.
+//!
+//! [`App::unfurl`](crate::app::App::unfurl) routes links by
+//! [`UrlPattern`] to handlers that return an [`Unfurl`] per link, and
+//! sends the results of every route in one `chat.unfurl` call. Handlers
+//! that need the user to connect an account first return
+//! [`Unfurl::AuthRequired`], which is sent as a separate call.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use slacko::{SlackClient, AuthConfig};
+//! use slacko::app::App;
+//! use slacko::blocks::MessageBuilder;
+//! use slacko::unfurl::Unfurl;
+//!
+//! # let client = SlackClient::new(AuthConfig::bot("xoxb-token")).unwrap();
+//! let app = App::new(client).unfurl("https://jira.example.com/browse/*", |ctx, link| async move {
+//!     // ctx.matches()[1] is the text matched by `*`
+//!     let issue = ctx.matches()[1].clone();
+//!     Ok(Unfurl::blocks(
+//!         MessageBuilder::new()
+//!             .section(format!("*{}* from <{}|Jira>", issue, link.url))
+//!             .build_blocks(),
+//!     ))
+//! });
+//! ```
+
+use crate::api::chat::UnfurlRequest;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+// Event types
+
+/// `link_shared` event
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinkSharedEvent {
+    /// Channel of the message, or `"COMPOSER"` while it is being written
+    pub channel: String,
+    pub user: String,
+    /// Timestamp of the message, or a temporary ID in the composer
+    pub message_ts: String,
+    pub thread_ts: Option<String>,
+    pub links: Vec<SharedLink>,
+    /// ID identifying this set of links in `chat.unfurl`
+    pub unfurl_id: Option<String>,
+    /// Where the links were shared: `"conversations_history"` or `"composer"`
+    pub source: Option<String>,
+    #[serde(default)]
+    pub is_bot_user_member: bool,
+    pub event_ts: Option<String>,
+}
+
+impl LinkSharedEvent {
+    /// Whether the links are in a message that has not been sent yet
+    pub fn in_composer(&self) -> bool {
+        self.source.as_deref() == Some("composer") || self.channel == "COMPOSER"
+    }
+
+    /// A `chat.unfurl` request for this event
+    ///
+    /// Uses `unfurl_id` and `source` when Slack sent them, which also works
+    /// in the composer, and `channel` and `ts` otherwise.
+    pub fn unfurl_request(&self, unfurls: Value) -> UnfurlRequest {
+        match (&self.unfurl_id, &self.source) {
+            (Some(unfurl_id), Some(source)) => UnfurlRequest {
+                unfurls,
+                unfurl_id: Some(unfurl_id.clone()),
+                source: Some(source.clone()),
+                ..Default::default()
+            },
+            _ => UnfurlRequest {
+                channel: self.channel.clone(),
+                ts: self.message_ts.clone(),
+                unfurls,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// A link in a `link_shared` event
+#[derive(Debug, Clone, Deserialize)]
+pub struct SharedLink {
+    pub url: String,
+    pub domain: String,
+}
+
+/// How to unfurl one link
+#[derive(Debug, Clone)]
+pub enum Unfurl {
+    /// Block Kit blocks shown under the message
+    Blocks(Vec<Value>),
+    /// A legacy attachment (e.g. `{"title": ..., "text": ...}`)
+    Attachment(Value),
+    /// Ask the user to connect their account before unfurling
+    ///
+    /// Slack shows `blocks`, or `message` (or a default) with a link to
+    /// `url`.
+    AuthRequired {
+        message: Option<String>,
+        url: Option<String>,
+        blocks: Option<Vec<Value>>,
+    },
+    /// Leave the link as it is
+    Skip,
+}
+
+impl Unfurl {
+    pub fn blocks(blocks: Vec<Value>) -> Self {
+        Self::Blocks(blocks)
+    }
+
+    /// Ask the user to authenticate at `url`
+    pub fn auth_required(url: impl Into<String>) -> Self {
+        Self::AuthRequired {
+            message: None,
+            url: Some(url.into()),
+            blocks: None,
+        }
+    }
+
+    /// The value for this link in `chat.unfurl`'s `unfurls` map
+    fn to_value(&self) -> Option<Value> {
+        match self {
+            Self::Blocks(blocks) => Some(json!({ "blocks": blocks })),
+            Self::Attachment(attachment) => Some(attachment.clone()),
+            Self::AuthRequired { .. } | Self::Skip => None,
+        }
+    }
+}
+
+/// Build the `chat.unfurl` requests for handler results
+///
+/// The unfurls go in one request and an auth prompt in another, since
+/// Slack ignores the unfurls of a request that asks the user to
+/// authenticate. Empty if there is nothing to send.
+pub(crate) fn unfurl_requests(
+    event: &LinkSharedEvent,
+    results: &[(String, Unfurl)],
+) -> Vec<UnfurlRequest> {
+    let mut requests = Vec::new();
+    let unfurls: Map<String, Value> = results
+        .iter()
+        .filter_map(|(url, unfurl)| Some((url.clone(), unfurl.to_value()?)))
+        .collect();
+    if !unfurls.is_empty() {
+        requests.push(event.unfurl_request(Value::Object(unfurls)));
+    }
+
+    let auth = results.iter().find_map(|(_, unfurl)| match unfurl {
+        Unfurl::AuthRequired {
+            message,
+            url,
+            blocks,
+        } => Some((message, url, blocks)),
+        _ => None,
+    });
+    if let Some((message, url, blocks)) = auth {
+        requests.push(UnfurlRequest {
+            user_auth_required: Some(true),
+            user_auth_message: message.clone(),
+            user_auth_url: url.clone(),
+            user_auth_blocks: blocks.clone(),
+            ..event.unfurl_request(json!({}))
+        });
+    }
+    requests
+}
+
+/// A link and its handler's result, `None` until the handler succeeds
+type ClaimedLink = (String, Option<Unfurl>);
+
+/// Links claimed by unfurl routes for one event, in registration order
+///
+/// Shared by every route so that the first pattern matching a link
+/// handles it and the results go out together.
+#[derive(Clone, Default)]
+pub(crate) struct UnfurlResults {
+    links: Arc<Mutex<Vec<ClaimedLink>>>,
+}
+
+impl UnfurlResults {
+    /// Claim `url` for a route; `false` if an earlier route claimed it
+    pub(crate) fn claim(&self, url: &str) -> bool {
+        let mut links = self.links.lock().unwrap();
+        if links.iter().any(|(claimed, _)| claimed == url) {
+            return false;
+        }
+        links.push((url.to_string(), None));
+        true
+    }
+
+    /// Record the result for a claimed link
+    pub(crate) fn set(&self, url: &str, unfurl: Unfurl) {
+        let mut links = self.links.lock().unwrap();
+        if let Some((_, result)) = links.iter_mut().find(|(claimed, _)| claimed == url) {
+            *result = Some(unfurl);
+        }
+    }
+
+    /// Take the results, leaving out links whose handler failed
+    pub(crate) fn take(&self) -> Vec<(String, Unfurl)> {
+        std::mem::take(&mut *self.links.lock().unwrap())
+            .into_iter()
+            .filter_map(|(url, unfurl)| Some((url, unfurl?)))
+            .collect()
+    }
+}
+
+/// URL pattern where `*` matches any run of characters
+///
+/// The whole URL must match. The text matched by each `*` is available
+/// from [`Context::matches`](crate::app::Context::matches), after the
+/// whole URL at index 0.
+#[derive(Clone)]
+pub struct UrlPattern {
+    pattern: String,
+    regex: Regex,
+}
+
+impl UrlPattern {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        let body = pattern
+            .split('*')
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join("(.*)");
+        let regex = Regex::new(&format!("^{}$", body)).expect("escaped pattern is a valid regex");
+        Self { pattern, regex }
+    }
+
+    /// Whether `url` matches
+    pub fn is_match(&self, url: &str) -> bool {
+        self.regex.is_match(url)
+    }
+
+    /// The whole URL and the text matched by each `*`, if `url` matches
+    pub fn captures(&self, url: &str) -> Option<Vec<String>> {
+        let captures = self.regex.captures(url)?;
+        Some(
+            captures
+                .iter()
+                .map(|m| m.map(|m| m.as_str().to_string()).unwrap_or_default())
+                .collect(),
+        )
+    }
+
+    /// The pattern as written
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl fmt::Debug for UrlPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("UrlPattern").field(&self.pattern).finish()
+    }
+}
+
+impl fmt::Display for UrlPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
@@ -2,8 +2,13 @@
 
 #![allow(dead_code)]
 
+pub mod socket;
+
+use serde_json::{json, Value};
+use slacko::api::socket_mode::{Ack, SocketModeApi, SocketModeEnvelope, SocketModeEvent};
 use slacko::{AuthConfig, SlackClient};
 use std::sync::Once;
+use tokio::sync::oneshot;
 
 static INIT: Once = Once::new();
 
@@ -46,6 +51,26 @@ macro_rules! skip_if_no_client {
     };
 }
 
+/// An `events_api` envelope wrapping the inner `event`
+pub fn events_api(event: Value) -> Value {
+    json!({
+        "envelope_id": "env-1",
+        "type": "events_api",
+        "accepts_response_payload": false,
+        "payload": { "type": "event_callback", "event_id": "Ev1", "event": event }
+    })
+}
+
+/// Parse an envelope into an event, with the receiver its ack resolves
+pub fn socket_event(envelope: Value) -> (SocketModeEvent, oneshot::Receiver<Option<Value>>) {
+    let envelope: SocketModeEnvelope = serde_json::from_value(envelope).unwrap();
+    let (ack, rx) = Ack::new(
+        envelope.envelope_id.clone(),
+        envelope.accepts_response_payload,
+    );
+    (SocketModeApi::parse_envelope(envelope, ack), rx)
+}
+
 /// Get test channel ID from environment or use default
 pub fn test_channel() -> String {
     std::env::var("SLACK_TEST_CHANNEL").unwrap_or_else(|_| "general".to_string())
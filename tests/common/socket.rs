@@ -0,0 +1,86 @@
+//! Local Socket Mode server for tests
+//!
+//! A [`MockApi`] answers `apps.connections.open` with the URL of a local
+//! WebSocket listener, so runners connect without Slack credentials.
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use slacko::replay::MockApi;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{accept_async, tungstenite::Message, WebSocketStream};
+
+pub type Server = WebSocketStream<TcpStream>;
+
+/// Mock Web API whose `apps.connections.open` points at the returned listener
+pub async fn setup() -> (MockApi, TcpListener) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let api = MockApi::start().await.unwrap();
+    api.respond(
+        "apps.connections.open",
+        json!({ "ok": true, "url": format!("ws://{}", listener.local_addr().unwrap()) }),
+    );
+    (api, listener)
+}
+
+/// Accept the next client connection
+pub async fn accept(listener: &TcpListener) -> Server {
+    let (stream, _) = tokio::time::timeout(Duration::from_secs(5), listener.accept())
+        .await
+        .expect("client did not connect")
+        .unwrap();
+    accept_async(stream).await.unwrap()
+}
+
+pub async fn send(server: &mut Server, frame: Value) {
+    server.send(Message::Text(frame.to_string())).await.unwrap();
+}
+
+/// Next text frame from the client, or `None` once it closes the socket
+///
+/// Pings and pongs are skipped.
+pub async fn receive(server: &mut Server) -> Option<Value> {
+    loop {
+        let frame = tokio::time::timeout(Duration::from_secs(5), server.next())
+            .await
+            .expect("no frame from client");
+        match frame {
+            Some(Ok(Message::Text(text))) => return Some(serde_json::from_str(&text).unwrap()),
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return None,
+            Some(Ok(_)) => continue,
+        }
+    }
+}
+
+/// Ping the client and wait for its pong
+pub async fn ping(server: &mut Server) {
+    server.send(Message::Ping(b"ping".to_vec())).await.unwrap();
+    loop {
+        let frame = tokio::time::timeout(Duration::from_secs(2), server.next())
+            .await
+            .expect("no pong from client");
+        match frame {
+            Some(Ok(Message::Pong(_))) => return,
+            Some(Ok(Message::Ping(_))) => continue,
+            other => panic!("expected a pong, got {:?}", other),
+        }
+    }
+}
+
+pub fn hello() -> Value {
+    json!({ "type": "hello", "num_connections": 1 })
+}
+
+pub fn disconnect(reason: &str) -> Value {
+    json!({ "type": "disconnect", "reason": reason })
+}
+
+/// An `events_api` envelope carrying a message in `C1`
+pub fn message(envelope_id: &str, text: &str) -> Value {
+    let mut envelope = super::events_api(
+        json!({ "type": "message", "channel": "C1", "user": "U1", "text": text }),
+    );
+    envelope["envelope_id"] = json!(envelope_id);
+    envelope["payload"]["event_id"] = json!(format!("Ev{}", envelope_id));
+    envelope
+}
@@ -0,0 +1,596 @@
+//! Tests for event routing with `App`
+//!
+//! These tests dispatch parsed envelopes directly and do not need Slack
+//! credentials.
+
+mod common;
+
+use common::{events_api, socket_event as event};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use slacko::app::middleware::{from_fn, DedupeRetries, IgnoreSelf, MemoryEventStore, ReportErrors};
+use slacko::app::{App, Regex};
+use slacko::blocks::{MultiSelectElement, OptionGroupObject, OptionObject, SelectElement};
+use slacko::replay::MockApi;
+use slacko::responder::OptionsResponse;
+use slacko::{AuthConfig, SlackClient, SlackError};
+use std::sync::{Arc, Mutex};
+
+fn app() -> App {
+    App::new(SlackClient::new(AuthConfig::bot("xoxb-test")).unwrap())
+}
+
+fn interactive(payload: Value) -> Value {
+    json!({
+        "envelope_id": "env-2",
+        "type": "interactive",
+        "accepts_response_payload": true,
+        "payload": payload
+    })
+}
+
+/// Records which listeners ran
+fn recorder() -> Arc<Mutex<Vec<String>>> {
+    Arc::new(Mutex::new(Vec::new()))
+}
+
+#[tokio::test]
+async fn test_routes_event_by_type() {
+    let calls = recorder();
+    let (c1, c2) = (calls.clone(), calls.clone());
+    let app = app()
+        .event("app_mention", move |ctx| {
+            let calls = c1.clone();
+            async move {
+                assert_eq!(ctx.channel_id(), Some("C1"));
+                assert_eq!(ctx.user_id(), Some("U1"));
+                calls.lock().unwrap().push("mention".into());
+                Ok(())
+            }
+        })
+        .event("reaction_added", move |_| {
+            let calls = c2.clone();
+            async move {
+                calls.lock().unwrap().push("reaction".into());
+                Ok(())
+            }
+        });
+
+    let (ev, rx) = event(events_api(
+        json!({ "type": "app_mention", "channel": "C1", "user": "U1", "text": "<@B1> hi" }),
+    ));
+    app.dispatch(ev).await;
+
+    assert_eq!(*calls.lock().unwrap(), vec!["mention".to_string()]);
+    assert_eq!(rx.await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_message_pattern_captures() {
+    let captured = recorder();
+    let c = captured.clone();
+    let app = app().message(Regex::new(r"^deploy (\w+)$").unwrap(), move |ctx| {
+        let captured = c.clone();
+        async move {
+            captured.lock().unwrap().extend(ctx.matches().to_vec());
+            Ok(())
+        }
+    });
+
+    let (ev, _) = event(events_api(
+        json!({ "type": "message", "channel": "C1", "user": "U1", "text": "deploy api" }),
+    ));
+    app.dispatch(ev).await;
+    let (ev, _) = event(events_api(
+        json!({ "type": "message", "channel": "C1", "user": "U1", "text": "hello" }),
+    ));
+    app.dispatch(ev).await;
+
+    assert_eq!(
+        *captured.lock().unwrap(),
+        vec!["deploy api".to_string(), "api".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_command_ack_with_payload() {
+    let app = app().command("/deploy", |ctx| async move {
+        assert_eq!(ctx.trigger_id(), Some("T.1"));
+        assert_eq!(
+            ctx.response_url(),
+            Some("https://hooks.slack.com/commands/1")
+        );
+        ctx.ack_with(json!({ "text": "Deploying" }));
+        Ok(())
+    });
+
+    let (ev, rx) = event(json!({
+        "envelope_id": "env-3",
+        "type": "slash_commands",
+        "accepts_response_payload": true,
+        "payload": {
+            "command": "/deploy",
+            "text": "api",
+            "response_url": "https://hooks.slack.com/commands/1",
+            "trigger_id": "T.1",
+            "user_id": "U1",
+            "channel_id": "C1"
+        }
+    }));
+    app.dispatch(ev).await;
+
+    assert_eq!(rx.await.unwrap(), Some(json!({ "text": "Deploying" })));
+}
+
+#[tokio::test]
+async fn test_routes_interactions_by_id() {
+    let calls = recorder();
+    let (c1, c2, c3, c4) = (calls.clone(), calls.clone(), calls.clone(), calls.clone());
+    let app = app()
+        .action("approve_btn", move |_| {
+            let calls = c1.clone();
+            async move {
+                calls.lock().unwrap().push("action".into());
+                Ok(())
+            }
+        })
+        .view_submission("deploy_modal", move |_| {
+            let calls = c2.clone();
+            async move {
+                calls.lock().unwrap().push("view".into());
+                Ok(())
+            }
+        })
+        .shortcut("open_deploy", move |_| {
+            let calls = c3.clone();
+            async move {
+                calls.lock().unwrap().push("shortcut".into());
+                Ok(())
+            }
+        })
+        .options("service_select", move |ctx| {
+            let calls = c4.clone();
+            async move {
+                calls.lock().unwrap().push("options".into());
+                ctx.ack_with(json!({ "options": [] }));
+                Ok(())
+            }
+        });
+
+    let payloads = [
+        json!({
+            "type": "block_actions",
+            "user": { "id": "U1" },
+            "actions": [{ "action_id": "approve_btn", "type": "button", "value": "yes" }]
+        }),
+        json!({
+            "type": "block_actions",
+            "user": { "id": "U1" },
+            "actions": [{ "action_id": "reject_btn", "type": "button" }]
+        }),
+        json!({
+            "type": "view_submission",
+            "user": { "id": "U1" },
+            "view": { "callback_id": "deploy_modal", "state": { "values": {} } }
+        }),
+        json!({ "type": "shortcut", "callback_id": "open_deploy", "user": { "id": "U1" } }),
+        json!({ "type": "block_suggestion", "action_id": "service_select", "value": "ap" }),
+    ];
+    for payload in payloads {
+        let (ev, _) = event(interactive(payload));
+        app.dispatch(ev).await;
+    }
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec!["action", "view", "shortcut", "options"]
+    );
+}
+
+#[tokio::test]
+async fn test_unmatched_and_failed_listeners_are_acked() {
+    let app = app().action("approve_btn", |_| async move {
+        Err(SlackError::Other("boom".into()))
+    });
+
+    let (ev, rx) = event(interactive(json!({
+        "type": "block_actions",
+        "actions": [{ "action_id": "approve_btn" }]
+    })));
+    app.dispatch(ev).await;
+    assert_eq!(rx.await.unwrap(), None);
+
+    let (ev, rx) = event(interactive(json!({ "type": "view_closed", "view": {} })));
+    app.dispatch(ev).await;
+    assert_eq!(rx.await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_say_requires_channel() {
+    let app = app().shortcut("global", |ctx| async move {
+        assert!(ctx.channel_id().is_none());
+        assert!(ctx.say("hi").await.is_err());
+        assert!(ctx.respond("hi").await.is_err());
+        Ok(())
+    });
+
+    let (ev, _) = event(interactive(
+        json!({ "type": "shortcut", "callback_id": "global" }),
+    ));
+    app.dispatch(ev).await;
+}
+
+#[tokio::test]
+async fn test_middleware_order_and_short_circuit() {
+    let calls = recorder();
+    let (c1, c2, c3, c4) = (calls.clone(), calls.clone(), calls.clone(), calls.clone());
+    let app = app()
+        .middleware(from_fn(move |ctx, next| {
+            let calls = c1.clone();
+            async move {
+                calls.lock().unwrap().push("global".into());
+                next.run(ctx).await
+            }
+        }))
+        .event("app_mention", move |_| {
+            let calls = c2.clone();
+            async move {
+                calls.lock().unwrap().push("mention".into());
+                Ok(())
+            }
+        })
+        .with_middleware(from_fn(move |ctx, next| {
+            let calls = c3.clone();
+            async move {
+                calls.lock().unwrap().push("listener".into());
+                // Only users in the allow list reach the listener
+                match ctx.user_id() {
+                    Some("U_ALLOWED") => next.run(ctx).await,
+                    _ => Ok(()),
+                }
+            }
+        }))
+        .event("app_mention", move |_| {
+            let calls = c4.clone();
+            async move {
+                calls.lock().unwrap().push("unfiltered".into());
+                Ok(())
+            }
+        });
+
+    for user in ["U_ALLOWED", "U_OTHER"] {
+        let (ev, _) = event(events_api(
+            json!({ "type": "app_mention", "channel": "C1", "user": user }),
+        ));
+        app.dispatch(ev).await;
+    }
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            "global",
+            "listener",
+            "mention",
+            "unfiltered",
+            "global",
+            "listener",
+            "unfiltered"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_ignore_self() {
+    let calls = recorder();
+    let c = calls.clone();
+    let app = app()
+        .middleware(IgnoreSelf::with_ids("U_BOT", Some("B_BOT".into())))
+        .event("message", move |ctx| {
+            let calls = c.clone();
+            async move {
+                let text = ctx.body().unwrap()["text"].as_str().unwrap_or_default();
+                calls.lock().unwrap().push(text.to_string());
+                Ok(())
+            }
+        });
+
+    let messages = [
+        json!({ "type": "message", "channel": "C1", "user": "U1", "text": "from user" }),
+        json!({ "type": "message", "channel": "C1", "user": "U_BOT", "text": "own user" }),
+        json!({ "type": "message", "channel": "C1", "bot_id": "B_BOT", "text": "own bot" }),
+        json!({
+            "type": "message",
+            "subtype": "message_changed",
+            "channel": "C1",
+            "text": "own edit",
+            "message": { "user": "U_BOT", "text": "edited" }
+        }),
+    ];
+    for message in messages {
+        let (ev, _) = event(events_api(message));
+        app.dispatch(ev).await;
+    }
+
+    assert_eq!(*calls.lock().unwrap(), vec!["from user".to_string()]);
+}
+
+#[tokio::test]
+async fn test_dedupe_retries() {
+    let calls = recorder();
+    let c = calls.clone();
+    let app = app()
+        .middleware(DedupeRetries::with_store(MemoryEventStore::new(10)))
+        .event("app_mention", move |_| {
+            let calls = c.clone();
+            async move {
+                calls.lock().unwrap().push("mention".into());
+                Ok(())
+            }
+        });
+
+    let original = events_api(json!({ "type": "app_mention", "channel": "C1" }));
+    let mut retry = original.clone();
+    retry["retry_attempt"] = json!(1);
+    retry["retry_reason"] = json!("timeout");
+    let mut other = original.clone();
+    other["payload"]["event_id"] = json!("Ev2");
+
+    for envelope in [original, retry, other] {
+        let (ev, _) = event(envelope);
+        app.dispatch(ev).await;
+    }
+
+    assert_eq!(calls.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_report_errors_passes_error_through() {
+    let seen = recorder();
+    let s = seen.clone();
+    let app = app()
+        .middleware(from_fn(move |ctx, next| {
+            let seen = s.clone();
+            async move {
+                let result = next.run(ctx).await;
+                if let Err(e) = &result {
+                    seen.lock().unwrap().push(e.to_string());
+                }
+                result
+            }
+        }))
+        // No channel or response URL, so reporting fails without a request
+        .middleware(ReportErrors::new().formatter(|e| format!("Oops: {}", e)))
+        .shortcut("global", |_| async move {
+            Err(SlackError::Other("boom".into()))
+        });
+
+    let (ev, rx) = event(interactive(
+        json!({ "type": "shortcut", "callback_id": "global" }),
+    ));
+    app.dispatch(ev).await;
+
+    assert_eq!(*seen.lock().unwrap(), vec!["boom".to_string()]);
+    assert_eq!(rx.await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_options_request_acks_with_options() {
+    let app = app().options_request("service_select", |_, request| async move {
+        assert_eq!(request.user.id, "U1");
+        let options = ["api", "app", "web"]
+            .into_iter()
+            .filter(|s| s.starts_with(&request.value))
+            .map(|s| OptionObject::new(s.to_uppercase(), s))
+            .collect();
+        Ok(OptionsResponse::options(options))
+    });
+
+    let (ev, rx) = event(interactive(json!({
+        "type": "block_suggestion",
+        "action_id": "service_select",
+        "block_id": "b1",
+        "value": "ap",
+        "user": { "id": "U1" }
+    })));
+    app.dispatch(ev).await;
+
+    assert_eq!(
+        rx.await.unwrap(),
+        Some(json!({
+            "options": [
+                { "text": { "type": "plain_text", "text": "API" }, "value": "api" },
+                { "text": { "type": "plain_text", "text": "APP" }, "value": "app" }
+            ]
+        }))
+    );
+}
+
+#[tokio::test]
+async fn test_options_request_groups_and_external_select() {
+    let select = SelectElement::external("service_select", "Pick a service")
+        .min_query_length(2)
+        .build();
+    assert_eq!(select["type"], "external_select");
+    assert_eq!(select["min_query_length"], 2);
+    let multi = MultiSelectElement::external("services", "Pick services")
+        .min_query_length(0)
+        .initial_options(vec![OptionObject::new("API", "api")])
+        .build();
+    assert_eq!(multi["type"], "multi_external_select");
+    assert_eq!(multi["initial_options"][0]["value"], "api");
+
+    let app = app().options_request("services", |_, _| async {
+        Ok(OptionsResponse::option_groups(vec![
+            OptionGroupObject::new("Backend").option(OptionObject::new("API", "api")),
+        ]))
+    });
+    let (ev, rx) = event(interactive(json!({
+        "type": "block_suggestion",
+        "action_id": "services",
+        "value": "",
+        "user": { "id": "U1" }
+    })));
+    app.dispatch(ev).await;
+
+    let ack = rx.await.unwrap().unwrap();
+    assert!(ack.get("options").is_none());
+    assert_eq!(ack["option_groups"][0]["label"]["text"], "Backend");
+    assert_eq!(ack["option_groups"][0]["options"][0]["value"], "api");
+}
+
+fn function_executed(callback_id: &str, inputs: Value) -> Value {
+    events_api(json!({
+        "type": "function_executed",
+        "function": {
+            "id": "Fn1",
+            "callback_id": callback_id,
+            "title": "Greet",
+            "type": "app",
+            "input_parameters": [
+                { "name": "user_id", "type": "slack#/types/user_id", "is_required": true }
+            ],
+            "output_parameters": [{ "name": "greeting", "type": "string" }]
+        },
+        "inputs": inputs,
+        "function_execution_id": "Fx1",
+        "workflow_execution_id": "Wx1",
+        "event_ts": "1.1",
+        "bot_access_token": "xwfp-execution"
+    }))
+}
+
+#[derive(Deserialize)]
+struct GreetInputs {
+    user_id: String,
+}
+
+#[derive(Serialize)]
+struct GreetOutputs {
+    greeting: String,
+}
+
+#[tokio::test]
+async fn test_function_completes_with_typed_outputs() {
+    let api = MockApi::start().await.unwrap();
+    let app = App::new(api.client())
+        .function("greet", |ctx, inputs: GreetInputs| async move {
+            let execution = ctx.function_executed().unwrap();
+            assert_eq!(execution.function.input_parameters[0].name, "user_id");
+            assert!(execution.function.input_parameters[0].is_required);
+            assert_eq!(execution.input("user_id"), Some(&json!("U1")));
+            Ok(GreetOutputs {
+                greeting: format!("Hello <@{}>", inputs.user_id),
+            })
+        })
+        .function("other", |_, _: Value| async { Ok(json!({})) });
+
+    let (ev, rx) = event(function_executed("greet", json!({ "user_id": "U1" })));
+    app.dispatch(ev).await;
+    assert_eq!(rx.await.unwrap(), None);
+
+    let calls = api.calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].method, "functions.completeSuccess");
+    assert_eq!(calls[0].params["function_execution_id"], "Fx1");
+    assert_eq!(calls[0].params["outputs"]["greeting"], "Hello <@U1>");
+    // Calls for the execution use its workflow token
+    assert_eq!(calls[0].token.as_deref(), Some("xwfp-execution"));
+}
+
+#[tokio::test]
+async fn test_function_errors_complete_with_error() {
+    let api = MockApi::start().await.unwrap();
+    let app = App::new(api.client()).function("greet", |_, inputs: GreetInputs| async move {
+        Err::<GreetOutputs, _>(SlackError::Other(format!("{} is away", inputs.user_id)))
+    });
+
+    let (ev, _) = event(function_executed("greet", json!({ "user_id": "U1" })));
+    app.dispatch(ev).await;
+    // Inputs that do not match the handler's type
+    let (ev, _) = event(function_executed("greet", json!({ "channel_id": "C1" })));
+    app.dispatch(ev).await;
+
+    let calls = api.calls_to("functions.completeError");
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].params["function_execution_id"], "Fx1");
+    assert!(calls[0].params["error"]
+        .as_str()
+        .unwrap()
+        .contains("U1 is away"));
+    assert!(calls[1].params["error"]
+        .as_str()
+        .unwrap()
+        .contains("invalid inputs for function greet"));
+    assert!(api.calls_to("functions.completeSuccess").is_empty());
+}
+
+#[tokio::test]
+async fn test_function_malformed_event_completes_with_error() {
+    let api = MockApi::start().await.unwrap();
+    let errors = recorder();
+    let e = errors.clone();
+    let app = App::new(api.client())
+        .middleware(from_fn(move |ctx, next| {
+            let errors = e.clone();
+            async move {
+                let result = next.run(ctx).await;
+                if let Err(e) = &result {
+                    errors.lock().unwrap().push(e.to_string());
+                }
+                result
+            }
+        }))
+        .function("greet", |_, _: GreetInputs| async move {
+            Ok(GreetOutputs {
+                greeting: String::new(),
+            })
+        });
+
+    // Inputs must be an object, so the event does not parse
+    let (ev, _) = event(function_executed("greet", json!(["U1"])));
+    app.dispatch(ev).await;
+
+    let calls = api.calls_to("functions.completeError");
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].params["function_execution_id"], "Fx1");
+    assert!(calls[0].params["error"]
+        .as_str()
+        .unwrap()
+        .contains("malformed function_executed event"));
+    assert_eq!(errors.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_function_error_survives_failed_completion() {
+    let api = MockApi::start().await.unwrap();
+    api.respond(
+        "functions.completeError",
+        json!({ "ok": false, "error": "invalid_auth" }),
+    );
+    let errors = recorder();
+    let e = errors.clone();
+    let app = App::new(api.client())
+        .middleware(from_fn(move |ctx, next| {
+            let errors = e.clone();
+            async move {
+                let result = next.run(ctx).await;
+                if let Err(e) = &result {
+                    errors.lock().unwrap().push(e.to_string());
+                }
+                result
+            }
+        }))
+        .function("greet", |_, inputs: GreetInputs| async move {
+            Err::<GreetOutputs, _>(SlackError::Other(format!("{} is away", inputs.user_id)))
+        });
+
+    let (ev, _) = event(function_executed("greet", json!({ "user_id": "U1" })));
+    app.dispatch(ev).await;
+
+    // The handler's error is reported, not the completion failure
+    assert_eq!(api.calls_to("functions.completeError").len(), 1);
+    let errors = errors.lock().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("U1 is away"));
+    assert!(!errors[0].contains("invalid_auth"));
+}
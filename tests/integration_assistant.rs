@@ -0,0 +1,124 @@
+//! Tests for the Assistant API and assistant thread events
+//!
+//! These tests run against a local mock Web API and do not need Slack
+//! credentials.
+
+mod common;
+
+use common::events_api;
+use serde_json::json;
+use slacko::api::assistant::{AssistantThreadStartedEvent, SuggestedPrompt};
+use slacko::app::App;
+use slacko::replay::{MockApi, Replay};
+use slacko::SlackError;
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn test_assistant_thread_methods() {
+    let api = MockApi::start().await.unwrap();
+    let assistant = api.client().assistant();
+
+    assistant
+        .set_suggested_prompts(
+            "D1",
+            "1.1",
+            vec![SuggestedPrompt::new("Summarize", "Summarize this channel")],
+            Some("Try asking"),
+        )
+        .await
+        .unwrap();
+    assistant
+        .set_title("D1", "1.1", "Channel summary")
+        .await
+        .unwrap();
+
+    let calls = api.calls();
+    assert_eq!(calls[0].method, "assistant.threads.setSuggestedPrompts");
+    assert_eq!(
+        calls[0].params,
+        json!({
+            "channel_id": "D1",
+            "thread_ts": "1.1",
+            "prompts": [{ "title": "Summarize", "message": "Summarize this channel" }],
+            "title": "Try asking"
+        })
+    );
+    assert_eq!(calls[1].method, "assistant.threads.setTitle");
+    assert_eq!(calls[1].params["title"], "Channel summary");
+}
+
+#[tokio::test]
+async fn test_with_status_sets_and_clears() {
+    let api = MockApi::start().await.unwrap();
+    let client = api.client();
+    let assistant = client.assistant();
+
+    let result = assistant
+        .with_status("D1", "1.1", "is thinking...", async {
+            let chat = client.chat();
+            chat.append_stream("D1", "S1", "Hello").await?;
+            Ok("done")
+        })
+        .await
+        .unwrap();
+    assert_eq!(result, "done");
+
+    // Cleared even when generation fails
+    let err = assistant
+        .with_status("D1", "1.1", "is thinking...", async {
+            Err::<(), _>(SlackError::Other("model unavailable".into()))
+        })
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("model unavailable"));
+
+    let statuses: Vec<_> = api
+        .calls_to("assistant.threads.setStatus")
+        .into_iter()
+        .map(|c| c.params["status"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(statuses, ["is thinking...", "", "is thinking...", ""]);
+
+    let methods: Vec<_> = api.calls().into_iter().map(|c| c.method).collect();
+    assert_eq!(methods[1], "chat.appendStream");
+}
+
+#[tokio::test]
+async fn test_assistant_thread_events() {
+    let started = json!({
+        "type": "assistant_thread_started",
+        "assistant_thread": {
+            "user_id": "U1",
+            "context": { "channel_id": "C9", "team_id": "T1", "enterprise_id": null },
+            "channel_id": "D1",
+            "thread_ts": "1.1"
+        },
+        "event_ts": "1.2"
+    });
+    let event: AssistantThreadStartedEvent = serde_json::from_value(started.clone()).unwrap();
+    assert_eq!(
+        event.assistant_thread.context.channel_id.as_deref(),
+        Some("C9")
+    );
+    assert_eq!(event.assistant_thread.thread_ts, "1.1");
+
+    let api = MockApi::start().await.unwrap();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    let app = App::new(api.client()).event("assistant_thread_context_changed", move |ctx| {
+        let log = log.clone();
+        async move {
+            let thread = ctx.assistant_thread().unwrap();
+            log.lock().unwrap().push(thread.context.channel_id.unwrap());
+            Ok(())
+        }
+    });
+
+    let mut changed = started;
+    changed["type"] = json!("assistant_thread_context_changed");
+    changed["assistant_thread"]["context"]["channel_id"] = json!("C10");
+    app.replay(Replay::from_envelopes([events_api(changed)]))
+        .await
+        .unwrap();
+    assert_eq!(*seen.lock().unwrap(), ["C10"]);
+}
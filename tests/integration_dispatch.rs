@@ -0,0 +1,179 @@
+//! Tests for bounded dispatch and handler isolation in Socket Mode
+//!
+//! These tests replay envelopes through the dispatcher and do not need
+//! Slack credentials.
+
+use serde_json::{json, Value};
+use slacko::api::socket_mode::{SocketModeApi, SocketModeEvent, SocketModePayload};
+use slacko::replay::Replay;
+use slacko::{AuthConfig, HandlerOutcome, OverflowPolicy, SlackClient};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn socket_mode() -> SocketModeApi {
+    SlackClient::new(AuthConfig::app("xapp-test"))
+        .unwrap()
+        .socket_mode()
+}
+
+fn message(envelope_id: &str, channel: &str, text: &str) -> Value {
+    json!({
+        "envelope_id": envelope_id,
+        "type": "events_api",
+        "accepts_response_payload": false,
+        "payload": {
+            "type": "event_callback",
+            "event_id": format!("Ev{}", envelope_id),
+            "event": { "type": "message", "channel": channel, "user": "U1", "text": text }
+        }
+    })
+}
+
+fn text(event: &SocketModeEvent) -> String {
+    match &event.payload {
+        SocketModePayload::EventsApi(p) => p.event.as_ref().unwrap()["text"]
+            .as_str()
+            .unwrap()
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+fn messages(count: usize) -> Replay {
+    Replay::from_envelopes((0..count).map(|i| message(&format!("env-{}", i), "C1", "hi")))
+}
+
+#[tokio::test]
+async fn test_panicking_handler_is_isolated_and_acked() {
+    let socket_mode = socket_mode();
+    let handled = Arc::new(AtomicUsize::new(0));
+
+    let counter = handled.clone();
+    let report = socket_mode
+        .replay(
+            Replay::from_envelopes([
+                message("env-1", "C1", "boom"),
+                message("env-2", "C1", "fine"),
+            ]),
+            move |event| {
+                let counter = counter.clone();
+                async move {
+                    if text(&event) == "boom" {
+                        panic!("handler bug");
+                    }
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    event.ack.ack();
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(report.acks.len(), 2);
+    assert_eq!(report.ack("env-1").unwrap().payload, None);
+    assert_eq!(handled.load(Ordering::SeqCst), 1);
+
+    let metrics = socket_mode.metrics();
+    assert_eq!(metrics.panicked, 1);
+    assert_eq!(metrics.completed, 1);
+    assert_eq!(metrics.in_flight, 0);
+}
+
+#[tokio::test]
+async fn test_max_in_flight_wait_bounds_concurrency() {
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let (r, p) = (running.clone(), peak.clone());
+    let socket_mode = socket_mode().max_in_flight(2);
+    let report = socket_mode
+        .replay(messages(6), move |event| {
+            let (running, peak) = (r.clone(), p.clone());
+            async move {
+                event.ack.ack();
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            }
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(report.acks.len(), 6);
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+    assert_eq!(socket_mode.metrics().completed, 6);
+}
+
+#[tokio::test]
+async fn test_overflow_drop_and_redeliver() {
+    let slow = |event: SocketModeEvent| async move {
+        event.ack.ack();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    };
+
+    let dropping = socket_mode()
+        .max_in_flight(1)
+        .overflow(OverflowPolicy::Drop);
+    let report = dropping.replay(messages(3), slow).await.unwrap();
+    // Dropped envelopes are acknowledged so Slack does not retry them
+    assert_eq!(report.acks.len(), 3);
+    assert_eq!(dropping.metrics().dropped, 2);
+    assert_eq!(dropping.metrics().completed, 1);
+
+    let redelivering = socket_mode()
+        .max_in_flight(1)
+        .overflow(OverflowPolicy::Redeliver);
+    let report = redelivering.replay(messages(3), slow).await.unwrap();
+    // Left unacknowledged for Slack to deliver again
+    assert_eq!(report.acks.len(), 1);
+    assert_eq!(report.acks[0].envelope_id, "env-0");
+    assert_eq!(redelivering.metrics().dropped, 2);
+}
+
+#[tokio::test]
+async fn test_ordered_by_channel_and_timing_observer() {
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let timings = Arc::new(Mutex::new(Vec::new()));
+
+    let recorded = timings.clone();
+    let socket_mode = socket_mode()
+        .ordered_by_channel(true)
+        .on_handler_timing(move |timing| recorded.lock().unwrap().push(timing.clone()));
+
+    // Earlier events sleep longer; per-channel ordering still runs them first
+    let envelopes = [
+        message("env-1", "C1", "30"),
+        message("env-2", "C2", "0"),
+        message("env-3", "C1", "10"),
+        message("env-4", "C1", "0"),
+    ];
+    let log = order.clone();
+    socket_mode
+        .replay(Replay::from_envelopes(envelopes), move |event| {
+            let log = log.clone();
+            async move {
+                event.ack.ack();
+                let delay = text(&event).parse().unwrap();
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                log.lock().unwrap().push(event.envelope_id.clone());
+            }
+        })
+        .await
+        .unwrap();
+
+    let order = order.lock().unwrap();
+    let c1: Vec<_> = order.iter().filter(|id| *id != "env-2").collect();
+    assert_eq!(c1, ["env-1", "env-3", "env-4"]);
+    // The other channel was not held up behind C1
+    assert_eq!(order[0], "env-2");
+
+    let timings = timings.lock().unwrap();
+    assert_eq!(timings.len(), 4);
+    assert!(timings
+        .iter()
+        .all(|t| t.kind.as_deref() == Some("message") && t.outcome == HandlerOutcome::Completed));
+    let queued = timings.iter().find(|t| t.envelope_id == "env-4").unwrap();
+    assert!(queued.queued >= Duration::from_millis(30));
+}
@@ -0,0 +1,237 @@
+//! Tests for typed interaction payloads
+//!
+//! These tests parse sample payloads and do not need Slack credentials.
+
+use chrono::{NaiveDate, NaiveTime};
+use serde_json::{json, Value};
+use slacko::api::socket_mode::InteractivePayload;
+use slacko::interactions::{ElementValue, Interaction};
+
+fn parse(payload: Value) -> Interaction {
+    let payload: InteractivePayload = serde_json::from_value(payload).unwrap();
+    payload.interaction().unwrap()
+}
+
+#[test]
+fn test_block_actions_typed_actions() {
+    let interaction = parse(json!({
+        "type": "block_actions",
+        "user": { "id": "U1", "username": "alice", "team_id": "T1" },
+        "team": { "id": "T1", "domain": "acme" },
+        "channel": { "id": "C1", "name": "general" },
+        "trigger_id": "123.456",
+        "response_url": "https://hooks.slack.com/actions/T1/1/abc",
+        "container": { "type": "message", "message_ts": "1.2" },
+        "message": { "ts": "1.2", "text": "Deploy?" },
+        "actions": [
+            {
+                "type": "button",
+                "action_id": "approve_btn",
+                "block_id": "b1",
+                "value": "approve",
+                "text": { "type": "plain_text", "text": "Approve" },
+                "action_ts": "1.3"
+            },
+            {
+                "type": "multi_users_select",
+                "action_id": "reviewers",
+                "block_id": "b2",
+                "selected_users": ["U2", "U3"],
+                "action_ts": "1.4"
+            },
+            {
+                "type": "checkboxes",
+                "action_id": "flags",
+                "block_id": "b3",
+                "selected_options": [
+                    { "text": { "type": "plain_text", "text": "Canary" }, "value": "canary" }
+                ],
+                "action_ts": "1.5"
+            },
+            {
+                "type": "some_future_element",
+                "action_id": "future",
+                "block_id": "b4"
+            }
+        ]
+    }));
+
+    let Interaction::BlockActions(actions) = interaction else {
+        panic!("expected block_actions");
+    };
+    assert_eq!(actions.user.id, "U1");
+    assert_eq!(
+        actions.team.as_ref().unwrap().domain.as_deref(),
+        Some("acme")
+    );
+    assert_eq!(actions.channel.as_ref().unwrap().id, "C1");
+    assert_eq!(actions.actions.len(), 4);
+
+    let button = actions.action("approve_btn").unwrap();
+    assert_eq!(button.block_id.as_deref(), Some("b1"));
+    assert_eq!(button.value.text(), Some("approve"));
+    assert!(matches!(button.value, ElementValue::Button { .. }));
+
+    let reviewers = actions.action("reviewers").unwrap();
+    assert_eq!(reviewers.value.selected_users(), vec!["U2", "U3"]);
+
+    let flags = actions.action("flags").unwrap();
+    assert_eq!(flags.value.selected_values(), vec!["canary"]);
+
+    assert!(matches!(
+        actions.action("future").unwrap().value,
+        ElementValue::Unknown
+    ));
+}
+
+#[test]
+fn test_view_submission_state_values() {
+    let interaction = parse(json!({
+        "type": "view_submission",
+        "user": { "id": "U1" },
+        "team": { "id": "T1" },
+        "trigger_id": "123.456",
+        "view": {
+            "id": "V1",
+            "type": "modal",
+            "callback_id": "deploy_modal",
+            "private_metadata": "{\"env\":\"prod\"}",
+            "hash": "abc",
+            "state": {
+                "values": {
+                    "title_block": {
+                        "title": { "type": "plain_text_input", "value": "Release 1.2" }
+                    },
+                    "date_block": {
+                        "date": { "type": "datepicker", "selected_date": "2026-10-18" }
+                    },
+                    "time_block": {
+                        "time": { "type": "timepicker", "selected_time": "14:30", "timezone": "Europe/Oslo" }
+                    },
+                    "when_block": {
+                        "when": { "type": "datetimepicker", "selected_date_time": 1760781600 }
+                    },
+                    "service_block": {
+                        "service": {
+                            "type": "static_select",
+                            "selected_option": {
+                                "text": { "type": "plain_text", "text": "API" },
+                                "value": "api"
+                            }
+                        }
+                    },
+                    "channel_block": {
+                        "channels": { "type": "multi_conversations_select", "selected_conversations": ["C1", "C2"] }
+                    }
+                }
+            },
+            "blocks": []
+        },
+        "response_urls": []
+    }));
+
+    let Interaction::ViewSubmission(submission) = interaction else {
+        panic!("expected view_submission");
+    };
+    let view = &submission.view;
+    assert_eq!(view.callback_id.as_deref(), Some("deploy_modal"));
+    assert_eq!(view.private_metadata.as_deref(), Some("{\"env\":\"prod\"}"));
+
+    let state = &view.state;
+    assert_eq!(
+        state.get("title_block", "title").unwrap().text(),
+        Some("Release 1.2")
+    );
+    assert!(state.get("title_block", "missing").is_none());
+    assert_eq!(
+        state.find("date").unwrap().selected_date(),
+        NaiveDate::from_ymd_opt(2026, 10, 18)
+    );
+    assert_eq!(
+        state.find("time").unwrap().selected_time(),
+        NaiveTime::from_hms_opt(14, 30, 0)
+    );
+    assert_eq!(
+        state
+            .find("when")
+            .unwrap()
+            .selected_date_time()
+            .unwrap()
+            .timestamp(),
+        1760781600
+    );
+    assert_eq!(
+        state.find("service").unwrap().selected_values(),
+        vec!["api"]
+    );
+    assert_eq!(
+        state.find("channels").unwrap().selected_conversations(),
+        vec!["C1", "C2"]
+    );
+}
+
+#[test]
+fn test_other_interaction_types() {
+    let closed = parse(json!({
+        "type": "view_closed",
+        "user": { "id": "U1" },
+        "view": { "id": "V1", "callback_id": "deploy_modal" },
+        "is_cleared": true
+    }));
+    assert!(matches!(closed, Interaction::ViewClosed(c) if c.is_cleared && c.view.id == "V1"));
+
+    let shortcut = parse(json!({
+        "type": "shortcut",
+        "callback_id": "open_deploy",
+        "trigger_id": "123.456",
+        "user": { "id": "U1" },
+        "team": { "id": "T1" }
+    }));
+    assert!(matches!(shortcut, Interaction::Shortcut(s) if s.callback_id == "open_deploy"));
+
+    let message_action = parse(json!({
+        "type": "message_action",
+        "callback_id": "summarize",
+        "trigger_id": "123.456",
+        "user": { "id": "U1" },
+        "channel": { "id": "C1" },
+        "message": { "ts": "1.2", "text": "Long thread" },
+        "response_url": "https://hooks.slack.com/app/T1/1/abc"
+    }));
+    let Interaction::MessageAction(action) = message_action else {
+        panic!("expected message_action");
+    };
+    assert_eq!(action.channel.id, "C1");
+    assert_eq!(action.message["text"], "Long thread");
+
+    let suggestion = parse(json!({
+        "type": "block_suggestion",
+        "action_id": "service_select",
+        "block_id": "b1",
+        "value": "ap",
+        "user": { "id": "U1" },
+        "container": { "type": "view", "view_id": "V1" }
+    }));
+    assert!(matches!(suggestion, Interaction::BlockSuggestion(s) if s.value == "ap"));
+
+    let unknown = parse(json!({ "type": "dialog_submission", "user": { "id": "U1" } }));
+    assert!(matches!(unknown, Interaction::Unknown));
+}
+
+#[test]
+fn test_raw_fields_still_available() {
+    let payload: InteractivePayload = serde_json::from_value(json!({
+        "type": "block_actions",
+        "user": { "id": "U1" },
+        "actions": [{ "type": "button", "action_id": "a", "value": "v" }],
+        "api_app_id": "A1"
+    }))
+    .unwrap();
+
+    assert_eq!(payload.actions[0]["value"], "v");
+    assert_eq!(payload.extra["api_app_id"], "A1");
+    assert!(matches!(
+        payload.interaction().unwrap(),
+        Interaction::BlockActions(_)
+    ));
+}
@@ -0,0 +1,260 @@
+//! Tests for connection lifecycle control
+//!
+//! These tests exercise shutdown handles and status channels against a
+//! local WebSocket server, so they run without Slack credentials.
+
+mod common;
+
+use common::socket;
+use serde_json::json;
+use slacko::replay::MockApi;
+use slacko::{AuthConfig, ConnectionState, ShutdownHandle, SlackClient};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+#[tokio::test]
+async fn test_shutdown_handle_signal() {
+    let handle = ShutdownHandle::new();
+    let clone = handle.clone();
+    assert!(!handle.is_shutdown());
+
+    let waiter = tokio::spawn(async move { clone.wait().await });
+
+    handle.shutdown();
+    assert!(handle.is_shutdown());
+
+    tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .expect("wait() should return after shutdown")
+        .unwrap();
+
+    // Waiting after shutdown returns immediately
+    tokio::time::timeout(Duration::from_millis(100), handle.wait())
+        .await
+        .expect("wait() should return immediately once triggered");
+
+    println!("✓ ShutdownHandle signals all clones");
+}
+
+#[tokio::test]
+async fn test_socket_mode_shared_shutdown_and_status() {
+    let client = SlackClient::new(AuthConfig::app("xapp-test")).unwrap();
+
+    let shutdown = ShutdownHandle::new();
+    let socket_mode = client.socket_mode().with_shutdown(shutdown.clone());
+    assert!(!socket_mode.shutdown_handle().is_shutdown());
+
+    shutdown.shutdown();
+    assert!(socket_mode.shutdown_handle().is_shutdown());
+
+    let status = socket_mode.status();
+    assert_eq!(status.borrow().state, ConnectionState::Connecting);
+    assert_eq!(status.borrow().open_connections, 0);
+    assert!(status.borrow().last_event_at.is_none());
+
+    println!("✓ Socket Mode exposes shutdown handle and status");
+}
+
+#[tokio::test]
+async fn test_rtm_shutdown_and_status() {
+    let client = SlackClient::new(AuthConfig::bot("xoxb-test")).unwrap();
+
+    let rtm = client.rtm();
+    let handle = rtm.shutdown_handle();
+    handle.shutdown();
+    assert!(rtm.shutdown_handle().is_shutdown());
+    assert_eq!(rtm.status().borrow().state, ConnectionState::Connecting);
+
+    println!("✓ RTM exposes shutdown handle and status");
+}
+
+#[tokio::test]
+async fn test_socket_mode_shutdown_drains_handlers_and_closes() {
+    let (api, listener) = socket::setup().await;
+    let socket_mode = api.client().socket_mode();
+    let shutdown = socket_mode.shutdown_handle();
+    let mut status = socket_mode.status();
+
+    let handled = Arc::new(Mutex::new(Vec::new()));
+    let (started_tx, started_rx) = oneshot::channel::<()>();
+    let (release_tx, release_rx) = oneshot::channel::<()>();
+    let gates = Arc::new(Mutex::new(Some((started_tx, release_rx))));
+    let seen = handled.clone();
+    let runner = tokio::spawn(async move {
+        socket_mode
+            .start_async_with_reconnect(move |event| {
+                let (seen, gates) = (seen.clone(), gates.clone());
+                async move {
+                    if event.envelope_id.is_empty() {
+                        return;
+                    }
+                    seen.lock().unwrap().push(event.envelope_id.clone());
+                    let gate = gates.lock().unwrap().take();
+                    if let Some((started, release)) = gate {
+                        let _ = started.send(());
+                        let _ = release.await;
+                    }
+                    event.ack.ack();
+                }
+            })
+            .await
+    });
+
+    let mut ws = socket::accept(&listener).await;
+    socket::send(&mut ws, socket::hello()).await;
+    socket::send(&mut ws, socket::message("env-1", "slow")).await;
+    started_rx.await.unwrap();
+    assert_eq!(status.borrow().state, ConnectionState::Connected);
+    assert_eq!(status.borrow().open_connections, 1);
+
+    shutdown.shutdown();
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        status.wait_for(|s| s.state == ConnectionState::ShuttingDown),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    // Envelopes arriving during shutdown are left for Slack to redeliver
+    socket::send(&mut ws, socket::message("env-2", "late")).await;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!runner.is_finished());
+    assert_eq!(status.borrow().state, ConnectionState::ShuttingDown);
+
+    // The in-flight ack is sent before the close frame
+    release_tx.send(()).unwrap();
+    assert_eq!(
+        socket::receive(&mut ws).await,
+        Some(json!({ "envelope_id": "env-1" }))
+    );
+    assert_eq!(socket::receive(&mut ws).await, None);
+
+    tokio::time::timeout(Duration::from_secs(5), runner)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(status.borrow().state, ConnectionState::Closed);
+    assert_eq!(status.borrow().open_connections, 0);
+    assert_eq!(*handled.lock().unwrap(), ["env-1"]);
+
+    println!("✓ Socket Mode shutdown drains handlers, acks and closes");
+}
+
+#[tokio::test]
+async fn test_socket_mode_shutdown_during_reconnect_acks_on_old_socket() {
+    let (api, listener) = socket::setup().await;
+    // The first connection opens; its replacement cannot
+    api.respond_once(
+        "apps.connections.open",
+        json!({ "ok": true, "url": format!("ws://{}", listener.local_addr().unwrap()) }),
+    );
+    api.respond(
+        "apps.connections.open",
+        json!({ "ok": false, "error": "internal_error" }),
+    );
+    let socket_mode = api.client().socket_mode();
+    let shutdown = socket_mode.shutdown_handle();
+    let mut status = socket_mode.status();
+
+    let (started_tx, started_rx) = oneshot::channel::<()>();
+    let (release_tx, release_rx) = oneshot::channel::<()>();
+    let gates = Arc::new(Mutex::new(Some((started_tx, release_rx))));
+    let runner = tokio::spawn(async move {
+        socket_mode
+            .start_async_with_reconnect(move |event| {
+                let gates = gates.clone();
+                async move {
+                    let gate = gates.lock().unwrap().take();
+                    if let Some((started, release)) = gate {
+                        let _ = started.send(());
+                        let _ = release.await;
+                    }
+                    event.ack.ack();
+                }
+            })
+            .await
+    });
+
+    let mut ws = socket::accept(&listener).await;
+    socket::send(&mut ws, socket::hello()).await;
+    socket::send(&mut ws, socket::message("env-1", "slow")).await;
+    started_rx.await.unwrap();
+    socket::send(&mut ws, socket::disconnect("refresh_requested")).await;
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        status.wait_for(|s| s.state == ConnectionState::Reconnecting { attempt: 1 }),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    // Shutting down before a replacement opens still drains the old socket
+    shutdown.shutdown();
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        status.wait_for(|s| s.state == ConnectionState::ShuttingDown),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    release_tx.send(()).unwrap();
+    assert_eq!(
+        socket::receive(&mut ws).await,
+        Some(json!({ "envelope_id": "env-1" }))
+    );
+    assert_eq!(socket::receive(&mut ws).await, None);
+
+    tokio::time::timeout(Duration::from_secs(5), runner)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(status.borrow().state, ConnectionState::Closed);
+
+    println!("✓ Socket Mode shutdown during a reconnect acks on the old socket");
+}
+
+#[tokio::test]
+async fn test_socket_mode_failed_connection_reports_reconnecting() {
+    // Nothing listens on the URL handed out by apps.connections.open
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let api = MockApi::start().await.unwrap();
+    api.respond("apps.connections.open", json!({ "ok": true, "url": url }));
+
+    let socket_mode = api.client().socket_mode();
+    let shutdown = socket_mode.shutdown_handle();
+    let mut status = socket_mode.status();
+    let runner = tokio::spawn(async move {
+        socket_mode
+            .start_async_with_reconnect(|event| async move {
+                event.ack.ack();
+            })
+            .await
+    });
+
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        status.wait_for(|s| s.state == ConnectionState::Reconnecting { attempt: 1 }),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(status.borrow().open_connections, 0);
+
+    // Shutdown interrupts the backoff
+    shutdown.shutdown();
+    tokio::time::timeout(Duration::from_secs(5), runner)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(status.borrow().state, ConnectionState::Closed);
+
+    println!("✓ Socket Mode reports reconnect attempts for failed connections");
+}
@@ -0,0 +1,162 @@
+//! Tests for typed message metadata
+//!
+//! These tests run against a local mock Web API and do not need Slack
+//! credentials.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use slacko::app::App;
+use slacko::metadata::MetadataRegistry;
+use slacko::replay::{MockApi, Replay};
+use slacko::ConversationHistoryRequest;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct TaskCreated {
+    id: String,
+    priority: u8,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct TaskClosed {
+    id: String,
+}
+
+#[tokio::test]
+async fn test_post_and_read_metadata() {
+    let api = MockApi::start().await.unwrap();
+    api.respond(
+        "chat.postMessage",
+        json!({ "ok": true, "channel": "C1", "ts": "1.2", "message": { "type": "message", "ts": "1.2" } }),
+    );
+    let client = api.client();
+    let task = TaskCreated {
+        id: "TK-1".into(),
+        priority: 2,
+    };
+
+    client
+        .chat()
+        .post_with_metadata("C1", "Task created", "task_created", &task)
+        .await
+        .unwrap();
+    assert_eq!(
+        api.calls_to("chat.postMessage")[0].params["metadata"],
+        json!({ "event_type": "task_created", "event_payload": { "id": "TK-1", "priority": 2 } })
+    );
+
+    api.respond(
+        "conversations.history",
+        json!({
+            "ok": true,
+            "has_more": false,
+            "messages": [
+                {
+                    "type": "message",
+                    "ts": "1.2",
+                    "text": "Task created",
+                    "metadata": {
+                        "event_type": "task_created",
+                        "event_payload": { "id": "TK-1", "priority": 2 }
+                    }
+                },
+                { "type": "message", "ts": "1.1", "text": "No metadata" }
+            ]
+        }),
+    );
+    let history = client
+        .conversations()
+        .history_with_options(ConversationHistoryRequest {
+            channel: "C1".into(),
+            include_all_metadata: Some(true),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        api.calls_to("conversations.history")[0].params["include_all_metadata"],
+        true
+    );
+
+    let messages = history.messages;
+    assert_eq!(
+        messages[0].metadata_as::<TaskCreated>().unwrap(),
+        Some(task)
+    );
+    assert_eq!(messages[1].metadata_as::<TaskCreated>().unwrap(), None);
+    assert!(messages[0].metadata_as::<Vec<String>>().is_err());
+}
+
+#[tokio::test]
+async fn test_registry_parses_metadata_events() {
+    let registry = MetadataRegistry::new()
+        .register::<TaskCreated>("task_created")
+        .register::<TaskClosed>("task_closed");
+    assert!(registry.contains("task_closed"));
+
+    let api = MockApi::start().await.unwrap();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    let app = App::new(api.client()).event("message_metadata_posted", move |ctx| {
+        let log = log.clone();
+        let registry = registry.clone();
+        async move {
+            let event = ctx.metadata_posted().unwrap();
+            let entry = match registry.parse_event(&event) {
+                None => format!("unknown {}", event.metadata.event_type),
+                Some(Err(_)) => "invalid".to_string(),
+                Some(Ok(parsed)) => {
+                    if let Some(task) = parsed.downcast_ref::<TaskCreated>() {
+                        format!("created {} p{}", task.id, task.priority)
+                    } else {
+                        let closed = parsed.downcast::<TaskClosed>().unwrap();
+                        format!("closed {}", closed.id)
+                    }
+                }
+            };
+            log.lock().unwrap().push(entry);
+            Ok(())
+        }
+    });
+
+    let envelopes = [
+        ("task_created", json!({ "id": "TK-1", "priority": 1 })),
+        ("task_closed", json!({ "id": "TK-1" })),
+        ("task_closed", json!({ "task": "TK-1" })),
+        ("deploy_started", json!({})),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(i, (event_type, payload))| {
+        json!({
+            "envelope_id": format!("env-{}", i),
+            "type": "events_api",
+            "accepts_response_payload": false,
+            "payload": {
+                "type": "event_callback",
+                "event_id": format!("Ev{}", i),
+                "event": {
+                    "type": "message_metadata_posted",
+                    "app_id": "A1",
+                    "bot_id": "B1",
+                    "team_id": "T1",
+                    "channel_id": "C1",
+                    "message_ts": "1.2",
+                    "event_ts": "1.3",
+                    "metadata": { "event_type": event_type, "event_payload": payload }
+                }
+            }
+        })
+    });
+    app.replay(Replay::from_envelopes(envelopes)).await.unwrap();
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            "created TK-1 p1",
+            "closed TK-1",
+            "invalid",
+            "unknown deploy_started"
+        ]
+    );
+}
@@ -0,0 +1,285 @@
+//! Tests for the HTTP Events API receiver
+//!
+//! These tests sign requests locally and do not need Slack credentials.
+//! Run with `cargo test --features http-receiver`.
+
+#![cfg(feature = "http-receiver")]
+
+mod common;
+
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use serde_json::json;
+use slacko::api::socket_mode::{SocketModeEventType, SocketModePayload};
+use slacko::receiver::HttpReceiver;
+use slacko::signature::SignatureVerifier;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::oneshot;
+
+const SECRET: &str = "8f742231b10e8888abcd99yyyzzz85a5";
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn signed_headers(body: &str, timestamp: i64, content_type: &str) -> HeaderMap {
+    let mut headers = SignatureVerifier::new(SECRET).signed_headers_at(timestamp, body.as_bytes());
+    headers.insert(CONTENT_TYPE, HeaderValue::from_str(content_type).unwrap());
+    headers
+}
+
+#[tokio::test]
+async fn test_receiver_url_verification() {
+    let receiver = HttpReceiver::new(SECRET);
+    let body = r#"{"type":"url_verification","challenge":"3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P","token":"x"}"#;
+    let headers = signed_headers(body, now(), "application/json");
+
+    let response = receiver
+        .handle(&headers, body.as_bytes(), |_event| async {})
+        .await;
+    assert_eq!(response.status, 200);
+    assert_eq!(
+        response.body,
+        "3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P"
+    );
+
+    println!("✓ HTTP receiver answers url_verification");
+}
+
+#[tokio::test]
+async fn test_receiver_rejects_bad_signature() {
+    let receiver = HttpReceiver::new(SECRET);
+    let body = r#"{"type":"event_callback","event":{"type":"app_mention"}}"#;
+
+    let mut headers = signed_headers(body, now(), "application/json");
+    headers.insert("x-slack-signature", HeaderValue::from_static("v0=deadbeef"));
+    let response = receiver
+        .handle(&headers, body.as_bytes(), |_event| async {})
+        .await;
+    assert_eq!(response.status, 401);
+
+    // Tampered body
+    let headers = signed_headers(body, now(), "application/json");
+    let response = receiver
+        .handle(&headers, b"{\"type\":\"event_callback\"}", |_event| async {
+        })
+        .await;
+    assert_eq!(response.status, 401);
+
+    // Stale timestamp
+    let headers = signed_headers(body, now() - 600, "application/json");
+    let response = receiver
+        .handle(&headers, body.as_bytes(), |_event| async {})
+        .await;
+    assert_eq!(response.status, 401);
+
+    println!("✓ HTTP receiver rejects invalid signatures and stale requests");
+}
+
+#[tokio::test]
+async fn test_receiver_event_callback_with_retry() {
+    let receiver = HttpReceiver::new(SECRET);
+    let body = json!({
+        "type": "event_callback",
+        "team_id": "T12345",
+        "api_app_id": "A12345",
+        "event": {"type": "app_mention", "user": "U12345", "text": "hi", "channel": "C12345"},
+        "event_id": "Ev12345",
+        "event_time": 1234567890
+    })
+    .to_string();
+    let mut headers = signed_headers(&body, now(), "application/json");
+    headers.insert("x-slack-retry-num", HeaderValue::from_static("1"));
+    headers.insert(
+        "x-slack-retry-reason",
+        HeaderValue::from_static("http_timeout"),
+    );
+
+    let seen = Arc::new(Mutex::new(None));
+    let seen_clone = seen.clone();
+    let response = receiver
+        .handle(&headers, body.as_bytes(), move |event| {
+            let seen = seen_clone.clone();
+            async move {
+                event.ack.ack();
+                *seen.lock().unwrap() = Some(event);
+            }
+        })
+        .await;
+    assert_eq!(response.status, 200);
+    assert!(response.body.is_empty());
+
+    let event = seen.lock().unwrap().take().expect("handler should run");
+    assert_eq!(event.envelope_type, SocketModeEventType::EventsApi);
+    assert_eq!(event.envelope_id, "Ev12345");
+    assert_eq!(event.retry_attempt, Some(1));
+    assert_eq!(event.retry_reason.as_deref(), Some("http_timeout"));
+    match event.payload {
+        SocketModePayload::EventsApi(payload) => {
+            assert_eq!(payload.event_id.as_deref(), Some("Ev12345"));
+        }
+        other => panic!("Expected EventsApi payload, got {:?}", other),
+    }
+
+    println!("✓ HTTP receiver parses event_callback with retry headers");
+}
+
+#[tokio::test]
+async fn test_receiver_slash_command_response() {
+    let receiver = HttpReceiver::new(SECRET);
+    let body = "command=%2Fweather&text=London&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2Fxxx&trigger_id=123.456&user_id=U12345&channel_id=C12345";
+    let headers = signed_headers(body, now(), "application/x-www-form-urlencoded");
+
+    let response = receiver
+        .handle(&headers, body.as_bytes(), |event| async move {
+            match &event.payload {
+                SocketModePayload::SlashCommand(cmd) => {
+                    assert_eq!(cmd.command, "/weather");
+                    assert_eq!(cmd.text.as_deref(), Some("London"));
+                    event.ack.ack_with(json!({"text": "Sunny in London"}));
+                }
+                other => panic!("Expected SlashCommand payload, got {:?}", other),
+            }
+        })
+        .await;
+
+    assert_eq!(response.status, 200);
+    assert_eq!(response.content_type, Some("application/json"));
+    let body: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+    assert_eq!(body["text"], "Sunny in London");
+
+    println!("✓ HTTP receiver answers slash commands with ack payload");
+}
+
+#[tokio::test]
+async fn test_receiver_interactive_payload() {
+    let receiver = HttpReceiver::new(SECRET).ack_timeout(Duration::from_millis(200));
+    let payload = json!({
+        "type": "block_actions",
+        "user": {"id": "U12345"},
+        "trigger_id": "123.456.abc",
+        "actions": [{"action_id": "approve", "type": "button", "value": "yes"}]
+    });
+    let body: String = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("payload", &payload.to_string())
+        .finish();
+    let headers = signed_headers(&body, now(), "application/x-www-form-urlencoded");
+
+    // Handler never acknowledges: receiver responds after the ack timeout
+    let response = receiver
+        .handle(&headers, body.as_bytes(), |event| async move {
+            assert_eq!(event.envelope_type, SocketModeEventType::Interactive);
+            assert!(event.accepts_response_payload);
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        })
+        .await;
+    assert_eq!(response.status, 200);
+    assert!(response.body.is_empty());
+
+    println!("✓ HTTP receiver parses interactive form posts");
+}
+
+#[tokio::test]
+async fn test_receiver_serve_and_shutdown() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let receiver = HttpReceiver::new(SECRET);
+    let shutdown = receiver.shutdown_handle();
+    let server = tokio::spawn(receiver.serve_listener(listener, |event| async move {
+        event.ack.ack_with(json!({"text": "pong"}));
+    }));
+
+    let body = "command=%2Fping&response_url=https%3A%2F%2Fexample.com&user_id=U1&channel_id=C1";
+    let http = reqwest::Client::new();
+    let response = http
+        .post(format!("http://{}/slack/events", addr))
+        .headers(signed_headers(
+            body,
+            now(),
+            "application/x-www-form-urlencoded",
+        ))
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(json["text"], "pong");
+
+    // Other paths are not served
+    let response = http
+        .post(format!("http://{}/other", addr))
+        .body("{}")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+
+    shutdown.shutdown();
+    tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .expect("server should stop after shutdown")
+        .unwrap()
+        .unwrap();
+
+    println!("✓ HTTP receiver serves requests and shuts down");
+}
+
+#[tokio::test]
+async fn test_receiver_shutdown_waits_for_handlers() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let receiver = HttpReceiver::new(SECRET);
+    let shutdown = receiver.shutdown_handle();
+    let finished = Arc::new(Mutex::new(false));
+    let (release_tx, release_rx) = oneshot::channel::<()>();
+    let release = Arc::new(Mutex::new(Some(release_rx)));
+    let done = finished.clone();
+    let server = tokio::spawn(receiver.serve_listener(listener, move |event| {
+        let (done, release) = (done.clone(), release.clone());
+        async move {
+            // Acknowledge first, then keep working
+            event.ack.ack();
+            let release = release.lock().unwrap().take().unwrap();
+            let _ = release.await;
+            *done.lock().unwrap() = true;
+        }
+    }));
+
+    let body = json!({
+        "type": "event_callback",
+        "event_id": "Ev1",
+        "event": { "type": "app_mention", "text": "hi" }
+    })
+    .to_string();
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/slack/events", addr))
+        .headers(signed_headers(&body, now(), "application/json"))
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    shutdown.shutdown();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(
+        !server.is_finished(),
+        "returned while a handler was running"
+    );
+
+    release_tx.send(()).unwrap();
+    tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .expect("server should stop once handlers finish")
+        .unwrap()
+        .unwrap();
+    assert!(*finished.lock().unwrap());
+
+    println!("✓ HTTP receiver shutdown waits for running handlers");
+}
@@ -0,0 +1,164 @@
+//! Tests for recording and replaying Socket Mode traffic
+//!
+//! These tests replay envelopes against a local mock Web API and do not
+//! need Slack credentials.
+
+use serde_json::{json, Value};
+use slacko::app::App;
+use slacko::replay::{MockApi, Recorder, Replay, Timing, REDACTED};
+use slacko::responder::CommandResponse;
+use slacko::{AuthConfig, SlackClient};
+use std::time::{Duration, Instant};
+
+fn mention(envelope_id: &str, text: &str) -> Value {
+    json!({
+        "envelope_id": envelope_id,
+        "type": "events_api",
+        "accepts_response_payload": false,
+        "payload": {
+            "type": "event_callback",
+            "token": "verification-token",
+            "event_id": format!("Ev{}", envelope_id),
+            "event": { "type": "app_mention", "user": "U1", "channel": "C1", "text": text, "ts": "1.1" }
+        }
+    })
+}
+
+fn command(envelope_id: &str) -> Value {
+    json!({
+        "envelope_id": envelope_id,
+        "type": "slash_commands",
+        "accepts_response_payload": true,
+        "payload": {
+            "command": "/status",
+            "text": "",
+            "user_id": "U1",
+            "channel_id": "C1",
+            "response_url": "https://hooks.slack.com/commands/T1/1/secret",
+            "trigger_id": "1.2"
+        }
+    })
+}
+
+#[test]
+fn test_recorder_redacts_and_replay_reads_back() {
+    let path = std::env::temp_dir().join(format!("slacko-replay-{}.jsonl", std::process::id()));
+    let recorder = Recorder::create(&path)
+        .unwrap()
+        .redact_key("text")
+        .redact_with(|frame| {
+            if let Some(user) = frame.pointer_mut("/payload/event/user") {
+                *user = json!("U_ANON");
+            }
+        });
+
+    recorder.record(&json!({ "type": "hello", "num_connections": 1 }).to_string());
+    recorder.record(&mention("env-1", "my password is hunter2").to_string());
+    recorder.record(&command("env-2").to_string());
+    recorder.record("not json");
+    recorder.flush();
+
+    let replay = Replay::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let frames = replay.frames();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0].frame["type"], "hello");
+
+    let event = &frames[1].frame["payload"];
+    assert_eq!(event["token"], REDACTED);
+    assert_eq!(event["event"]["text"], REDACTED);
+    assert_eq!(event["event"]["user"], "U_ANON");
+    assert_eq!(event["event"]["channel"], "C1");
+    assert_eq!(frames[2].frame["payload"]["response_url"], REDACTED);
+    assert!(frames[2].offset_ms >= frames[1].offset_ms);
+}
+
+#[tokio::test]
+async fn test_app_replay_asserts_acks_and_api_calls() {
+    let api = MockApi::start().await.unwrap();
+    api.respond(
+        "chat.postMessage",
+        json!({ "ok": true, "channel": "C1", "ts": "2.1", "message": { "ts": "2.1", "text": "Hi!" } }),
+    );
+
+    let app = App::new(api.client())
+        .event("app_mention", |ctx| async move {
+            ctx.say("Hi!").await?;
+            Ok(())
+        })
+        .command("/status", |ctx| async move {
+            ctx.ack_response(&CommandResponse::ephemeral_text("All good"))?;
+            Ok(())
+        });
+
+    // Bare envelopes and recorded frames can be mixed in one file
+    let jsonl = [
+        json!({ "type": "hello" }).to_string(),
+        json!({ "offset_ms": 5, "frame": mention("env-1", "hello") }).to_string(),
+        command("env-2").to_string(),
+        // Redelivered envelope: acknowledged again without running listeners
+        mention("env-1", "hello").to_string(),
+    ]
+    .join("\n");
+    let replay = Replay::from_reader(jsonl.as_bytes()).unwrap();
+
+    let report = app.replay(replay).await.unwrap();
+
+    assert_eq!(report.frames, 4);
+    assert_eq!(report.acks.len(), 3);
+    assert_eq!(report.ack("env-1").unwrap().payload, None);
+    assert_eq!(
+        report.ack("env-2").unwrap().payload,
+        Some(json!({ "response_type": "ephemeral", "text": "All good" }))
+    );
+
+    let calls = api.calls_to("chat.postMessage");
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].params["channel"], "C1");
+    assert_eq!(calls[0].params["text"], "Hi!");
+    assert_eq!(api.calls().len(), 1);
+}
+
+#[tokio::test]
+async fn test_replay_timing() {
+    let socket_mode = SlackClient::new(AuthConfig::app("xapp-test"))
+        .unwrap()
+        .socket_mode();
+    let frames = [
+        json!({ "offset_ms": 0, "frame": mention("env-1", "a") }),
+        json!({ "offset_ms": 200, "frame": mention("env-2", "b") }),
+    ];
+    let jsonl = frames
+        .iter()
+        .map(|f| f.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let started = Instant::now();
+    let report = socket_mode
+        .replay(
+            Replay::from_reader(jsonl.as_bytes())
+                .unwrap()
+                .timing(Timing::Scaled(0.5)),
+            |event| async move {
+                event.ack.ack();
+            },
+        )
+        .await
+        .unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(100));
+    assert_eq!(report.acks.len(), 2);
+
+    let started = Instant::now();
+    let report = socket_mode
+        .replay(
+            Replay::from_envelopes([mention("env-3", "c")]),
+            |_event| async move {},
+        )
+        .await
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_millis(100));
+    // Dropped without acking: acknowledged with an empty payload
+    assert_eq!(report.ack("env-3").unwrap().payload, None);
+}
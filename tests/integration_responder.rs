@@ -0,0 +1,221 @@
+//! Tests for response URL replies and ack payloads
+//!
+//! These tests post to a local HTTP server and do not need Slack credentials.
+
+use serde_json::{json, Value};
+use slacko::api::socket_mode::Ack;
+use slacko::responder::{
+    CommandResponse, ResponseMessage, ResponseType, ViewSubmissionResponse, MAX_USES,
+};
+use slacko::{AuthConfig, MessageBuilder, SlackClient, SlackError};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Serve canned responses, recording each request body
+async fn serve(responses: Vec<(u16, &'static str)>) -> (String, Arc<Mutex<Vec<Value>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/actions/T1/1/abc", listener.local_addr().unwrap());
+    let bodies = Arc::new(Mutex::new(Vec::new()));
+
+    let recorded = bodies.clone();
+    tokio::spawn(async move {
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 4096];
+            let request = loop {
+                let n = stream.read(&mut chunk).await.unwrap();
+                buf.extend_from_slice(&chunk[..n]);
+                let text = String::from_utf8_lossy(&buf).to_string();
+                if let Some((head, rest)) = text.split_once("\r\n\r\n") {
+                    let length: usize = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if rest.len() >= length {
+                        break rest.to_string();
+                    }
+                }
+            };
+            recorded
+                .lock()
+                .unwrap()
+                .push(serde_json::from_str(&request).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+
+    (url, bodies)
+}
+
+fn client() -> SlackClient {
+    SlackClient::new(AuthConfig::bot("xoxb-test")).unwrap()
+}
+
+#[test]
+fn test_response_message_shapes() {
+    assert_eq!(
+        serde_json::to_value(ResponseMessage::text("hi").ephemeral()).unwrap(),
+        json!({ "text": "hi", "response_type": "ephemeral" })
+    );
+    assert_eq!(
+        serde_json::to_value(
+            ResponseMessage::text("done")
+                .in_channel()
+                .replace_original(true)
+                .thread_ts("1.2")
+        )
+        .unwrap(),
+        json!({
+            "text": "done",
+            "response_type": "in_channel",
+            "replace_original": true,
+            "thread_ts": "1.2"
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(ResponseMessage::delete()).unwrap(),
+        json!({ "delete_original": true })
+    );
+
+    let built = MessageBuilder::new().text("fallback").divider();
+    let message = ResponseMessage::from(built).in_channel();
+    let value = serde_json::to_value(message).unwrap();
+    assert_eq!(value["text"], "fallback");
+    assert_eq!(value["blocks"][0]["type"], "divider");
+    assert_eq!(value["response_type"], "in_channel");
+}
+
+#[tokio::test]
+async fn test_send_and_use_limit() {
+    let (url, bodies) = serve(vec![(200, "ok"); MAX_USES as usize]).await;
+    let responder = client().responder(url);
+
+    responder.ephemeral("one").await.unwrap();
+    responder.in_channel("two").await.unwrap();
+    responder.replace("three").await.unwrap();
+    responder.delete_original().await.unwrap();
+    responder
+        .send(ResponseMessage::text("five").thread_ts("1.2"))
+        .await
+        .unwrap();
+    assert_eq!(responder.remaining_uses(), 0);
+    assert!(!responder.is_expired());
+
+    let err = responder.ephemeral("six").await.unwrap_err();
+    assert!(matches!(err, SlackError::ApiError { message, .. } if message == "used_url"));
+
+    let bodies = bodies.lock().unwrap();
+    assert_eq!(bodies.len(), 5);
+    assert_eq!(
+        bodies[0],
+        json!({ "text": "one", "response_type": "ephemeral" })
+    );
+    assert_eq!(bodies[2]["replace_original"], true);
+    assert_eq!(bodies[3], json!({ "delete_original": true }));
+}
+
+#[tokio::test]
+async fn test_error_mapping() {
+    let (url, _) = serve(vec![
+        (404, "expired_url"),
+        (200, r#"{"ok":false,"error":"invalid_blocks"}"#),
+        (429, ""),
+    ])
+    .await;
+    let responder = client().responder(url);
+
+    let err = responder.ephemeral("a").await.unwrap_err();
+    assert!(matches!(err, SlackError::ApiError { message, .. } if message == "expired_url"));
+
+    let err = responder.ephemeral("b").await.unwrap_err();
+    assert!(matches!(err, SlackError::ApiError { message, .. } if message == "invalid_blocks"));
+
+    let err = responder.ephemeral("c").await.unwrap_err();
+    assert!(err.is_rate_limit());
+}
+
+#[test]
+fn test_view_submission_response_shapes() {
+    assert_eq!(
+        serde_json::to_value(ViewSubmissionResponse::errors([(
+            "title_block",
+            "Title is required"
+        )]))
+        .unwrap(),
+        json!({
+            "response_action": "errors",
+            "errors": { "title_block": "Title is required" }
+        })
+    );
+
+    let view =
+        json!({ "type": "modal", "title": { "type": "plain_text", "text": "Next" }, "blocks": [] });
+    assert_eq!(
+        serde_json::to_value(ViewSubmissionResponse::update(view.clone())).unwrap(),
+        json!({ "response_action": "update", "view": view })
+    );
+    assert_eq!(
+        serde_json::to_value(ViewSubmissionResponse::push(view.clone())).unwrap(),
+        json!({ "response_action": "push", "view": view })
+    );
+    assert_eq!(
+        serde_json::to_value(ViewSubmissionResponse::clear()).unwrap(),
+        json!({ "response_action": "clear" })
+    );
+}
+
+#[test]
+fn test_command_response_shapes() {
+    let blocks = vec![json!({ "type": "divider" })];
+    assert_eq!(
+        serde_json::to_value(CommandResponse::ephemeral(blocks.clone())).unwrap(),
+        json!({ "response_type": "ephemeral", "blocks": blocks })
+    );
+    assert_eq!(
+        serde_json::to_value(CommandResponse::in_channel(blocks.clone()).text("fallback")).unwrap(),
+        json!({ "response_type": "in_channel", "text": "fallback", "blocks": blocks })
+    );
+    assert_eq!(
+        serde_json::to_value(CommandResponse::ephemeral_text("hi")).unwrap(),
+        json!({ "response_type": "ephemeral", "text": "hi" })
+    );
+
+    let built = MessageBuilder::new().text("Deploying").divider();
+    let value =
+        serde_json::to_value(CommandResponse::message(ResponseType::InChannel, built)).unwrap();
+    assert_eq!(value["response_type"], "in_channel");
+    assert_eq!(value["text"], "Deploying");
+    assert_eq!(value["blocks"][0]["type"], "divider");
+}
+
+#[tokio::test]
+async fn test_ack_response_requires_payload_support() {
+    let (ack, rx) = Ack::new("env1", true);
+    assert!(ack.ack_response(&ViewSubmissionResponse::clear()).unwrap());
+    assert_eq!(
+        rx.await.unwrap(),
+        Some(json!({ "response_action": "clear" }))
+    );
+
+    let (ack, _rx) = Ack::new("env2", false);
+    let err = ack
+        .ack_response(&CommandResponse::ephemeral_text("hi"))
+        .unwrap_err();
+    assert!(matches!(err, SlackError::InvalidParameter(_)));
+    // The envelope can still be acknowledged without a payload
+    assert!(!ack.is_acked());
+    assert!(ack.ack());
+}
@@ -0,0 +1,389 @@
+//! Tests for RTM sessions
+//!
+//! These tests run against a local WebSocket server and mock Web API and do
+//! not need Slack credentials.
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use slacko::api::rtm::RtmEvent;
+use slacko::replay::MockApi;
+use slacko::{AuthConfig, ConnectionState, SlackClient};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{accept_async, tungstenite::Message, WebSocketStream};
+
+type Server = WebSocketStream<TcpStream>;
+
+async fn setup() -> (MockApi, TcpListener) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let api = MockApi::start().await.unwrap();
+    api.respond(
+        "rtm.connect",
+        json!({
+            "ok": true,
+            "url": format!("ws://{}", listener.local_addr().unwrap()),
+            "self": { "id": "U0", "name": "bot" },
+            "team": { "id": "T1", "name": "Team" }
+        }),
+    );
+    (api, listener)
+}
+
+async fn accept(listener: &TcpListener) -> Server {
+    let (stream, _) = listener.accept().await.unwrap();
+    accept_async(stream).await.unwrap()
+}
+
+async fn send(server: &mut Server, frame: Value) {
+    server.send(Message::Text(frame.to_string())).await.unwrap();
+}
+
+/// Next text frame from the client, skipping pings
+async fn receive(server: &mut Server) -> Value {
+    loop {
+        match server.next().await.unwrap().unwrap() {
+            Message::Text(text) => {
+                let frame: Value = serde_json::from_str(&text).unwrap();
+                if frame["type"] != "ping" {
+                    return frame;
+                }
+            }
+            _ => continue,
+        }
+    }
+}
+
+async fn next_event(session: &mut slacko::api::rtm::RtmSession) -> RtmEvent {
+    tokio::time::timeout(Duration::from_secs(5), session.next())
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[test]
+fn test_rtm_event_parsing() {
+    let event = RtmEvent::from_value(
+        json!({ "type": "presence_change", "users": ["U1", "U2"], "presence": "away" }),
+    );
+    match event {
+        RtmEvent::PresenceChange(change) => {
+            assert_eq!(change.users.unwrap(), ["U1", "U2"]);
+            assert_eq!(change.presence, "away");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    let event = RtmEvent::from_value(json!({
+        "type": "reaction_added",
+        "user": "U1",
+        "reaction": "thumbsup",
+        "item": { "type": "message", "channel": "C1", "ts": "1.1" }
+    }));
+    assert!(matches!(&event, RtmEvent::ReactionAdded(r) if r.reaction == "thumbsup"));
+
+    let event =
+        RtmEvent::from_value(json!({ "type": "channel_marked", "channel": "C1", "ts": "1.1" }));
+    assert!(matches!(&event, RtmEvent::ChannelMarked(m) if m.channel == "C1"));
+
+    let event =
+        RtmEvent::from_value(json!({ "type": "error", "error": { "code": 1, "msg": "bad" } }));
+    assert!(matches!(&event, RtmEvent::Error(e) if e.msg == "bad"));
+
+    // Malformed and unknown events are kept untyped
+    let event = RtmEvent::from_value(json!({ "type": "user_typing", "channel": "C1" }));
+    assert!(matches!(event, RtmEvent::Other { .. }));
+    assert_eq!(event.event_type(), "user_typing");
+
+    let event: RtmEvent =
+        serde_json::from_value(json!({ "type": "dnd_updated", "user": "U1" })).unwrap();
+    assert_eq!(event.event_type(), "dnd_updated");
+}
+
+#[tokio::test]
+async fn test_rtm_session_events_and_outbound_frames() {
+    let (api, listener) = setup().await;
+    let server = tokio::spawn(async move {
+        let mut ws = accept(&listener).await;
+        send(&mut ws, json!({ "type": "hello" })).await;
+        send(
+            &mut ws,
+            json!({ "type": "user_typing", "channel": "C1", "user": "U1" }),
+        )
+        .await;
+
+        let typing = receive(&mut ws).await;
+        let message = receive(&mut ws).await;
+        let presence = receive(&mut ws).await;
+        // An event first, then the reply to the message
+        send(
+            &mut ws,
+            json!({ "type": "reaction_removed", "user": "U1", "reaction": "x", "item": {} }),
+        )
+        .await;
+        send(
+            &mut ws,
+            json!({ "ok": true, "reply_to": message["id"], "ts": "5.5", "text": "hi" }),
+        )
+        .await;
+        (typing, message, presence)
+    });
+
+    let mut session = api.client().rtm().session().await.unwrap();
+    assert_eq!(session.self_info().id, "U0");
+    assert_eq!(session.team().name, "Team");
+
+    assert!(matches!(next_event(&mut session).await, RtmEvent::Hello));
+    match next_event(&mut session).await {
+        RtmEvent::UserTyping(typing) => assert_eq!(typing.user, "U1"),
+        other => panic!("unexpected {:?}", other),
+    }
+
+    let sender = session.sender();
+    sender.typing("C1").await.unwrap();
+    let reply = tokio::spawn({
+        let sender = sender.clone();
+        async move { sender.send_message("C1", "hi").await }
+    });
+    // Queued after the message so the server sees frames in order
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    sender.presence_sub(&["U1", "U2"]).await.unwrap();
+
+    let reply = reply.await.unwrap().unwrap();
+    assert_eq!(reply.ts.as_deref(), Some("5.5"));
+    // The reply itself is not surfaced as an event
+    assert!(matches!(
+        next_event(&mut session).await,
+        RtmEvent::ReactionRemoved(_)
+    ));
+
+    let (typing, message, presence) = server.await.unwrap();
+    assert_eq!(typing["type"], "typing");
+    assert_eq!(typing["channel"], "C1");
+    assert_eq!(message["type"], "message");
+    assert_eq!(message["text"], "hi");
+    assert_eq!(reply.reply_to, message["id"].as_u64().unwrap());
+    assert!(message["id"].as_u64() > typing["id"].as_u64());
+    assert_eq!(presence["type"], "presence_sub");
+    assert_eq!(presence["ids"], json!(["U1", "U2"]));
+}
+
+#[tokio::test]
+async fn test_rtm_session_reconnects_after_goodbye_and_shuts_down() {
+    let (api, listener) = setup().await;
+    let rtm = api.client().rtm();
+    let shutdown = rtm.shutdown_handle();
+    let status = rtm.status();
+
+    let server = tokio::spawn(async move {
+        let mut first = accept(&listener).await;
+        send(&mut first, json!({ "type": "goodbye" })).await;
+
+        let mut second = accept(&listener).await;
+        send(&mut second, json!({ "type": "hello" })).await;
+        let message = receive(&mut second).await;
+        send(
+            &mut second,
+            json!({ "ok": false, "reply_to": message["id"], "error": { "code": 2, "msg": "channel_not_found" } }),
+        )
+        .await;
+        // Keep the socket open until the client closes it
+        while let Some(Ok(frame)) = second.next().await {
+            if frame.is_close() {
+                break;
+            }
+        }
+    });
+
+    let mut session = rtm.session().await.unwrap();
+    assert!(matches!(next_event(&mut session).await, RtmEvent::Goodbye));
+    assert!(matches!(next_event(&mut session).await, RtmEvent::Hello));
+    assert_eq!(api.calls_to("rtm.connect").len(), 2);
+
+    let err = session
+        .sender()
+        .send_message("C404", "hi")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("channel_not_found"));
+
+    shutdown.shutdown();
+    assert!(tokio::time::timeout(Duration::from_secs(5), session.next())
+        .await
+        .unwrap()
+        .is_none());
+    session.join().await.unwrap();
+    server.await.unwrap();
+    assert_eq!(status.borrow().state, ConnectionState::Closed);
+}
+
+#[tokio::test]
+async fn test_rtm_start_skips_own_user_and_bot_messages() {
+    let (api, listener) = setup().await;
+    api.respond(
+        "auth.test",
+        json!({
+            "ok": true,
+            "url": "https://team.slack.com/",
+            "team": "Team",
+            "user": "bot",
+            "team_id": "T1",
+            "user_id": "U0",
+            "bot_id": "B0"
+        }),
+    );
+    let rtm = api.client().rtm();
+    let shutdown = rtm.shutdown_handle();
+
+    let server = tokio::spawn(async move {
+        let mut ws = accept(&listener).await;
+        send(&mut ws, json!({ "type": "hello" })).await;
+        for message in [
+            json!({ "type": "message", "channel": "C1", "user": "U0", "text": "own user" }),
+            json!({ "type": "message", "subtype": "bot_message", "channel": "C1", "bot_id": "B0", "text": "own bot" }),
+            json!({ "type": "message", "subtype": "bot_message", "channel": "C1", "bot_id": "B9", "text": "other bot" }),
+            json!({ "type": "message", "channel": "C1", "user": "U1", "text": "user" }),
+        ] {
+            send(&mut ws, message).await;
+        }
+        while let Some(Ok(frame)) = ws.next().await {
+            if frame.is_close() {
+                break;
+            }
+        }
+    });
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let texts = seen.clone();
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        rtm.start(move |message| {
+            let mut texts = texts.lock().unwrap();
+            texts.push(message.text.unwrap_or_default());
+            if texts.len() == 2 {
+                shutdown.shutdown();
+            }
+        }),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    server.await.unwrap();
+
+    assert_eq!(*seen.lock().unwrap(), ["other bot", "user"]);
+    assert_eq!(api.calls_to("auth.test").len(), 1);
+}
+
+#[tokio::test]
+async fn test_rtm_session_pings_and_reports_dead_connection() {
+    let (api, listener) = setup().await;
+    let server = tokio::spawn(async move {
+        let mut ws = accept(&listener).await;
+        // Answer the first app-level ping, then go silent
+        loop {
+            if let Message::Text(text) = ws.next().await.unwrap().unwrap() {
+                let frame: Value = serde_json::from_str(&text).unwrap();
+                if frame["type"] == "ping" {
+                    send(&mut ws, json!({ "type": "pong", "reply_to": frame["id"] })).await;
+                    break;
+                }
+            }
+        }
+        let silent = ws;
+        let next = accept(&listener).await;
+        (silent, next)
+    });
+
+    let session = api
+        .client()
+        .rtm()
+        .ping_interval(Duration::from_millis(50))
+        .ping_timeout(Duration::from_millis(200))
+        .session()
+        .await
+        .unwrap();
+
+    let rtt = session.sender().ping().await.unwrap();
+    assert!(rtt < Duration::from_secs(5));
+
+    // The silent connection is replaced with a fresh one
+    let (_silent, _next) = tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(api.calls_to("rtm.connect").len(), 2);
+}
+
+// The handshake callback signature is fixed by tungstenite
+#[allow(clippy::result_large_err)]
+#[tokio::test]
+async fn test_stealth_session_uses_cookie_handshake() {
+    use tokio_tungstenite::accept_hdr_async;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let gateway = format!("ws://{}/", listener.local_addr().unwrap());
+    let api = MockApi::start().await.unwrap();
+    api.respond(
+        "auth.test",
+        json!({
+            "ok": true,
+            "url": "https://team.slack.com/",
+            "team": "Team",
+            "user": "me",
+            "team_id": "T1",
+            "user_id": "U9"
+        }),
+    );
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut handshake = None;
+        let mut ws = accept_hdr_async(stream, |request: &Request, response: Response| {
+            let header = |name| {
+                request
+                    .headers()
+                    .get(name)
+                    .map(|v| v.to_str().unwrap().to_string())
+            };
+            handshake = Some((
+                request.uri().to_string(),
+                header("cookie"),
+                header("origin"),
+            ));
+            Ok(response)
+        })
+        .await
+        .unwrap();
+        send(&mut ws, json!({ "type": "hello" })).await;
+        send(
+            &mut ws,
+            json!({ "type": "presence_change", "users": ["U1", "U2"], "presence": "active" }),
+        )
+        .await;
+        let typing = receive(&mut ws).await;
+        (handshake.unwrap(), typing)
+    });
+
+    let client = SlackClient::new(AuthConfig::stealth("xoxc-abc", "xoxd-def%2F"))
+        .unwrap()
+        .with_base_url(api.url());
+    let mut session = client.rtm().gateway_url(gateway).session().await.unwrap();
+    assert_eq!(session.self_info().id, "U9");
+    assert_eq!(session.team().id, "T1");
+
+    assert!(matches!(next_event(&mut session).await, RtmEvent::Hello));
+    match next_event(&mut session).await {
+        RtmEvent::PresenceChange(change) => assert_eq!(change.users.unwrap(), ["U1", "U2"]),
+        other => panic!("unexpected {:?}", other),
+    }
+    session.sender().typing("C1").await.unwrap();
+
+    let ((uri, cookie, origin), typing) = server.await.unwrap();
+    assert!(uri.contains("token=xoxc-abc"));
+    assert!(uri.contains("gateway_server=T1-1"));
+    assert_eq!(cookie.as_deref(), Some("d=xoxd-def%2F"));
+    assert_eq!(origin.as_deref(), Some("https://app.slack.com"));
+    assert_eq!(typing["type"], "typing");
+    assert_eq!(api.calls_to("rtm.connect").len(), 0);
+}
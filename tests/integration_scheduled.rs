@@ -0,0 +1,237 @@
+//! Tests for the scheduled message manager
+//!
+//! These tests run against a local mock Web API and do not need Slack
+//! credentials.
+
+use chrono::{Duration, FixedOffset, Utc};
+use serde_json::{json, Value};
+use slacko::blocks::MessageBuilder;
+use slacko::replay::MockApi;
+use slacko::scheduled::{ScheduledFilter, MAX_SCHEDULE_DAYS};
+use slacko::SlackError;
+
+fn scheduled(id: &str, channel: &str, post_at: i64, text: &str) -> Value {
+    json!({ "id": id, "channel_id": channel, "post_at": post_at, "date_created": 1, "text": text })
+}
+
+fn scheduled_message() -> Value {
+    scheduled("Q1", "C1", Utc::now().timestamp() + 60, "Reminder")
+}
+
+async fn api() -> MockApi {
+    let api = MockApi::start().await.unwrap();
+    api.respond(
+        "chat.scheduleMessage",
+        json!({ "ok": true, "channel": "C1", "scheduled_message_id": "Q9", "post_at": 0 }),
+    );
+    api
+}
+
+#[tokio::test]
+async fn test_schedule_validates_window() {
+    let api = api().await;
+    let scheduled = api.client().chat().scheduled();
+
+    // Any time zone; sent as a Unix timestamp
+    let at = (Utc::now() + Duration::hours(1)).with_timezone(&FixedOffset::east_opt(3600).unwrap());
+    let message = MessageBuilder::new()
+        .text("Standup")
+        .section("*Standup* soon")
+        .thread_ts("1.0");
+    scheduled.schedule("C1", message, at).await.unwrap();
+
+    let params = &api.calls_to("chat.scheduleMessage")[0].params;
+    assert_eq!(params["channel"], "C1");
+    assert_eq!(params["post_at"], at.timestamp());
+    assert_eq!(params["text"], "Standup");
+    assert_eq!(params["thread_ts"], "1.0");
+    assert_eq!(params["blocks"][0]["text"]["text"], "*Standup* soon");
+
+    for at in [
+        Utc::now() - Duration::minutes(1),
+        Utc::now() + Duration::days(MAX_SCHEDULE_DAYS + 1),
+    ] {
+        let err = scheduled
+            .schedule("C1", MessageBuilder::new().text("x"), at)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SlackError::InvalidParameter(_)), "{}", err);
+    }
+    assert_eq!(api.calls_to("chat.scheduleMessage").len(), 1);
+}
+
+#[tokio::test]
+async fn test_list_pages_and_cancel_by_filter() {
+    let api = api().await;
+    let now = Utc::now().timestamp();
+    api.respond_once(
+        "chat.scheduledMessages.list",
+        json!({
+            "ok": true,
+            "scheduled_messages": [
+                scheduled("Q1", "C1", now + 60, "Deploy reminder"),
+                scheduled("Q2", "C2", now + 120, "Lunch")
+            ],
+            "response_metadata": { "next_cursor": "page2" }
+        }),
+    );
+    api.respond(
+        "chat.scheduledMessages.list",
+        json!({
+            "ok": true,
+            "scheduled_messages": [scheduled("Q3", "C2", now + 180, "Deploy freeze")],
+            "response_metadata": { "next_cursor": "" }
+        }),
+    );
+    let scheduled = api.client().chat().scheduled();
+
+    let all = scheduled.list(&ScheduledFilter::new()).await.unwrap();
+    let ids: Vec<_> = all.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(ids, ["Q1", "Q2", "Q3"]);
+    assert_eq!(all[0].post_at_time().unwrap().timestamp(), now + 60);
+    let lists = api.calls_to("chat.scheduledMessages.list");
+    assert!(lists[0].params.get("channel").is_none());
+    assert_eq!(lists[1].params["cursor"], "page2");
+
+    // Only the last page is answered from here on
+    let cancelled = scheduled
+        .cancel(
+            ScheduledFilter::new()
+                .channel("C2")
+                .text_contains("Deploy")
+                .before(Utc::now() + Duration::hours(1)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(cancelled.deleted.len(), 1);
+    assert!(cancelled.is_complete());
+    let list = &api.calls_to("chat.scheduledMessages.list")[2].params;
+    assert_eq!(list["channel"], "C2");
+    assert!(list["latest"].is_string());
+    assert_eq!(
+        api.calls_to("chat.deleteScheduledMessage")[0].params,
+        json!({ "channel": "C2", "scheduled_message_id": "Q3" })
+    );
+}
+
+#[tokio::test]
+async fn test_reschedule_recreates_then_deletes() {
+    let api = api().await;
+    let scheduled = api.client().chat().scheduled();
+    let original = serde_json::from_value(scheduled_message()).unwrap();
+
+    let at = Utc::now() + Duration::days(2);
+    let replacement = scheduled.reschedule(&original, at).await.unwrap();
+    assert_eq!(replacement.scheduled_message_id, "Q9");
+
+    let methods: Vec<_> = api.calls().into_iter().map(|c| c.method).collect();
+    assert_eq!(
+        methods,
+        ["chat.scheduleMessage", "chat.deleteScheduledMessage"]
+    );
+    let params = &api.calls_to("chat.scheduleMessage")[0].params;
+    assert_eq!(params["text"], "Reminder");
+    assert_eq!(params["post_at"], at.timestamp());
+    assert_eq!(
+        api.calls_to("chat.deleteScheduledMessage")[0].params["scheduled_message_id"],
+        "Q1"
+    );
+
+    // A failed delete removes the replacement again
+    api.respond_once(
+        "chat.deleteScheduledMessage",
+        json!({ "ok": false, "error": "invalid_scheduled_message_id" }),
+    );
+    let err = scheduled.reschedule(&original, at).await.unwrap_err();
+    assert!(err.to_string().contains("invalid_scheduled_message_id"));
+    let deletes = api.calls_to("chat.deleteScheduledMessage");
+    assert_eq!(deletes.len(), 3);
+    assert_eq!(deletes[2].params["scheduled_message_id"], "Q9");
+
+    // Without text (e.g. blocks only) nothing is scheduled or deleted
+    let mut blocks_only = scheduled_message();
+    blocks_only["text"] = json!("");
+    let blocks_only = serde_json::from_value(blocks_only).unwrap();
+    let err = scheduled.reschedule(&blocks_only, at).await.unwrap_err();
+    assert!(matches!(err, SlackError::InvalidParameter(_)), "{}", err);
+    assert_eq!(api.calls_to("chat.scheduleMessage").len(), 2);
+    assert_eq!(api.calls_to("chat.deleteScheduledMessage").len(), 3);
+}
+
+#[tokio::test]
+async fn test_schedule_keeps_every_message_field() {
+    let api = api().await;
+    let chat = api.client().chat();
+    let at = (Utc::now() + Duration::hours(1)).timestamp();
+
+    let message = json!({
+        "text": "Build failed",
+        "attachments": [{ "color": "#ff0000", "title": "main", "text": "3 tests failed" }],
+        "unfurl_links": false,
+        "link_names": true,
+        "channel": "C1"
+    });
+    chat.schedule_message_blocks("C1", message, at)
+        .await
+        .unwrap();
+
+    let params = &api.calls_to("chat.scheduleMessage")[0].params;
+    assert_eq!(params["attachments"][0]["title"], "main");
+    assert_eq!(params["attachments"][0]["color"], "#ff0000");
+    assert_eq!(params["unfurl_links"], false);
+    assert_eq!(params["link_names"], true);
+
+    // Fields chat.scheduleMessage does not take, and another channel, are rejected
+    for message in [
+        json!({ "text": "x", "icon_emoji": ":robot:" }),
+        json!({ "text": "x", "channel": "C2" }),
+    ] {
+        let err = chat
+            .schedule_message_blocks("C1", message, at)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SlackError::InvalidParameter(_)), "{}", err);
+    }
+    assert_eq!(api.calls_to("chat.scheduleMessage").len(), 1);
+}
+
+#[tokio::test]
+async fn test_cancel_reports_deleted_and_failed() {
+    let api = api().await;
+    let now = Utc::now().timestamp();
+    api.respond(
+        "chat.scheduledMessages.list",
+        json!({
+            "ok": true,
+            "scheduled_messages": [
+                scheduled("Q1", "C1", now + 60, "a"),
+                scheduled("Q2", "C1", now + 120, "b"),
+                scheduled("Q3", "C1", now + 180, "c")
+            ]
+        }),
+    );
+    api.respond_once("chat.deleteScheduledMessage", json!({ "ok": true }));
+    api.respond_once(
+        "chat.deleteScheduledMessage",
+        json!({ "ok": false, "error": "invalid_scheduled_message_id" }),
+    );
+
+    // A failed delete does not stop the rest
+    let report = api
+        .client()
+        .chat()
+        .scheduled()
+        .cancel(ScheduledFilter::new())
+        .await
+        .unwrap();
+    let deleted: Vec<_> = report.deleted.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(deleted, ["Q1", "Q3"]);
+    assert!(!report.is_complete());
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0.id, "Q2");
+    assert!(report.failed[0]
+        .1
+        .to_string()
+        .contains("invalid_scheduled_message_id"));
+    assert_eq!(api.calls_to("chat.deleteScheduledMessage").len(), 3);
+}
@@ -0,0 +1,112 @@
+//! Tests for Slack request signature verification
+//!
+//! These tests are offline and do not need Slack credentials.
+
+use slacko::signature::{SignatureVerifier, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use std::time::Duration;
+
+// Example request from Slack's "Verifying requests from Slack" documentation
+const SECRET: &str = "8f742231b10e8888abcd99yyyzzz85a5";
+const TIMESTAMP: &str = "1531420618";
+const BODY: &str = "token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&team_domain=testteamnow&channel_id=G8PSS9T3V&channel_name=foobar&user_id=U2CERLKJA&user_name=roadrunner&command=%2Fwebhook-collect&text=&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2FT1DC2JH3J%2F397700885554%2F96rGlfmibIGlgcZRskXaIFfN&trigger_id=398738663015.47445629121.803a0bc887a14d10d2c447fce8b6703c";
+const SIGNATURE: &str = "v0=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503";
+
+#[test]
+fn test_sign_matches_documented_example() {
+    let verifier = SignatureVerifier::new(SECRET);
+    assert_eq!(
+        verifier.sign(TIMESTAMP.parse().unwrap(), BODY.as_bytes()),
+        SIGNATURE
+    );
+}
+
+#[test]
+fn test_verify_documented_example() {
+    let verifier = SignatureVerifier::new(SECRET);
+    let now = TIMESTAMP.parse::<i64>().unwrap() + 10;
+
+    assert!(verifier
+        .verify_at(TIMESTAMP, BODY.as_bytes(), SIGNATURE, now)
+        .is_ok());
+}
+
+#[test]
+fn test_verify_rejects_tampered_body() {
+    let verifier = SignatureVerifier::new(SECRET);
+    let now = TIMESTAMP.parse().unwrap();
+    let body = BODY.replace("roadrunner", "coyote");
+
+    assert!(verifier
+        .verify_at(TIMESTAMP, body.as_bytes(), SIGNATURE, now)
+        .is_err());
+}
+
+#[test]
+fn test_verify_rejects_wrong_secret() {
+    let verifier = SignatureVerifier::new("not-the-secret");
+    let now = TIMESTAMP.parse().unwrap();
+
+    assert!(verifier
+        .verify_at(TIMESTAMP, BODY.as_bytes(), SIGNATURE, now)
+        .is_err());
+}
+
+#[test]
+fn test_verify_rejects_stale_and_future_timestamps() {
+    let verifier = SignatureVerifier::new(SECRET).max_timestamp_skew(Duration::from_secs(60));
+    let ts: i64 = TIMESTAMP.parse().unwrap();
+
+    assert!(verifier
+        .verify_at(TIMESTAMP, BODY.as_bytes(), SIGNATURE, ts + 60)
+        .is_ok());
+    assert!(verifier
+        .verify_at(TIMESTAMP, BODY.as_bytes(), SIGNATURE, ts + 61)
+        .is_err());
+    assert!(verifier
+        .verify_at(TIMESTAMP, BODY.as_bytes(), SIGNATURE, ts - 61)
+        .is_err());
+}
+
+#[test]
+fn test_verify_rejects_malformed_input() {
+    let verifier = SignatureVerifier::new(SECRET);
+    let now = TIMESTAMP.parse().unwrap();
+
+    for signature in [
+        "a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503",
+        "v1=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503",
+        "v0=not-hex",
+        "v0=abc",
+        "v0=",
+    ] {
+        assert!(
+            verifier
+                .verify_at(TIMESTAMP, BODY.as_bytes(), signature, now)
+                .is_err(),
+            "accepted {}",
+            signature
+        );
+    }
+
+    assert!(verifier
+        .verify_at("yesterday", BODY.as_bytes(), SIGNATURE, now)
+        .is_err());
+}
+
+#[test]
+fn test_signed_headers_round_trip() {
+    let verifier = SignatureVerifier::new(SECRET);
+    let body = br#"{"type":"event_callback","event_id":"Ev123"}"#;
+    let headers = verifier.signed_headers(body);
+
+    assert!(headers.contains_key(TIMESTAMP_HEADER));
+    assert!(headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|s| s.starts_with("v0=")));
+    assert!(verifier.verify_headers(&headers, body).is_ok());
+
+    let mut missing = headers.clone();
+    missing.remove(SIGNATURE_HEADER);
+    assert!(verifier.verify_headers(&missing, body).is_err());
+}
@@ -6,40 +6,32 @@
 
 mod common;
 
-use common::{init, test_client};
+use common::socket;
+use futures_util::StreamExt;
+use serde_json::json;
 use slacko::api::socket_mode::{
-    EventsApiPayload, InteractivePayload, SlashCommandPayload, SocketModeEnvelope,
-    SocketModeEventType,
+    Ack, EventsApiPayload, InteractivePayload, SlashCommandPayload, SocketModeApi,
+    SocketModeEnvelope, SocketModeEventType,
 };
+use slacko::{AuthConfig, ConnectionState, OverflowPolicy, SlackClient, SlackError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
 
 #[tokio::test]
 async fn test_socket_mode_open_connection() {
-    init();
-    let client = skip_if_no_client!(test_client());
+    let (api, listener) = socket::setup().await;
 
-    // apps.connections.open requires an app-level token (xapp-...)
-    let result = client.socket_mode().open_connection().await;
+    let response = api.client().socket_mode().open_connection().await.unwrap();
+    assert!(response.ok);
+    assert_eq!(
+        response.url,
+        format!("ws://{}", listener.local_addr().unwrap())
+    );
+    assert_eq!(api.calls_to("apps.connections.open").len(), 1);
 
-    match result {
-        Ok(response) => {
-            assert!(
-                !response.url.is_empty(),
-                "WebSocket URL should not be empty"
-            );
-            assert!(
-                response.url.starts_with("wss://"),
-                "URL should be a WebSocket URL"
-            );
-            println!("✓ apps.connections.open: got WebSocket URL");
-        }
-        Err(e) => {
-            // Expected to fail without app-level token
-            println!(
-                "✓ apps.connections.open: {} (requires app-level token xapp-...)",
-                e
-            );
-        }
-    }
+    println!("✓ apps.connections.open: got WebSocket URL");
 }
 
 #[tokio::test]
@@ -311,3 +303,455 @@ async fn test_socket_mode_view_submission_envelope() {
 
     println!("✓ Socket Mode view_submission envelope parses correctly");
 }
+
+#[tokio::test]
+async fn test_socket_mode_ack_with_payload() {
+    let (ack, rx) = Ack::new("env123", true);
+    assert_eq!(ack.envelope_id(), "env123");
+    assert!(!ack.is_acked());
+
+    assert!(ack.ack_with(json!({"text": "ok"})));
+    assert!(ack.is_acked());
+
+    // Only the first acknowledgement is delivered
+    assert!(!ack.ack());
+
+    let payload = rx.await.unwrap();
+    assert_eq!(payload, Some(json!({"text": "ok"})));
+
+    println!("✓ Socket Mode ack delivers response payload once");
+}
+
+#[tokio::test]
+async fn test_socket_mode_ack_without_response_payload() {
+    let (ack, rx) = Ack::new("env456", false);
+
+    // Payload is dropped when the envelope does not accept one
+    assert!(ack.ack_with(json!({"text": "ignored"})));
+    assert_eq!(rx.await.unwrap(), None);
+
+    println!("✓ Socket Mode ack drops payload when not accepted");
+}
+
+#[tokio::test]
+async fn test_socket_mode_ack_on_drop() {
+    let (ack, rx) = Ack::new("env789", true);
+    let clone = ack.clone();
+
+    drop(ack);
+    assert!(!clone.is_acked());
+
+    // Dropping the last handle acknowledges with an empty payload
+    drop(clone);
+    assert_eq!(rx.await.unwrap(), None);
+
+    println!("✓ Socket Mode ack is sent when all handles are dropped");
+}
+
+#[tokio::test]
+async fn test_socket_mode_ack_after_deadline() {
+    let (ack, rx) = Ack::new("late123", false);
+
+    // Receiver gone means the deadline already passed
+    drop(rx);
+    assert!(!ack.ack());
+
+    println!("✓ Socket Mode late ack is reported");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_socket_mode_connect_stream_order_and_broadcast() {
+    let (api, listener) = socket::setup().await;
+    let (go_tx, go_rx) = oneshot::channel::<()>();
+
+    let server = tokio::spawn(async move {
+        let mut ws = socket::accept(&listener).await;
+        go_rx.await.unwrap();
+        socket::send(&mut ws, socket::hello()).await;
+        for i in 0..20 {
+            let id = format!("env-{}", i);
+            socket::send(&mut ws, socket::message(&id, &id)).await;
+        }
+        let mut acks = Vec::new();
+        while acks.len() < 20 {
+            acks.push(socket::receive(&mut ws).await.unwrap()["envelope_id"].clone());
+        }
+        acks
+    });
+
+    let stream = api.client().socket_mode().connect().await.unwrap();
+    let broadcast = stream.into_broadcast(64);
+    let mut first = broadcast.subscribe();
+    let mut second = broadcast.subscribe();
+    assert_eq!(broadcast.subscriber_count(), 2);
+    go_tx.send(()).unwrap();
+
+    let expected: Vec<String> = std::iter::once(String::new())
+        .chain((0..20).map(|i| format!("env-{}", i)))
+        .collect();
+    for subscriber in [&mut first, &mut second] {
+        let mut ids = Vec::new();
+        while ids.len() < expected.len() {
+            let event = tokio::time::timeout(Duration::from_secs(5), subscriber.next())
+                .await
+                .unwrap()
+                .unwrap();
+            if ids.is_empty() {
+                assert_eq!(event.envelope_type, SocketModeEventType::Hello);
+            }
+            // Clones share the ack, so only the first subscriber's is sent
+            event.ack.ack();
+            ids.push(event.envelope_id);
+        }
+        assert_eq!(ids, expected);
+    }
+
+    // Each envelope is acknowledged once; ack frames are not ordered
+    let acks = server.await.unwrap();
+    let mut acks: Vec<&str> = acks.iter().map(|id| id.as_str().unwrap()).collect();
+    let mut envelopes: Vec<&str> = expected[1..].iter().map(String::as_str).collect();
+    acks.sort_unstable();
+    envelopes.sort_unstable();
+    assert_eq!(acks, envelopes);
+
+    println!("✓ Socket Mode stream yields envelopes in order to every subscriber");
+}
+
+#[tokio::test]
+async fn test_socket_mode_live_hello_and_disconnect_shapes() {
+    // Slack sends hello and disconnect without an envelope_id
+    let hello_json = r#"{
+        "type": "hello",
+        "num_connections": 2,
+        "connection_info": {"app_id": "A12345"}
+    }"#;
+
+    let envelope: SocketModeEnvelope = serde_json::from_str(hello_json).unwrap();
+    assert_eq!(envelope.envelope_type, "hello");
+    assert!(envelope.envelope_id.is_empty());
+
+    // The disconnect reason is a top-level field
+    let disconnect_json = r#"{
+        "type": "disconnect",
+        "reason": "refresh_requested",
+        "debug_info": {"host": "applink-1"}
+    }"#;
+
+    let envelope: SocketModeEnvelope = serde_json::from_str(disconnect_json).unwrap();
+    assert_eq!(envelope.envelope_type, "disconnect");
+    assert!(envelope.envelope_id.is_empty());
+    assert_eq!(envelope.reason, Some("refresh_requested".to_string()));
+
+    println!("✓ Socket Mode live hello/disconnect messages parse correctly");
+}
+
+#[tokio::test]
+async fn test_socket_mode_from_app_token() {
+    let bot = SlackClient::new(AuthConfig::bot("xoxb-test")).unwrap();
+    let socket_mode = SocketModeApi::from_app_token("xapp-1-test", bot).unwrap();
+
+    // Handlers receive the bot client, not the app-level connection client
+    assert_eq!(socket_mode.api_client().auth_type_str(), "bot");
+
+    // Without a separate API client, the connection client is used
+    let app = SlackClient::new(AuthConfig::app("xapp-1-test")).unwrap();
+    assert_eq!(app.socket_mode().api_client().auth_type_str(), "app");
+
+    println!("✓ Socket Mode pairs app-level token with API client");
+}
+
+#[tokio::test]
+async fn test_socket_mode_acks_at_deadline_and_on_drop() {
+    let (api, listener) = socket::setup().await;
+    let socket_mode = api
+        .client()
+        .socket_mode()
+        .ack_timeout(Duration::from_millis(500));
+
+    let held = Arc::new(Mutex::new(Vec::new()));
+    let keep = held.clone();
+    let runner = tokio::spawn(async move {
+        socket_mode
+            .start_async(move |event| {
+                let keep = keep.clone();
+                async move {
+                    // env-1 is held past the deadline, env-2 dropped unacknowledged
+                    if event.envelope_id == "env-1" {
+                        keep.lock().unwrap().push(event.ack.clone());
+                    }
+                }
+            })
+            .await
+    });
+
+    let mut ws = socket::accept(&listener).await;
+    socket::send(&mut ws, socket::hello()).await;
+
+    let sent = Instant::now();
+    socket::send(&mut ws, socket::message("env-1", "held")).await;
+    let ack = socket::receive(&mut ws).await.unwrap();
+    assert_eq!(ack, json!({ "envelope_id": "env-1" }));
+    assert!(sent.elapsed() >= Duration::from_millis(500));
+    // Acknowledging after the automatic ack is reported and not sent
+    assert!(!held.lock().unwrap()[0].ack());
+
+    let sent = Instant::now();
+    socket::send(&mut ws, socket::message("env-2", "dropped")).await;
+    let ack = socket::receive(&mut ws).await.unwrap();
+    assert_eq!(ack, json!({ "envelope_id": "env-2" }));
+    assert!(sent.elapsed() < Duration::from_millis(500));
+
+    runner.abort();
+    println!("✓ Socket Mode sends empty acks at the deadline and on drop");
+}
+
+#[tokio::test]
+async fn test_socket_mode_disconnect_opens_replacement_before_closing() {
+    let (api, listener) = socket::setup().await;
+    let socket_mode = api.client().socket_mode();
+
+    // env-1 is acknowledged only after the replacement connection is open
+    let (release_tx, release_rx) = oneshot::channel::<()>();
+    let release = Arc::new(Mutex::new(Some(release_rx)));
+    let runner = tokio::spawn(async move {
+        socket_mode
+            .start_async_with_reconnect(move |event| {
+                let release = release.clone();
+                async move {
+                    if event.envelope_id == "env-1" {
+                        let rx = release.lock().unwrap().take().unwrap();
+                        let _ = rx.await;
+                        event.ack.ack_with(json!({ "text": "done" }));
+                    } else {
+                        event.ack.ack();
+                    }
+                }
+            })
+            .await
+    });
+
+    let mut first = socket::accept(&listener).await;
+    socket::send(&mut first, socket::hello()).await;
+    let mut envelope = socket::message("env-1", "slow");
+    envelope["accepts_response_payload"] = json!(true);
+    socket::send(&mut first, envelope).await;
+    socket::send(&mut first, socket::disconnect("refresh_requested")).await;
+
+    let mut second = socket::accept(&listener).await;
+    // Let the runner swap connections before the handler acknowledges
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    release_tx.send(()).unwrap();
+
+    // The old socket carries the late ack, then closes
+    let ack = socket::receive(&mut first).await.unwrap();
+    assert_eq!(
+        ack,
+        json!({ "envelope_id": "env-1", "payload": { "text": "done" } })
+    );
+    assert!(socket::receive(&mut first).await.is_none());
+
+    socket::send(&mut second, socket::hello()).await;
+    socket::send(&mut second, socket::message("env-2", "next")).await;
+    let ack = socket::receive(&mut second).await.unwrap();
+    assert_eq!(ack["envelope_id"], "env-2");
+    assert_eq!(api.calls_to("apps.connections.open").len(), 2);
+
+    runner.abort();
+    println!("✓ Socket Mode replaces the connection before closing the old one");
+}
+
+#[tokio::test]
+async fn test_socket_mode_wait_overflow_keeps_reading() {
+    let (api, listener) = socket::setup().await;
+    let socket_mode = api
+        .client()
+        .socket_mode()
+        .max_in_flight(1)
+        .overflow(OverflowPolicy::Wait);
+
+    let handled = Arc::new(Mutex::new(Vec::new()));
+    let (release_tx, release_rx) = oneshot::channel::<()>();
+    let release = Arc::new(Mutex::new(Some(release_rx)));
+    let seen = handled.clone();
+    let runner = tokio::spawn(async move {
+        socket_mode
+            .start_async_with_reconnect(move |event| {
+                let (seen, release) = (seen.clone(), release.clone());
+                async move {
+                    if event.envelope_id.is_empty() {
+                        return;
+                    }
+                    seen.lock().unwrap().push(event.envelope_id.clone());
+                    let rx = release.lock().unwrap().take();
+                    if let Some(rx) = rx {
+                        let _ = rx.await;
+                    }
+                    event.ack.ack();
+                }
+            })
+            .await
+    });
+
+    let mut ws = socket::accept(&listener).await;
+    socket::send(&mut ws, socket::hello()).await;
+    socket::send(&mut ws, socket::message("env-1", "slow")).await;
+    socket::send(&mut ws, socket::message("env-2", "queued")).await;
+
+    // env-2 waits for a handler slot without stalling the connection
+    socket::ping(&mut ws).await;
+    assert_eq!(*handled.lock().unwrap(), ["env-1"]);
+
+    release_tx.send(()).unwrap();
+    for id in ["env-1", "env-2"] {
+        let ack = socket::receive(&mut ws).await.unwrap();
+        assert_eq!(ack["envelope_id"], id);
+    }
+    assert_eq!(*handled.lock().unwrap(), ["env-1", "env-2"]);
+
+    runner.abort();
+    println!("✓ Socket Mode answers pings while envelopes wait for a handler slot");
+}
+
+#[tokio::test]
+async fn test_socket_mode_deduplicates_across_connections() {
+    let (api, listener) = socket::setup().await;
+    let socket_mode = api.client().socket_mode().connections(2);
+
+    let handled = Arc::new(AtomicUsize::new(0));
+    let counter = handled.clone();
+    let runner = tokio::spawn(async move {
+        socket_mode
+            .start_async_with_reconnect(move |event| {
+                let counter = counter.clone();
+                async move {
+                    if event.envelope_id == "env-dup" {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                    }
+                    event.ack.ack();
+                }
+            })
+            .await
+    });
+
+    let mut first = socket::accept(&listener).await;
+    let mut second = socket::accept(&listener).await;
+    for ws in [&mut first, &mut second] {
+        socket::send(ws, socket::hello()).await;
+        socket::send(ws, socket::message("env-dup", "twice")).await;
+    }
+
+    // Both deliveries are acknowledged, the handler runs once
+    for ws in [&mut first, &mut second] {
+        let ack = socket::receive(ws).await.unwrap();
+        assert_eq!(ack, json!({ "envelope_id": "env-dup" }));
+    }
+    assert_eq!(handled.load(Ordering::SeqCst), 1);
+
+    runner.abort();
+    println!("✓ Socket Mode handles an envelope delivered on two connections once");
+}
+
+#[tokio::test]
+async fn test_socket_mode_replaces_silent_connection() {
+    let (api, listener) = socket::setup().await;
+    let socket_mode = api
+        .client()
+        .socket_mode()
+        .ping_interval(Duration::from_millis(50))
+        .ping_timeout(Duration::from_millis(200));
+    let status = socket_mode.status();
+
+    let runner = tokio::spawn(async move {
+        socket_mode
+            .start_async_with_reconnect(|event| async move {
+                event.ack.ack();
+            })
+            .await
+    });
+
+    // Never read from the first socket, so the client's pings go unanswered
+    let _silent = socket::accept(&listener).await;
+    let opened = Instant::now();
+    let mut status_rx = status.clone();
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        status_rx.wait_for(|s| s.state == ConnectionState::Reconnecting { attempt: 1 }),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert!(opened.elapsed() >= Duration::from_millis(200));
+
+    let mut replacement = socket::accept(&listener).await;
+    socket::send(&mut replacement, socket::hello()).await;
+    socket::send(&mut replacement, socket::message("env-1", "hi")).await;
+    assert_eq!(
+        socket::receive(&mut replacement).await.unwrap()["envelope_id"],
+        "env-1"
+    );
+    assert_eq!(status.borrow().state, ConnectionState::Connected);
+    assert_eq!(api.calls_to("apps.connections.open").len(), 2);
+
+    runner.abort();
+    println!("✓ Socket Mode replaces a connection that stops sending frames");
+}
+
+#[tokio::test]
+async fn test_socket_mode_link_disabled_returns_error() {
+    let (api, listener) = socket::setup().await;
+    let socket_mode = api.client().socket_mode();
+
+    let runner = tokio::spawn(async move {
+        socket_mode
+            .start_async_with_reconnect(|event| async move {
+                event.ack.ack();
+            })
+            .await
+    });
+
+    let mut ws = socket::accept(&listener).await;
+    socket::send(&mut ws, socket::hello()).await;
+    socket::send(&mut ws, socket::disconnect("link_disabled")).await;
+
+    let result = tokio::time::timeout(Duration::from_secs(5), runner)
+        .await
+        .unwrap()
+        .unwrap();
+    match result {
+        Err(SlackError::ConfigError(message)) => assert!(message.contains("link_disabled")),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(api.calls_to("apps.connections.open").len(), 1);
+
+    println!("✓ Socket Mode stops with an error when the app link is disabled");
+}
+
+#[test]
+fn test_socket_mode_from_env_requires_api_token() {
+    // The only test in this binary that touches the environment
+    for var in [
+        "SLACK_XOXC_TOKEN",
+        "SLACK_XOXD_COOKIE",
+        "SLACK_XOXP_TOKEN",
+        "SLACK_BOT_TOKEN",
+        "SLACK_TOKEN",
+    ] {
+        std::env::remove_var(var);
+    }
+    std::env::set_var("SLACK_APP_TOKEN", "xapp-1-test");
+
+    // The app-level token alone cannot make Web API calls for handlers
+    match SocketModeApi::from_env() {
+        Err(SlackError::ConfigError(message)) => assert!(message.contains("SLACK_BOT_TOKEN")),
+        Ok(_) => panic!("from_env accepted an app-level token for the API client"),
+        Err(other) => panic!("unexpected {:?}", other),
+    }
+
+    std::env::set_var("SLACK_BOT_TOKEN", "xoxb-test");
+    let socket_mode = SocketModeApi::from_env().unwrap();
+    assert_eq!(socket_mode.api_client().auth_type_str(), "bot");
+
+    std::env::remove_var("SLACK_BOT_TOKEN");
+    std::env::remove_var("SLACK_APP_TOKEN");
+    println!("✓ Socket Mode from_env requires a bot or user token");
+}
@@ -0,0 +1,364 @@
+//! Tests for streaming messages with `MessageStream`
+//!
+//! These tests run against a local mock Web API and do not need Slack
+//! credentials.
+
+use futures_util::stream;
+use serde_json::json;
+use slacko::replay::{ApiCall, MockApi};
+use slacko::streaming::{StreamMode, DEFAULT_PLACEHOLDER, MAX_MESSAGE_BLOCKS, MAX_MESSAGE_CHARS};
+use slacko::SlackError;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+
+async fn api() -> MockApi {
+    let api = MockApi::start().await.unwrap();
+    api.respond(
+        "chat.startStream",
+        json!({ "ok": true, "channel": "C1", "stream_id": "S1", "ts": "1.1" }),
+    );
+    api.respond(
+        "chat.stopStream",
+        json!({ "ok": true, "channel": "C1", "ts": "1.1" }),
+    );
+    api
+}
+
+fn appended(calls: &[ApiCall]) -> String {
+    calls
+        .iter()
+        .map(|c| c.params["text"].as_str().unwrap())
+        .collect()
+}
+
+fn tokens(count: usize) -> Vec<String> {
+    (0..count).map(|i| format!("t{} ", i)).collect()
+}
+
+#[tokio::test]
+async fn test_tokens_coalesce_by_time_and_size() {
+    let api = api().await;
+    let chat = api.client().chat();
+
+    let mut message = chat
+        .message_stream("C1")
+        .thread_ts("0.1")
+        .flush_interval(Duration::from_millis(200))
+        .start()
+        .await
+        .unwrap();
+    assert_eq!(message.ts(), "1.1");
+    for token in tokens(20) {
+        message.write(token).await.unwrap();
+    }
+    let done = message.finish().await.unwrap();
+    assert_eq!(done.ts, "1.1");
+
+    let appends = api.calls_to("chat.appendStream");
+    assert!(appends.len() <= 2, "{} appends", appends.len());
+    assert_eq!(appended(&appends), tokens(20).concat());
+    assert_eq!(appends[0].params["stream_id"], "S1");
+    assert_eq!(
+        api.calls_to("chat.startStream")[0].params["thread_ts"],
+        "0.1"
+    );
+    assert_eq!(api.calls_to("chat.stopStream").len(), 1);
+
+    // Reaching the flush size sends without waiting for the interval
+    let mut message = chat
+        .message_stream("C1")
+        .flush_interval(Duration::from_secs(60))
+        .flush_size(6)
+        .start()
+        .await
+        .unwrap();
+    message.write("abc").await.unwrap();
+    message.write("def").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(
+        appended(&api.calls_to("chat.appendStream")[appends.len()..]),
+        "abcdef"
+    );
+    message.finish().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_pipe_retries_rate_limits() {
+    let api = api().await;
+    api.rate_limit("chat.appendStream", 0);
+    api.rate_limit("chat.stopStream", 0);
+
+    let done = api
+        .client()
+        .chat()
+        .message_stream("C1")
+        .flush_interval(Duration::from_millis(10))
+        .start()
+        .await
+        .unwrap()
+        .pipe(stream::iter(tokens(50)))
+        .await
+        .unwrap();
+    assert_eq!(done.channel, "C1");
+
+    let appends = api.calls_to("chat.appendStream");
+    // The rate-limited text is resent with nothing lost or duplicated
+    assert_eq!(appended(&appends[1..]), tokens(50).concat());
+    assert!(tokens(50)
+        .concat()
+        .starts_with(appends[0].params["text"].as_str().unwrap()));
+    assert_eq!(api.calls_to("chat.stopStream").len(), 2);
+}
+
+#[tokio::test]
+async fn test_failed_append_finalizes_stream() {
+    let api = api().await;
+    api.respond(
+        "chat.appendStream",
+        json!({ "ok": false, "error": "msg_too_long" }),
+    );
+
+    let mut message = api
+        .client()
+        .chat()
+        .message_stream("C1")
+        .flush_size(1)
+        .start()
+        .await
+        .unwrap();
+    message.write("too long").await.unwrap();
+    let err = message.finish().await.unwrap_err();
+    assert!(err.to_string().contains("msg_too_long"));
+    assert_eq!(api.calls_to("chat.stopStream").len(), 1);
+}
+
+#[tokio::test]
+async fn test_drop_finalizes_and_async_write() {
+    let api = api().await;
+    let chat = api.client().chat();
+
+    let mut message = chat.message_stream("C1").start().await.unwrap();
+    message.write("partial").await.unwrap();
+    drop(message);
+    for _ in 0..50 {
+        if !api.calls_to("chat.stopStream").is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(appended(&api.calls_to("chat.appendStream")), "partial");
+    assert_eq!(api.calls_to("chat.stopStream").len(), 1);
+
+    // Multi-byte characters split across writes are reassembled
+    let mut message = chat.message_stream("C1").start().await.unwrap();
+    let bytes = "héllo wörld".as_bytes();
+    message.write_all(&bytes[..2]).await.unwrap();
+    message.write_all(&bytes[2..9]).await.unwrap();
+    message.write_all(&bytes[9..]).await.unwrap();
+    message.shutdown().await.unwrap();
+
+    let appends = api.calls_to("chat.appendStream");
+    assert_eq!(appended(&appends[1..]), "héllo wörld");
+}
+
+async fn api_without_streaming() -> MockApi {
+    let api = MockApi::start().await.unwrap();
+    api.respond(
+        "chat.startStream",
+        json!({ "ok": false, "error": "unknown_method" }),
+    );
+    api.respond(
+        "chat.postMessage",
+        json!({ "ok": true, "channel": "C1", "ts": "2.1", "message": { "type": "message", "ts": "2.1" } }),
+    );
+    api.respond(
+        "chat.update",
+        json!({ "ok": true, "channel": "C1", "ts": "2.1", "text": "" }),
+    );
+    api
+}
+
+#[tokio::test]
+async fn test_falls_back_to_updates() {
+    let api = api_without_streaming().await;
+
+    let mut message = api
+        .client()
+        .chat()
+        .message_stream("C1")
+        .thread_ts("0.1")
+        .update_interval(Duration::from_millis(20))
+        .start()
+        .await
+        .unwrap();
+    assert_eq!(message.mode(), StreamMode::Updates);
+    assert_eq!(message.stream_id(), None);
+    assert_eq!(message.ts(), "2.1");
+    message.write("Hello").await.unwrap();
+    message.write(", world").await.unwrap();
+    let done = message.finish().await.unwrap();
+    assert_eq!(done.ts, "2.1");
+
+    let posts = api.calls_to("chat.postMessage");
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].params["text"], DEFAULT_PLACEHOLDER);
+    assert_eq!(posts[0].params["thread_ts"], "0.1");
+
+    // Each edit carries everything written so far
+    let updates = api.calls_to("chat.update");
+    let last = &updates.last().unwrap().params;
+    assert_eq!(last["ts"], "2.1");
+    assert_eq!(last["text"], "Hello, world");
+    assert_eq!(last["blocks"][0]["text"]["text"], "Hello, world");
+    assert!(api.calls_to("chat.stopStream").is_empty());
+}
+
+#[tokio::test]
+async fn test_updates_are_throttled() {
+    let api = api_without_streaming().await;
+
+    let mut message = api
+        .client()
+        .chat()
+        .message_stream("C1")
+        .mode(StreamMode::Updates)
+        .update_interval(Duration::from_millis(200))
+        .start()
+        .await
+        .unwrap();
+    assert!(api.calls_to("chat.startStream").is_empty());
+    for token in tokens(20) {
+        message.write(token).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    message.finish().await.unwrap();
+
+    let updates = api.calls_to("chat.update");
+    assert!(updates.len() <= 3, "{} updates", updates.len());
+    assert_eq!(updates.last().unwrap().params["text"], tokens(20).concat());
+}
+
+#[tokio::test]
+async fn test_updates_spill_into_thread_replies() {
+    let api = api_without_streaming().await;
+    let chat = api.client().chat();
+
+    // Past the character limit
+    let words = "word ".repeat(1000);
+    let message = chat
+        .message_stream("C1")
+        .mode(StreamMode::Updates)
+        .update_interval(Duration::from_millis(10))
+        .start()
+        .await
+        .unwrap();
+    message.pipe(stream::iter([words.clone()])).await.unwrap();
+
+    let posts = api.calls_to("chat.postMessage");
+    assert_eq!(posts.len(), 2);
+    assert!(posts[0].params.get("thread_ts").is_none());
+    assert_eq!(posts[1].params["thread_ts"], "2.1");
+    let texts: Vec<_> = api
+        .calls_to("chat.update")
+        .into_iter()
+        .map(|c| c.params["text"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(texts.len(), 2);
+    assert!(texts[0].chars().count() <= MAX_MESSAGE_CHARS);
+    assert!(texts[0].ends_with("word"), "split mid-word");
+    assert_eq!(format!("{} {}", texts[0], texts[1]), words);
+
+    // Past the block limit with short paragraphs
+    let paragraphs: Vec<String> = (0..60).map(|i| format!("p{}\n\n", i)).collect();
+    let before = api.calls_to("chat.update").len();
+    chat.message_stream("C1")
+        .mode(StreamMode::Updates)
+        .update_interval(Duration::from_millis(10))
+        .start()
+        .await
+        .unwrap()
+        .pipe(stream::iter([paragraphs.concat()]))
+        .await
+        .unwrap();
+
+    let updates = &api.calls_to("chat.update")[before..];
+    assert_eq!(updates.len(), 2);
+    let blocks = updates[0].params["blocks"].as_array().unwrap();
+    assert_eq!(blocks.len(), MAX_MESSAGE_BLOCKS);
+    assert_eq!(blocks[49]["text"]["text"], "p49");
+    assert_eq!(updates[1].params["blocks"][0]["text"]["text"], "p50");
+    assert_eq!(updates[1].params["blocks"].as_array().unwrap().len(), 10);
+}
+
+#[tokio::test]
+async fn test_auto_returns_errors_unrelated_to_streaming() {
+    let api = api_without_streaming().await;
+    api.respond(
+        "chat.startStream",
+        json!({ "ok": false, "error": "channel_not_found" }),
+    );
+
+    let err = api
+        .client()
+        .chat()
+        .message_stream("C404")
+        .start()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, SlackError::ApiError { message, .. } if message == "channel_not_found"),
+        "{}",
+        err
+    );
+    assert!(api.calls_to("chat.postMessage").is_empty());
+}
+
+#[tokio::test]
+async fn test_updates_are_spaced_per_channel() {
+    let api = api_without_streaming().await;
+    let client = api.client();
+
+    let started = Instant::now();
+    let streams = ["a", "b"].map(|text| {
+        let chat = client.chat();
+        async move {
+            let mut message = chat
+                .message_stream("C1")
+                .mode(StreamMode::Updates)
+                .update_interval(Duration::from_millis(200))
+                .start()
+                .await
+                .unwrap();
+            message.write(text).await.unwrap();
+            message.finish().await.unwrap();
+        }
+    });
+    futures_util::future::join_all(streams).await;
+
+    // Two streams in one channel share the update interval
+    assert_eq!(api.calls_to("chat.update").len(), 2);
+    assert!(started.elapsed() >= Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn test_blank_text_replaces_placeholder() {
+    let api = api_without_streaming().await;
+
+    let mut message = api
+        .client()
+        .chat()
+        .message_stream("C1")
+        .mode(StreamMode::Updates)
+        .update_interval(Duration::from_millis(10))
+        .start()
+        .await
+        .unwrap();
+    message.write("  \n").await.unwrap();
+    message.finish().await.unwrap();
+
+    let updates = api.calls_to("chat.update");
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].params["ts"], "2.1");
+    assert_eq!(updates[0].params["text"], "  \n");
+    assert_eq!(updates[0].params["blocks"], json!([]));
+}
@@ -0,0 +1,144 @@
+//! Tests for thread handles
+//!
+//! These tests run against a local mock Web API and do not need Slack
+//! credentials.
+
+mod common;
+
+use common::{events_api, socket_event};
+use futures_util::{stream, StreamExt};
+use serde_json::{json, Value};
+use slacko::api::socket_mode::SocketModeEvent;
+use slacko::replay::MockApi;
+
+fn message(ts: &str, user: &str, text: &str) -> Value {
+    json!({ "type": "message", "ts": ts, "thread_ts": "1.0", "user": user, "text": text })
+}
+
+fn event(inner: Value) -> SocketModeEvent {
+    socket_event(events_api(inner)).0
+}
+
+/// Queue a two-page thread for the next load
+fn queue_pages(api: &MockApi) {
+    api.respond_once(
+        "conversations.replies",
+        json!({
+            "ok": true,
+            "has_more": true,
+            "messages": [message("1.0", "U1", "Root"), message("1.1", "U2", "First")],
+            "response_metadata": { "next_cursor": "page2" }
+        }),
+    );
+    api.respond_once(
+        "conversations.replies",
+        json!({
+            "ok": true,
+            "has_more": false,
+            "messages": [
+                message("1.2", "U1", "Second"),
+                { "type": "message", "subtype": "bot_message", "ts": "1.3", "thread_ts": "1.0", "bot_id": "B1", "text": "Third" }
+            ],
+            "response_metadata": { "next_cursor": "" }
+        }),
+    );
+}
+
+#[tokio::test]
+async fn test_load_follows_pages() {
+    let api = MockApi::start().await.unwrap();
+    queue_pages(&api);
+
+    let thread = api.client().conversations().thread("C1", "1.0");
+    let texts: Vec<_> = thread
+        .load()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.text)
+        .collect();
+    assert_eq!(texts, ["Root", "First", "Second", "Third"]);
+
+    let calls = api.calls_to("conversations.replies");
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].params["ts"], "1.0");
+    assert!(calls[0].params.get("cursor").is_none());
+    assert_eq!(calls[1].params["cursor"], "page2");
+}
+
+#[tokio::test]
+async fn test_participants_and_replies_span_pages() {
+    let api = MockApi::start().await.unwrap();
+    let thread = api.client().conversations().thread("C1", "1.0");
+
+    // U1 posts on both pages and is listed once, in order of first post
+    queue_pages(&api);
+    assert_eq!(thread.participants().await.unwrap(), ["U1", "U2", "B1"]);
+
+    // The root on the first page is left out
+    queue_pages(&api);
+    let replies: Vec<_> = thread
+        .replies()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.text)
+        .collect();
+    assert_eq!(replies, ["First", "Second", "Third"]);
+    assert_eq!(api.calls_to("conversations.replies").len(), 4);
+}
+
+#[tokio::test]
+async fn test_reply_broadcast_and_mark_read() {
+    let api = MockApi::start().await.unwrap();
+    api.respond(
+        "chat.postMessage",
+        json!({ "ok": true, "channel": "C1", "ts": "1.5", "message": { "type": "message", "ts": "1.5" } }),
+    );
+    let thread = api.client().conversations().thread("C1", "1.0");
+
+    thread.reply("Looking").await.unwrap();
+    thread.reply_broadcast("Fixed").await.unwrap();
+    thread.mark_read("1.5").await.unwrap();
+
+    let posts = api.calls_to("chat.postMessage");
+    assert_eq!(
+        posts[0].params,
+        json!({ "channel": "C1", "text": "Looking", "thread_ts": "1.0" })
+    );
+    assert_eq!(posts[1].params["thread_ts"], "1.0");
+    assert_eq!(posts[1].params["reply_broadcast"], true);
+    assert_eq!(
+        api.calls_to("subscriptions.thread.mark")[0].params,
+        json!({ "channel": "C1", "thread_ts": "1.0", "ts": "1.5", "read": true })
+    );
+}
+
+#[tokio::test]
+async fn test_follow_new_replies() {
+    let api = MockApi::start().await.unwrap();
+    let thread = api.client().conversations().thread("C1", "1.0");
+
+    let mut reply = message("1.4", "U3", "New reply");
+    reply["channel"] = json!("C1");
+    let mut other_thread = reply.clone();
+    other_thread["thread_ts"] = json!("9.0");
+    let mut other_channel = reply.clone();
+    other_channel["channel"] = json!("C2");
+    let mut root = reply.clone();
+    root["ts"] = json!("1.0");
+    let mut edited = reply.clone();
+    edited["subtype"] = json!("message_changed");
+
+    let events = [other_thread, other_channel, root, edited, reply.clone()]
+        .into_iter()
+        .map(event)
+        .chain([event(json!({ "type": "reaction_added", "user": "U1" }))]);
+    let replies: Vec<_> = thread.follow(stream::iter(events)).collect().await;
+
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0].ts, "1.4");
+    assert_eq!(replies[0].user.as_deref(), Some("U3"));
+    assert_eq!(replies[0].text, "New reply");
+    assert!(thread.reply_in(&event(reply)).is_some());
+}
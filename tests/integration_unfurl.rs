@@ -0,0 +1,193 @@
+//! Tests for link unfurling with `App::unfurl`
+//!
+//! These tests run against a local mock Web API and do not need Slack
+//! credentials.
+
+mod common;
+
+use common::events_api;
+use serde_json::{json, Value};
+use slacko::app::App;
+use slacko::replay::{MockApi, Replay};
+use slacko::unfurl::{LinkSharedEvent, Unfurl, UrlPattern};
+use slacko::SlackError;
+
+fn link_shared(links: &[&str], extra: Value) -> Value {
+    let mut event = json!({
+        "type": "link_shared",
+        "channel": "C1",
+        "user": "U1",
+        "message_ts": "1.2",
+        "links": links
+            .iter()
+            .map(|url| json!({ "url": url, "domain": "jira.example.com" }))
+            .collect::<Vec<_>>(),
+        "is_bot_user_member": true,
+        "event_ts": "1.3"
+    });
+    event
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    events_api(event)
+}
+
+fn jira_app(api: &MockApi) -> App {
+    App::new(api.client())
+        .unfurl(
+            "https://jira.example.com/browse/*",
+            |ctx, link| async move {
+                let issue = ctx.matches()[1].clone();
+                match issue.as_str() {
+                    "SECRET-1" => Ok(Unfurl::AuthRequired {
+                        message: None,
+                        url: Some("https://app.example.com/connect".into()),
+                        blocks: Some(vec![json!({
+                            "type": "section",
+                            "text": { "type": "mrkdwn", "text": "Connect Jira to see previews" }
+                        })]),
+                    }),
+                    "BROKEN-1" => Err(SlackError::Other("jira unavailable".into())),
+                    "SKIP-1" => Ok(Unfurl::Skip),
+                    _ => Ok(Unfurl::blocks(vec![json!({
+                        "type": "section",
+                        "text": { "type": "mrkdwn", "text": format!("*{}* <{}>", issue, link.url) }
+                    })])),
+                }
+            },
+        )
+        .unfurl("https://docs.example.com/*", |_, link| async move {
+            Ok(Unfurl::Attachment(
+                json!({ "title": "Docs", "title_link": link.url }),
+            ))
+        })
+}
+
+#[test]
+fn test_url_pattern() {
+    let pattern = UrlPattern::new("https://jira.example.com/browse/*");
+    assert_eq!(
+        pattern
+            .captures("https://jira.example.com/browse/ABC-12")
+            .unwrap(),
+        ["https://jira.example.com/browse/ABC-12", "ABC-12"]
+    );
+    assert!(!pattern.is_match("https://jira.example.com/projects/ABC"));
+    // Dots are literal and the whole URL must match
+    assert!(!pattern.is_match("https://jiraXexample.com/browse/A"));
+    assert!(!pattern.is_match("see https://jira.example.com/browse/A"));
+}
+
+#[tokio::test]
+async fn test_unfurls_matching_links_by_channel_and_ts() {
+    let api = MockApi::start().await.unwrap();
+    let envelope = link_shared(
+        &[
+            "https://jira.example.com/browse/ABC-1",
+            "https://docs.example.com/guide",
+            "https://jira.example.com/browse/SKIP-1",
+            "https://example.org/other",
+        ],
+        json!({}),
+    );
+    let event: LinkSharedEvent =
+        serde_json::from_value(envelope["payload"]["event"].clone()).unwrap();
+    assert!(!event.in_composer());
+
+    jira_app(&api)
+        .replay(Replay::from_envelopes([envelope]))
+        .await
+        .unwrap();
+
+    // One call for every matching route
+    let calls = api.calls_to("chat.unfurl");
+    assert_eq!(calls.len(), 1);
+    let params = &calls[0].params;
+    assert_eq!(params["channel"], "C1");
+    assert_eq!(params["ts"], "1.2");
+    assert!(params.get("unfurl_id").is_none());
+    let unfurls = params["unfurls"].as_object().unwrap();
+    assert_eq!(unfurls.len(), 2);
+    assert_eq!(
+        unfurls["https://jira.example.com/browse/ABC-1"]["blocks"][0]["text"]["text"],
+        "*ABC-1* <https://jira.example.com/browse/ABC-1>"
+    );
+    assert_eq!(unfurls["https://docs.example.com/guide"]["title"], "Docs");
+}
+
+#[tokio::test]
+async fn test_first_matching_pattern_unfurls_a_link() {
+    let api = MockApi::start().await.unwrap();
+    let app = jira_app(&api).unfurl("https://*.example.com/*", |_, _| async move {
+        Ok(Unfurl::Attachment(json!({ "title": "Example" })))
+    });
+
+    let envelope = link_shared(
+        &[
+            "https://jira.example.com/browse/ABC-3",
+            "https://status.example.com/now",
+        ],
+        json!({}),
+    );
+    app.replay(Replay::from_envelopes([envelope]))
+        .await
+        .unwrap();
+
+    let calls = api.calls_to("chat.unfurl");
+    assert_eq!(calls.len(), 1);
+    let unfurls = calls[0].params["unfurls"].as_object().unwrap();
+    assert_eq!(unfurls.len(), 2);
+    assert!(unfurls["https://jira.example.com/browse/ABC-3"]["blocks"].is_array());
+    assert_eq!(
+        unfurls["https://status.example.com/now"]["title"],
+        "Example"
+    );
+}
+
+#[tokio::test]
+async fn test_composer_links_use_unfurl_id_and_auth_flow() {
+    let api = MockApi::start().await.unwrap();
+    let app = jira_app(&api);
+
+    let envelope = link_shared(
+        &[
+            "https://jira.example.com/browse/ABC-2",
+            "https://jira.example.com/browse/SECRET-1",
+            "https://jira.example.com/browse/BROKEN-1",
+        ],
+        json!({
+            "channel": "COMPOSER",
+            "message_ts": "U1-1234",
+            "unfurl_id": "Uf1",
+            "source": "composer"
+        }),
+    );
+    app.replay(Replay::from_envelopes([envelope]))
+        .await
+        .unwrap();
+
+    // The unfurls and the auth prompt go in separate calls
+    let calls = api.calls_to("chat.unfurl");
+    assert_eq!(calls.len(), 2);
+    for call in &calls {
+        assert_eq!(call.params["unfurl_id"], "Uf1");
+        assert_eq!(call.params["source"], "composer");
+        assert!(call.params.get("channel").is_none());
+        assert!(call.params.get("ts").is_none());
+    }
+
+    // The failed link is skipped, the rest still unfurl
+    let params = &calls[0].params;
+    assert!(params.get("user_auth_required").is_none());
+    let urls: Vec<_> = params["unfurls"].as_object().unwrap().keys().collect();
+    assert_eq!(urls, ["https://jira.example.com/browse/ABC-2"]);
+
+    let params = &calls[1].params;
+    assert_eq!(params["user_auth_required"], true);
+    assert_eq!(params["user_auth_url"], "https://app.example.com/connect");
+    assert_eq!(
+        params["user_auth_blocks"][0]["text"]["text"],
+        "Connect Jira to see previews"
+    );
+    assert_eq!(params["unfurls"], json!({}));
+}